@@ -0,0 +1,24 @@
+use std::env;
+use std::io::Result;
+use std::path::PathBuf;
+
+fn main() -> Result<()> {
+    // Get the workspace root (parent of api/)
+    let workspace_root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+        .parent()
+        .unwrap()
+        .to_path_buf();
+
+    let proto_dir = workspace_root.join("proto");
+    let service_proto = proto_dir.join("beep/authz/v1/permission_service.proto");
+
+    // Build the server we expose, and the client for tests and other Rust consumers
+    tonic_build::configure()
+        .build_server(true)
+        .build_client(true)
+        .compile_protos(&[&service_proto], &[&proto_dir])?;
+
+    println!("cargo:rerun-if-changed={}", service_proto.display());
+
+    Ok(())
+}
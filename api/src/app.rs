@@ -0,0 +1,84 @@
+use authz_core::{
+    application::{AuthzService, create_repositories},
+    domain::common::CoreError,
+    infrastructure::common::permissions_translations::BeepPermissions,
+};
+use std::net::SocketAddr;
+use tracing::{debug, info, instrument};
+
+use crate::{
+    config::Config, grpc::PermissionGrpcService, http::router,
+    proto::permission_service_server::PermissionServiceServer,
+};
+
+pub struct App {
+    service: AuthzService,
+    grpc_address: SocketAddr,
+    http_address: SocketAddr,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AppError {
+    #[error("Repositories creation error: {0}")]
+    RepositoriesCreationError(CoreError),
+    #[error("gRPC server error: {0}")]
+    GrpcServerError(tonic::transport::Error),
+    #[error("HTTP server error: {0}")]
+    HttpServerError(std::io::Error),
+}
+
+impl App {
+    #[instrument(skip_all)]
+    pub async fn new(config: Config) -> Result<Self, AppError> {
+        info!("Initializing application");
+
+        debug!("Creating permissions descriptor");
+        let permissions_descriptor = BeepPermissions::new().descriptor();
+        info!("Permissions descriptor created successfully");
+
+        debug!("Creating authorization repositories");
+        let authz_repositories = create_repositories(
+            config.authzed_config,
+            &config.check_cache_config,
+            permissions_descriptor,
+        )
+        .await
+        .map_err(AppError::RepositoriesCreationError)?;
+        info!(
+            check_cache = config.check_cache_config.enabled,
+            "Authorization repositories created successfully"
+        );
+
+        info!("Application initialization complete");
+        Ok(Self {
+            service: authz_repositories.into(),
+            grpc_address: config.grpc_address,
+            http_address: config.http_address,
+        })
+    }
+
+    #[instrument(skip_all, fields(grpc_address = %self.grpc_address, http_address = %self.http_address))]
+    pub async fn start(self) -> Result<(), AppError> {
+        let http_listener = tokio::net::TcpListener::bind(self.http_address)
+            .await
+            .map_err(AppError::HttpServerError)?;
+
+        info!("Starting gRPC and HTTP servers");
+        let grpc = async {
+            tonic::transport::Server::builder()
+                .add_service(PermissionServiceServer::new(PermissionGrpcService::new(
+                    self.service.clone(),
+                )))
+                .serve(self.grpc_address)
+                .await
+                .map_err(AppError::GrpcServerError)
+        };
+        let http = async {
+            axum::serve(http_listener, router(self.service.clone()))
+                .await
+                .map_err(AppError::HttpServerError)
+        };
+
+        tokio::try_join!(grpc, http).map(|_| ())
+    }
+}
@@ -0,0 +1,24 @@
+use authz_core::infrastructure::{
+    authzed::AuthZedConfig, permission::repository::cached::CheckCacheConfig,
+};
+use clap::Parser;
+use std::net::SocketAddr;
+
+#[derive(Clone, Parser, Debug)]
+#[command(name = "authz-api")]
+#[command(about = "Authorization query API", long_about = None)]
+pub struct Config {
+    #[command(flatten)]
+    pub authzed_config: AuthZedConfig,
+
+    #[command(flatten)]
+    pub check_cache_config: CheckCacheConfig,
+
+    /// Address the gRPC server listens on
+    #[arg(long, env = "GRPC_ADDRESS", default_value = "0.0.0.0:50052")]
+    pub grpc_address: SocketAddr,
+
+    /// Address the HTTP/JSON server listens on
+    #[arg(long, env = "HTTP_ADDRESS", default_value = "0.0.0.0:8080")]
+    pub http_address: SocketAddr,
+}
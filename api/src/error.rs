@@ -0,0 +1,111 @@
+use authz_core::domain::{channel::ChannelError, permission::PermissionError};
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use thiserror::Error;
+use tonic::{Code, Status};
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("Invalid request: {msg}")]
+    InvalidRequest { msg: String },
+    #[error("{0}")]
+    PermissionError(#[from] PermissionError),
+    #[error("{0}")]
+    ChannelError(#[from] ChannelError),
+}
+
+impl ApiError {
+    pub fn invalid(msg: impl Into<String>) -> Self {
+        ApiError::InvalidRequest { msg: msg.into() }
+    }
+
+    /// gRPC code of the error, the HTTP status follows from it
+    fn code(&self) -> Code {
+        match self {
+            ApiError::InvalidRequest { .. }
+            | ApiError::PermissionError(PermissionError::InvalidPermission { .. }) => {
+                Code::InvalidArgument
+            }
+            ApiError::PermissionError(PermissionError::NotFound { .. }) => Code::NotFound,
+            ApiError::PermissionError(PermissionError::Unavailable { .. })
+            | ApiError::ChannelError(ChannelError::Unavailable { .. }) => Code::Unavailable,
+            _ => Code::Internal,
+        }
+    }
+}
+
+impl From<ApiError> for Status {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::InvalidRequest { msg } => Status::invalid_argument(msg),
+            e => Status::new(e.code(), e.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.code() {
+            Code::InvalidArgument => StatusCode::BAD_REQUEST,
+            Code::NotFound => StatusCode::NOT_FOUND,
+            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses(error: impl Fn() -> ApiError) -> (Code, StatusCode) {
+        (
+            Status::from(error()).code(),
+            error().into_response().status(),
+        )
+    }
+
+    #[test]
+    fn test_domain_errors_map_to_matching_statuses() {
+        assert_eq!(
+            statuses(|| PermissionError::InvalidPermission {
+                msg: "role r1 has no permission bitmask".to_string(),
+            }
+            .into()),
+            (Code::InvalidArgument, StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            statuses(|| PermissionError::NotFound {
+                msg: "channel c1 does not belong to any server".to_string(),
+            }
+            .into()),
+            (Code::NotFound, StatusCode::NOT_FOUND)
+        );
+        assert_eq!(
+            statuses(|| PermissionError::Unavailable {
+                msg: "connection refused".to_string(),
+            }
+            .into()),
+            (Code::Unavailable, StatusCode::SERVICE_UNAVAILABLE)
+        );
+        assert_eq!(
+            statuses(|| ChannelError::Unavailable {
+                msg: "connection refused".to_string(),
+            }
+            .into()),
+            (Code::Unavailable, StatusCode::SERVICE_UNAVAILABLE)
+        );
+        assert_eq!(
+            statuses(|| PermissionError::CheckPermissionError {
+                msg: "unknown permission".to_string(),
+            }
+            .into()),
+            (Code::Internal, StatusCode::INTERNAL_SERVER_ERROR)
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use authz_core::domain::{
+    common::entities::{Consistency, ZedToken},
+    permission::entities::{
+        CheckPermissionInput, LookupResourcesInput, LookupSubjectsInput, PermissionCheckResult,
+        PermissionExplanation, Resource, ResourceType, SubjectsLookup,
+    },
+};
+
+use crate::{
+    error::ApiError,
+    permissions::{check_input, resource_permission},
+    proto,
+};
+
+pub fn resource_type_from_proto(value: i32) -> Result<ResourceType, ApiError> {
+    match proto::ResourceType::try_from(value) {
+        Ok(proto::ResourceType::Server) => Ok(ResourceType::Server),
+        Ok(proto::ResourceType::Channel) => Ok(ResourceType::Channel),
+        Ok(proto::ResourceType::Role) => Ok(ResourceType::Role),
+        _ => Err(ApiError::invalid(format!(
+            "unknown resource type {}",
+            value
+        ))),
+    }
+}
+
+pub fn resource_from_proto(resource: Option<proto::Resource>) -> Result<Resource, ApiError> {
+    let resource = resource.ok_or_else(|| ApiError::invalid("resource is required"))?;
+    if resource.id.is_empty() {
+        return Err(ApiError::invalid("resource id is required"));
+    }
+    Ok(match resource_type_from_proto(resource.r#type)? {
+        ResourceType::Server => Resource::Server(resource.id),
+        ResourceType::Channel => Resource::Channel(resource.id),
+        ResourceType::Role => Resource::Role(resource.id),
+    })
+}
+
+pub fn consistency_from_proto(consistency: Option<proto::Consistency>) -> Consistency {
+    match consistency.and_then(|c| c.requirement) {
+        Some(proto::consistency::Requirement::AtLeastAsFresh(token)) => {
+            Consistency::AtLeastAsFresh(ZedToken(token))
+        }
+        Some(proto::consistency::Requirement::FullyConsistent(true)) => {
+            Consistency::FullyConsistent
+        }
+        _ => Consistency::MinimizeLatency,
+    }
+}
+
+impl From<PermissionCheckResult> for proto::CheckPermissionResponse {
+    fn from(result: PermissionCheckResult) -> Self {
+        match result {
+            PermissionCheckResult::Allowed => proto::CheckPermissionResponse {
+                permissionship: proto::Permissionship::Allowed.into(),
+                missing_context: Vec::new(),
+            },
+            PermissionCheckResult::Denied => proto::CheckPermissionResponse {
+                permissionship: proto::Permissionship::Denied.into(),
+                missing_context: Vec::new(),
+            },
+            PermissionCheckResult::Conditional { missing_context } => {
+                proto::CheckPermissionResponse {
+                    permissionship: proto::Permissionship::Conditional.into(),
+                    missing_context,
+                }
+            }
+        }
+    }
+}
+
+pub fn check_input_from_proto(
+    request: proto::CheckPermissionRequest,
+) -> Result<CheckPermissionInput, ApiError> {
+    let resource = resource_from_proto(request.resource)?;
+    check_input(
+        request.user_id,
+        resource,
+        &request.permission,
+        consistency_from_proto(request.consistency),
+    )
+}
+
+pub fn lookup_resources_input_from_proto(
+    request: proto::LookupResourcesRequest,
+) -> Result<LookupResourcesInput, ApiError> {
+    if request.user_id.is_empty() {
+        return Err(ApiError::invalid("user_id is required"));
+    }
+    let resource_type = resource_type_from_proto(request.resource_type)?;
+    let permission = resource_permission(resource_type, &request.permission)?;
+    Ok(LookupResourcesInput {
+        user_id: request.user_id,
+        permission,
+        consistency: consistency_from_proto(request.consistency),
+    })
+}
+
+pub fn lookup_subjects_input_from_proto(
+    request: proto::LookupSubjectsRequest,
+) -> Result<LookupSubjectsInput, ApiError> {
+    let resource = resource_from_proto(request.resource)?;
+    let permission = resource_permission(resource.resource_type(), &request.permission)?;
+    Ok(LookupSubjectsInput {
+        resource,
+        permission,
+        consistency: consistency_from_proto(request.consistency),
+    })
+}
+
+impl From<SubjectsLookup> for proto::LookupSubjectsResponse {
+    fn from(lookup: SubjectsLookup) -> Self {
+        proto::LookupSubjectsResponse {
+            user_ids: lookup.user_ids,
+            all_users: lookup.wildcard.is_some(),
+            excluded_user_ids: lookup
+                .wildcard
+                .map(|wildcard| wildcard.excluded_user_ids)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<PermissionExplanation> for proto::ExplainPermissionResponse {
+    fn from(explanation: PermissionExplanation) -> Self {
+        let summary = explanation.to_string();
+        let permissionship =
+            proto::CheckPermissionResponse::from(explanation.result).permissionship;
+        proto::ExplainPermissionResponse {
+            permissionship,
+            deciding_element: explanation.deciding_element.to_string(),
+            chain: explanation
+                .chain
+                .into_iter()
+                .map(|step| proto::ExplanationStep {
+                    object: step.object,
+                    name: step.name,
+                    granted: step.granted,
+                })
+                .collect(),
+            summary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use authz_core::domain::permission::entities::{
+        ChannelPermission, RolePermission, ServerPermission,
+    };
+
+    fn request(
+        resource_type: proto::ResourceType,
+        id: &str,
+        permission: &str,
+    ) -> proto::CheckPermissionRequest {
+        proto::CheckPermissionRequest {
+            user_id: "user_1".to_string(),
+            resource: Some(proto::Resource {
+                r#type: resource_type.into(),
+                id: id.to_string(),
+            }),
+            permission: permission.to_string(),
+            consistency: None,
+        }
+    }
+
+    #[test]
+    fn test_check_input_translates_beep_permission_names() {
+        let channel =
+            check_input_from_proto(request(proto::ResourceType::Channel, "c1", "view_channel"))
+                .unwrap();
+        assert_eq!(channel.resource, Resource::Channel("c1".to_string()));
+        assert_eq!(channel.permission, ChannelPermission::View.into());
+
+        let server =
+            check_input_from_proto(request(proto::ResourceType::Server, "s1", "admin")).unwrap();
+        assert_eq!(server.resource, Resource::Server("s1".to_string()));
+        assert_eq!(server.permission, ServerPermission::Admin.into());
+
+        let role = check_input_from_proto(request(proto::ResourceType::Role, "r1", "manage_role"))
+            .unwrap();
+        assert_eq!(role.permission, RolePermission::Manage.into());
+    }
+
+    #[test]
+    fn test_check_input_rejects_invalid_requests() {
+        let unknown_permission =
+            check_input_from_proto(request(proto::ResourceType::Channel, "c1", "admin"))
+                .unwrap_err();
+        assert!(matches!(
+            unknown_permission,
+            ApiError::InvalidRequest { .. }
+        ));
+
+        let unknown_type = check_input_from_proto(request(
+            proto::ResourceType::Unspecified,
+            "c1",
+            "send_message",
+        ))
+        .unwrap_err();
+        assert!(matches!(unknown_type, ApiError::InvalidRequest { .. }));
+
+        let mut missing_user = request(proto::ResourceType::Channel, "c1", "send_message");
+        missing_user.user_id = String::new();
+        assert!(matches!(
+            check_input_from_proto(missing_user),
+            Err(ApiError::InvalidRequest { .. })
+        ));
+    }
+
+    #[test]
+    fn test_conditional_result_keeps_missing_context() {
+        let response = proto::CheckPermissionResponse::from(PermissionCheckResult::Conditional {
+            missing_context: vec!["now".to_string()],
+        });
+        assert_eq!(
+            response.permissionship(),
+            proto::Permissionship::Conditional
+        );
+        assert_eq!(response.missing_context, vec!["now"]);
+    }
+
+    #[test]
+    fn test_check_input_keeps_requested_consistency() {
+        let mut fresh = request(proto::ResourceType::Channel, "c1", "send_message");
+        fresh.consistency = Some(proto::Consistency {
+            requirement: Some(proto::consistency::Requirement::AtLeastAsFresh(
+                "GhUKEzE3".to_string(),
+            )),
+        });
+
+        assert_eq!(
+            check_input_from_proto(fresh).unwrap().consistency,
+            Consistency::AtLeastAsFresh(ZedToken("GhUKEzE3".to_string()))
+        );
+        assert_eq!(
+            check_input_from_proto(request(proto::ResourceType::Channel, "c1", "send_message"))
+                .unwrap()
+                .consistency,
+            Consistency::MinimizeLatency
+        );
+    }
+}
@@ -0,0 +1,405 @@
+use authz_core::domain::permission::port::PermissionChecker;
+use tonic::{Request, Response, Status};
+use tracing::{info, instrument};
+
+use crate::{
+    error::ApiError,
+    proto::{self, check_bulk_permissions_result, permission_service_server::PermissionService},
+};
+
+pub mod entities;
+
+/// gRPC front of the permission checker, speaking Beep permission names
+#[derive(Clone)]
+pub struct PermissionGrpcService<S> {
+    service: S,
+}
+
+impl<S> PermissionGrpcService<S> {
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+#[tonic::async_trait]
+impl<S> PermissionService for PermissionGrpcService<S>
+where
+    S: PermissionChecker + 'static,
+{
+    #[instrument(skip_all)]
+    async fn check_permission(
+        &self,
+        request: Request<proto::CheckPermissionRequest>,
+    ) -> Result<Response<proto::CheckPermissionResponse>, Status> {
+        let input = entities::check_input_from_proto(request.into_inner())?;
+        info!(user_id = %input.user_id, resource = ?input.resource, permission = ?input.permission, "Checking permission over gRPC");
+
+        let result = self.service.check(input).await.map_err(ApiError::from)?;
+        Ok(Response::new(result.into()))
+    }
+
+    #[instrument(skip_all)]
+    async fn check_bulk_permissions(
+        &self,
+        request: Request<proto::CheckBulkPermissionsRequest>,
+    ) -> Result<Response<proto::CheckBulkPermissionsResponse>, Status> {
+        let requests = request.into_inner().items;
+        info!(
+            item_count = requests.len(),
+            "Checking permissions in bulk over gRPC"
+        );
+
+        // Invalid items get their own error; only the valid ones reach the checker
+        let mut results: Vec<proto::CheckBulkPermissionsResult> =
+            Vec::with_capacity(requests.len());
+        let mut inputs = Vec::new();
+        let mut positions = Vec::new();
+        for (index, item) in requests.into_iter().enumerate() {
+            let result = match entities::check_input_from_proto(item.clone()) {
+                Ok(input) => {
+                    inputs.push(input);
+                    positions.push(index);
+                    None
+                }
+                Err(e) => Some(check_bulk_permissions_result::Result::Error(e.to_string())),
+            };
+            results.push(proto::CheckBulkPermissionsResult {
+                request: Some(item),
+                result,
+            });
+        }
+
+        let items = self
+            .service
+            .check_bulk(inputs)
+            .await
+            .map_err(ApiError::from)?;
+        for (index, item) in positions.into_iter().zip(items) {
+            results[index].result = Some(match item.result {
+                Ok(result) => check_bulk_permissions_result::Result::Response(result.into()),
+                Err(e) => check_bulk_permissions_result::Result::Error(e.to_string()),
+            });
+        }
+
+        Ok(Response::new(proto::CheckBulkPermissionsResponse {
+            results,
+        }))
+    }
+
+    #[instrument(skip_all)]
+    async fn lookup_resources(
+        &self,
+        request: Request<proto::LookupResourcesRequest>,
+    ) -> Result<Response<proto::LookupResourcesResponse>, Status> {
+        let input = entities::lookup_resources_input_from_proto(request.into_inner())?;
+        info!(user_id = %input.user_id, permission = ?input.permission, "Looking up resources over gRPC");
+
+        let resource_ids = self
+            .service
+            .lookup_resources(input)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Response::new(proto::LookupResourcesResponse {
+            resource_ids,
+        }))
+    }
+
+    #[instrument(skip_all)]
+    async fn lookup_subjects(
+        &self,
+        request: Request<proto::LookupSubjectsRequest>,
+    ) -> Result<Response<proto::LookupSubjectsResponse>, Status> {
+        let input = entities::lookup_subjects_input_from_proto(request.into_inner())?;
+        info!(resource = ?input.resource, permission = ?input.permission, "Looking up subjects over gRPC");
+
+        let lookup = self
+            .service
+            .lookup_subjects(input)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Response::new(lookup.into()))
+    }
+
+    #[instrument(skip_all)]
+    async fn explain_permission(
+        &self,
+        request: Request<proto::CheckPermissionRequest>,
+    ) -> Result<Response<proto::ExplainPermissionResponse>, Status> {
+        let input = entities::check_input_from_proto(request.into_inner())?;
+        info!(user_id = %input.user_id, resource = ?input.resource, permission = ?input.permission, "Explaining permission over gRPC");
+
+        let explanation = self.service.explain(input).await.map_err(ApiError::from)?;
+        Ok(Response::new(explanation.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use authz_core::domain::permission::{
+        PermissionError,
+        entities::{
+            BulkCheckItem, ChannelPermission, CheckPermissionInput, DecidingElement,
+            EffectivePermissionsInput, ExplanationStep, LookupResourcesInput, LookupSubjectsInput,
+            PermissionCheckResult, PermissionExplanation, Resource, SubjectsLookup, WildcardGrant,
+        },
+    };
+    use std::sync::{Arc, Mutex};
+    use tonic::Code;
+
+    // In-process checker: channels are allowed, servers denied, "broken_" channels fail
+    #[derive(Clone, Default)]
+    struct FakePermissionChecker {
+        last_check_input: Arc<Mutex<Option<CheckPermissionInput>>>,
+        last_lookup_input: Arc<Mutex<Option<LookupResourcesInput>>>,
+    }
+
+    impl FakePermissionChecker {
+        fn answer(input: &CheckPermissionInput) -> Result<PermissionCheckResult, PermissionError> {
+            match &input.resource {
+                Resource::Channel(id) if id.starts_with("broken_") => {
+                    Err(PermissionError::CheckPermissionError {
+                        msg: format!("unknown channel {}", id),
+                    })
+                }
+                Resource::Channel(_) => Ok(PermissionCheckResult::Allowed),
+                _ => Ok(PermissionCheckResult::Denied),
+            }
+        }
+    }
+
+    impl PermissionChecker for FakePermissionChecker {
+        async fn check(
+            &self,
+            input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            *self.last_check_input.lock().unwrap() = Some(input.clone());
+            Self::answer(&input)
+        }
+
+        async fn check_bulk(
+            &self,
+            inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            Ok(inputs
+                .into_iter()
+                .map(|input| BulkCheckItem {
+                    result: Self::answer(&input),
+                    input,
+                })
+                .collect())
+        }
+
+        async fn lookup_resources(
+            &self,
+            input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            *self.last_lookup_input.lock().unwrap() = Some(input);
+            Ok(vec!["channel_1".to_string(), "channel_2".to_string()])
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup {
+                user_ids: vec!["user_1".to_string()],
+                wildcard: Some(WildcardGrant {
+                    excluded_user_ids: vec!["user_2".to_string()],
+                }),
+            })
+        }
+
+        async fn explain(
+            &self,
+            input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Ok(PermissionExplanation {
+                result: PermissionCheckResult::Denied,
+                deciding_element: DecidingElement::NoGrant,
+                chain: vec![ExplanationStep {
+                    object: input.resource.to_string(),
+                    name: "send_message".to_string(),
+                    granted: false,
+                }],
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
+        }
+    }
+
+    fn check_request(
+        resource_type: proto::ResourceType,
+        id: &str,
+        permission: &str,
+    ) -> proto::CheckPermissionRequest {
+        proto::CheckPermissionRequest {
+            user_id: "user_1".to_string(),
+            resource: Some(proto::Resource {
+                r#type: resource_type.into(),
+                id: id.to_string(),
+            }),
+            permission: permission.to_string(),
+            consistency: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_permission_uses_beep_names() {
+        // Arrange
+        let checker = FakePermissionChecker::default();
+        let grpc = PermissionGrpcService::new(checker.clone());
+
+        // Act
+        let response = grpc
+            .check_permission(Request::new(check_request(
+                proto::ResourceType::Channel,
+                "general",
+                "view_channel",
+            )))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // Assert
+        assert_eq!(response.permissionship(), proto::Permissionship::Allowed);
+        let input = checker.last_check_input.lock().unwrap().clone().unwrap();
+        assert_eq!(input.resource, Resource::Channel("general".to_string()));
+        assert_eq!(input.permission, ChannelPermission::View.into());
+    }
+
+    #[tokio::test]
+    async fn test_check_permission_rejects_unknown_permission() {
+        // Arrange
+        let grpc = PermissionGrpcService::new(FakePermissionChecker::default());
+
+        // Act
+        let status = grpc
+            .check_permission(Request::new(check_request(
+                proto::ResourceType::Channel,
+                "general",
+                "view",
+            )))
+            .await
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_check_bulk_reports_each_item() {
+        // Arrange
+        let grpc = PermissionGrpcService::new(FakePermissionChecker::default());
+        let items = vec![
+            check_request(proto::ResourceType::Channel, "general", "send_message"),
+            check_request(proto::ResourceType::Channel, "general", "manage_role"),
+            check_request(
+                proto::ResourceType::Channel,
+                "broken_channel",
+                "send_message",
+            ),
+            check_request(proto::ResourceType::Server, "server_1", "admin"),
+        ];
+
+        // Act
+        let results = grpc
+            .check_bulk_permissions(Request::new(proto::CheckBulkPermissionsRequest { items }))
+            .await
+            .unwrap()
+            .into_inner()
+            .results;
+
+        // Assert
+        assert_eq!(results.len(), 4);
+        assert!(matches!(
+            &results[0].result,
+            Some(check_bulk_permissions_result::Result::Response(r)) if r.permissionship() == proto::Permissionship::Allowed
+        ));
+        assert!(matches!(
+            &results[1].result,
+            Some(check_bulk_permissions_result::Result::Error(_))
+        ));
+        assert!(matches!(
+            &results[2].result,
+            Some(check_bulk_permissions_result::Result::Error(msg)) if msg.contains("broken_channel")
+        ));
+        assert!(matches!(
+            &results[3].result,
+            Some(check_bulk_permissions_result::Result::Response(r)) if r.permissionship() == proto::Permissionship::Denied
+        ));
+        assert_eq!(
+            results[1].request.as_ref().unwrap().permission,
+            "manage_role"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_resources_and_subjects() {
+        // Arrange
+        let checker = FakePermissionChecker::default();
+        let grpc = PermissionGrpcService::new(checker.clone());
+
+        // Act
+        let resources = grpc
+            .lookup_resources(Request::new(proto::LookupResourcesRequest {
+                user_id: "user_1".to_string(),
+                resource_type: proto::ResourceType::Channel.into(),
+                permission: "view_channel".to_string(),
+                consistency: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let subjects = grpc
+            .lookup_subjects(Request::new(proto::LookupSubjectsRequest {
+                resource: Some(proto::Resource {
+                    r#type: proto::ResourceType::Channel.into(),
+                    id: "general".to_string(),
+                }),
+                permission: "manage_message".to_string(),
+                consistency: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // Assert
+        assert_eq!(resources.resource_ids, vec!["channel_1", "channel_2"]);
+        let lookup_input = checker.last_lookup_input.lock().unwrap().clone().unwrap();
+        assert_eq!(lookup_input.permission, ChannelPermission::View.into());
+        assert_eq!(subjects.user_ids, vec!["user_1"]);
+        assert!(subjects.all_users);
+        assert_eq!(subjects.excluded_user_ids, vec!["user_2"]);
+    }
+
+    #[tokio::test]
+    async fn test_explain_permission_summary() {
+        // Arrange
+        let grpc = PermissionGrpcService::new(FakePermissionChecker::default());
+
+        // Act
+        let response = grpc
+            .explain_permission(Request::new(check_request(
+                proto::ResourceType::Channel,
+                "general",
+                "send_message",
+            )))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // Assert
+        assert_eq!(response.permissionship(), proto::Permissionship::Denied);
+        assert_eq!(response.chain.len(), 1);
+        assert_eq!(
+            response.summary,
+            "channel:general send_message (denied): decided by no role, grant or ownership applies"
+        );
+    }
+}
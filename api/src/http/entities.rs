@@ -0,0 +1,195 @@
+use authz_core::domain::{
+    common::entities::{Consistency, ZedToken},
+    permission::entities::{
+        CheckPermissionInput, PermissionCheckResult, PermissionExplanation, Resource,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ApiError, permissions::check_input};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceType {
+    Server,
+    Channel,
+    Role,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceBody {
+    #[serde(rename = "type")]
+    pub resource_type: ResourceType,
+    pub id: String,
+}
+
+impl TryFrom<ResourceBody> for Resource {
+    type Error = ApiError;
+
+    fn try_from(body: ResourceBody) -> Result<Self, Self::Error> {
+        if body.id.is_empty() {
+            return Err(ApiError::invalid("resource id is required"));
+        }
+        Ok(match body.resource_type {
+            ResourceType::Server => Resource::Server(body.id),
+            ResourceType::Channel => Resource::Channel(body.id),
+            ResourceType::Role => Resource::Role(body.id),
+        })
+    }
+}
+
+/// Freshness of an answer; by default SpiceDB may answer from a cached revision
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsistencyParams {
+    /// ZedToken of a write the answer must include
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at_least_as_fresh: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fully_consistent: bool,
+}
+
+impl TryFrom<ConsistencyParams> for Consistency {
+    type Error = ApiError;
+
+    fn try_from(params: ConsistencyParams) -> Result<Self, Self::Error> {
+        match (params.at_least_as_fresh, params.fully_consistent) {
+            (Some(_), true) => Err(ApiError::invalid(
+                "at_least_as_fresh and fully_consistent are mutually exclusive",
+            )),
+            (Some(token), false) => Ok(Consistency::AtLeastAsFresh(ZedToken(token))),
+            (None, true) => Ok(Consistency::FullyConsistent),
+            (None, false) => Ok(Consistency::MinimizeLatency),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRequest {
+    pub user_id: String,
+    pub resource: ResourceBody,
+    /// Beep capability name, e.g. "send_message"
+    pub permission: String,
+    #[serde(flatten)]
+    pub consistency: ConsistencyParams,
+}
+
+impl TryFrom<CheckRequest> for CheckPermissionInput {
+    type Error = ApiError;
+
+    fn try_from(request: CheckRequest) -> Result<Self, Self::Error> {
+        check_input(
+            request.user_id,
+            request.resource.try_into()?,
+            &request.permission,
+            request.consistency.try_into()?,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckOutcome {
+    Allowed,
+    Denied,
+    Conditional,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResponse {
+    /// Beep capability name, as requested
+    pub permission: String,
+    pub result: CheckOutcome,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_context: Vec<String>,
+}
+
+impl CheckResponse {
+    pub fn new(permission: String, result: PermissionCheckResult) -> Self {
+        let (result, missing_context) = match result {
+            PermissionCheckResult::Allowed => (CheckOutcome::Allowed, Vec::new()),
+            PermissionCheckResult::Denied => (CheckOutcome::Denied, Vec::new()),
+            PermissionCheckResult::Conditional { missing_context } => {
+                (CheckOutcome::Conditional, missing_context)
+            }
+        };
+        Self {
+            permission,
+            result,
+            missing_context,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkCheckRequest {
+    pub items: Vec<CheckRequest>,
+}
+
+/// One item of a bulk check: either a response or the error of that item alone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkCheckResult {
+    pub request: CheckRequest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<CheckResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkCheckResponse {
+    pub results: Vec<BulkCheckResult>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserQuery {
+    pub user: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelsResponse {
+    pub channel_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectivePermissionsResponse {
+    pub bitmask: u64,
+    /// Beep capability names set in the bitmask
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplanationStepBody {
+    pub object: String,
+    pub name: String,
+    pub granted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainResponse {
+    pub permission: String,
+    pub result: CheckOutcome,
+    pub deciding_element: String,
+    pub chain: Vec<ExplanationStepBody>,
+    pub summary: String,
+}
+
+impl ExplainResponse {
+    pub fn new(permission: String, explanation: PermissionExplanation) -> Self {
+        let summary = explanation.to_string();
+        Self {
+            result: CheckResponse::new(permission.clone(), explanation.result).result,
+            permission,
+            deciding_element: explanation.deciding_element.to_string(),
+            chain: explanation
+                .chain
+                .into_iter()
+                .map(|step| ExplanationStepBody {
+                    object: step.object,
+                    name: step.name,
+                    granted: step.granted,
+                })
+                .collect(),
+            summary,
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use authz_core::domain::{
+    channel::{entities::ListVisibleChannelsInput, port::ChannelService},
+    permission::{
+        entities::{CheckPermissionInput, EffectivePermissionsInput, Resource},
+        port::PermissionChecker,
+    },
+};
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    routing::{get, post},
+};
+use tracing::{info, instrument};
+
+use crate::{error::ApiError, permissions::permission_names};
+
+pub mod entities;
+
+use entities::{
+    BulkCheckRequest, BulkCheckResponse, BulkCheckResult, ChannelsResponse, CheckRequest,
+    CheckResponse, ConsistencyParams, EffectivePermissionsResponse, ExplainResponse, UserQuery,
+};
+
+/// JSON routes over the core service, speaking Beep permission names
+pub fn router<S>(service: S) -> Router
+where
+    S: PermissionChecker + ChannelService + Clone + 'static,
+{
+    Router::new()
+        .route("/check", post(check::<S>))
+        .route("/check/bulk", post(check_bulk::<S>))
+        .route("/explain", post(explain::<S>))
+        .route("/servers/:id/channels", get(visible_channels::<S>))
+        .route("/servers/:id/permissions", get(server_permissions::<S>))
+        .route("/channels/:id/permissions", get(channel_permissions::<S>))
+        .with_state(service)
+}
+
+#[instrument(skip_all)]
+async fn check<S: PermissionChecker>(
+    State(service): State<S>,
+    Json(request): Json<CheckRequest>,
+) -> Result<Json<CheckResponse>, ApiError> {
+    let permission = request.permission.clone();
+    let input = CheckPermissionInput::try_from(request)?;
+    info!(user_id = %input.user_id, resource = ?input.resource, permission = %permission, "Checking permission over HTTP");
+
+    let result = service.check(input).await?;
+    Ok(Json(CheckResponse::new(permission, result)))
+}
+
+#[instrument(skip_all)]
+async fn check_bulk<S: PermissionChecker>(
+    State(service): State<S>,
+    Json(request): Json<BulkCheckRequest>,
+) -> Result<Json<BulkCheckResponse>, ApiError> {
+    info!(
+        item_count = request.items.len(),
+        "Checking permissions in bulk over HTTP"
+    );
+
+    // Invalid items get their own error; only the valid ones reach the checker
+    let mut results = Vec::with_capacity(request.items.len());
+    let mut inputs = Vec::new();
+    let mut positions = Vec::new();
+    for (index, item) in request.items.into_iter().enumerate() {
+        let error = match CheckPermissionInput::try_from(item.clone()) {
+            Ok(input) => {
+                inputs.push(input);
+                positions.push(index);
+                None
+            }
+            Err(e) => Some(e.to_string()),
+        };
+        results.push(BulkCheckResult {
+            request: item,
+            response: None,
+            error,
+        });
+    }
+
+    let items = service.check_bulk(inputs).await?;
+    for (index, item) in positions.into_iter().zip(items) {
+        let result = &mut results[index];
+        match item.result {
+            Ok(outcome) => {
+                result.response = Some(CheckResponse::new(
+                    result.request.permission.clone(),
+                    outcome,
+                ))
+            }
+            Err(e) => result.error = Some(e.to_string()),
+        }
+    }
+
+    Ok(Json(BulkCheckResponse { results }))
+}
+
+#[instrument(skip_all)]
+async fn explain<S: PermissionChecker>(
+    State(service): State<S>,
+    Json(request): Json<CheckRequest>,
+) -> Result<Json<ExplainResponse>, ApiError> {
+    let permission = request.permission.clone();
+    let input = CheckPermissionInput::try_from(request)?;
+    info!(user_id = %input.user_id, resource = ?input.resource, permission = %permission, "Explaining permission over HTTP");
+
+    let explanation = service.explain(input).await?;
+    Ok(Json(ExplainResponse::new(permission, explanation)))
+}
+
+#[instrument(skip_all, fields(server_id = %server_id))]
+async fn visible_channels<S: ChannelService>(
+    State(service): State<S>,
+    Path(server_id): Path<String>,
+    Query(query): Query<UserQuery>,
+    Query(consistency): Query<ConsistencyParams>,
+) -> Result<Json<ChannelsResponse>, ApiError> {
+    info!(user_id = %query.user, "Listing visible channels over HTTP");
+
+    let channel_ids = service
+        .list_visible_channels(ListVisibleChannelsInput {
+            user_id: query.user,
+            server_id,
+            consistency: consistency.try_into()?,
+        })
+        .await?;
+    Ok(Json(ChannelsResponse { channel_ids }))
+}
+
+async fn effective_permissions<S: PermissionChecker>(
+    service: &S,
+    user_id: String,
+    resource: Resource,
+    consistency: ConsistencyParams,
+) -> Result<Json<EffectivePermissionsResponse>, ApiError> {
+    info!(user_id = %user_id, resource = ?resource, "Computing effective permissions over HTTP");
+
+    let bitmask = service
+        .effective_permissions(EffectivePermissionsInput {
+            user_id,
+            resource,
+            consistency: consistency.try_into()?,
+        })
+        .await?;
+    Ok(Json(EffectivePermissionsResponse {
+        bitmask,
+        permissions: permission_names(bitmask),
+    }))
+}
+
+#[instrument(skip_all, fields(server_id = %server_id))]
+async fn server_permissions<S: PermissionChecker>(
+    State(service): State<S>,
+    Path(server_id): Path<String>,
+    Query(query): Query<UserQuery>,
+    Query(consistency): Query<ConsistencyParams>,
+) -> Result<Json<EffectivePermissionsResponse>, ApiError> {
+    effective_permissions(
+        &service,
+        query.user,
+        Resource::Server(server_id),
+        consistency,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(channel_id = %channel_id))]
+async fn channel_permissions<S: PermissionChecker>(
+    State(service): State<S>,
+    Path(channel_id): Path<String>,
+    Query(query): Query<UserQuery>,
+    Query(consistency): Query<ConsistencyParams>,
+) -> Result<Json<EffectivePermissionsResponse>, ApiError> {
+    effective_permissions(
+        &service,
+        query.user,
+        Resource::Channel(channel_id),
+        consistency,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use authz_core::domain::{
+        channel::{
+            ChannelError,
+            entities::{CreateChannelInput, DeleteChannelInput},
+        },
+        common::entities::{Consistency, DeleteOutput, ZedToken},
+        permission::{
+            PermissionError,
+            entities::{
+                BulkCheckItem, ChannelPermission, DecidingElement, LookupResourcesInput,
+                LookupSubjectsInput, PermissionCheckResult, PermissionExplanation, SubjectsLookup,
+            },
+        },
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode, header},
+    };
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+
+    // In-process backend: channels are allowed, servers denied, "broken_" channels fail
+    #[derive(Clone, Default)]
+    struct FakeService {
+        last_check_input: Arc<Mutex<Option<CheckPermissionInput>>>,
+        last_visible_input: Arc<Mutex<Option<ListVisibleChannelsInput>>>,
+    }
+
+    impl FakeService {
+        fn answer(input: &CheckPermissionInput) -> Result<PermissionCheckResult, PermissionError> {
+            match &input.resource {
+                Resource::Channel(id) if id.starts_with("broken_") => {
+                    Err(PermissionError::CheckPermissionError {
+                        msg: format!("unknown channel {}", id),
+                    })
+                }
+                Resource::Channel(_) => Ok(PermissionCheckResult::Allowed),
+                _ => Ok(PermissionCheckResult::Denied),
+            }
+        }
+    }
+
+    impl PermissionChecker for FakeService {
+        async fn check(
+            &self,
+            input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            *self.last_check_input.lock().unwrap() = Some(input.clone());
+            Self::answer(&input)
+        }
+
+        async fn check_bulk(
+            &self,
+            inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            Ok(inputs
+                .into_iter()
+                .map(|input| BulkCheckItem {
+                    result: Self::answer(&input),
+                    input,
+                })
+                .collect())
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
+        }
+
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Ok(PermissionExplanation {
+                result: PermissionCheckResult::Denied,
+                deciding_element: DecidingElement::NoGrant,
+                chain: vec![],
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            match input.resource {
+                // view_channel | send_message
+                Resource::Channel(_) => Ok(0x40 | 0x80),
+                _ => Ok(0x1),
+            }
+        }
+    }
+
+    impl ChannelService for FakeService {
+        async fn create(&self, _input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 0,
+            })
+        }
+
+        async fn list_visible_channels(
+            &self,
+            input: ListVisibleChannelsInput,
+        ) -> Result<Vec<String>, ChannelError> {
+            *self.last_visible_input.lock().unwrap() = Some(input);
+            Ok(vec!["general".to_string()])
+        }
+    }
+
+    async fn send(service: FakeService, request: Request<Body>) -> (StatusCode, Value) {
+        let response = router(service).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn post_json(uri: &str, body: Value) -> Request<Body> {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn check_body(resource_type: &str, id: &str, permission: &str) -> Value {
+        json!({
+            "user_id": "user_1",
+            "resource": { "type": resource_type, "id": id },
+            "permission": permission,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_check_uses_beep_names() {
+        // Arrange
+        let service = FakeService::default();
+
+        // Act
+        let (status, body) = send(
+            service.clone(),
+            post_json("/check", check_body("channel", "general", "view_channel")),
+        )
+        .await;
+
+        // Assert
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["result"], "allowed");
+        assert_eq!(body["permission"], "view_channel");
+        let input = service.last_check_input.lock().unwrap().clone().unwrap();
+        assert_eq!(input.permission, ChannelPermission::View.into());
+        assert_eq!(input.consistency, Consistency::MinimizeLatency);
+    }
+
+    #[tokio::test]
+    async fn test_check_rejects_unknown_permission() {
+        // Act
+        let (status, body) = send(
+            FakeService::default(),
+            post_json("/check", check_body("channel", "general", "view")),
+        )
+        .await;
+
+        // Assert
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"].as_str().unwrap().contains("view"));
+    }
+
+    #[tokio::test]
+    async fn test_check_rejects_conflicting_consistency() {
+        // Arrange
+        let mut body = check_body("channel", "general", "view_channel");
+        body["at_least_as_fresh"] = json!("GhUKEzE3");
+        body["fully_consistent"] = json!(true);
+
+        // Act
+        let (status, _) = send(FakeService::default(), post_json("/check", body)).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_check_bulk_reports_each_item() {
+        // Arrange
+        let items = json!({ "items": [
+            check_body("channel", "general", "send_message"),
+            check_body("channel", "general", "manage_role"),
+            check_body("channel", "broken_channel", "send_message"),
+            check_body("server", "server_1", "admin"),
+        ]});
+
+        // Act
+        let (status, body) = send(FakeService::default(), post_json("/check/bulk", items)).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::OK);
+        let results = body["results"].as_array().unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0]["response"]["result"], "allowed");
+        assert!(results[1]["error"].is_string());
+        assert_eq!(results[1]["request"]["permission"], "manage_role");
+        assert!(
+            results[2]["error"]
+                .as_str()
+                .unwrap()
+                .contains("broken_channel")
+        );
+        assert_eq!(results[3]["response"]["result"], "denied");
+    }
+
+    #[tokio::test]
+    async fn test_visible_channels_and_effective_permissions() {
+        // Arrange
+        let service = FakeService::default();
+
+        // Act
+        let (channels_status, channels) = send(
+            service.clone(),
+            Request::get("/servers/server_1/channels?user=user_1&at_least_as_fresh=GhUKEzE3")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        let (permissions_status, permissions) = send(
+            service.clone(),
+            Request::get("/channels/general/permissions?user=user_1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        // Assert
+        assert_eq!(channels_status, StatusCode::OK);
+        assert_eq!(channels["channel_ids"], json!(["general"]));
+        let input = service.last_visible_input.lock().unwrap().clone().unwrap();
+        assert_eq!(input.server_id, "server_1");
+        assert_eq!(input.user_id, "user_1");
+        assert_eq!(
+            input.consistency,
+            Consistency::AtLeastAsFresh(ZedToken("GhUKEzE3".to_string()))
+        );
+        assert_eq!(permissions_status, StatusCode::OK);
+        assert_eq!(permissions["bitmask"], 0xC0);
+        assert_eq!(
+            permissions["permissions"],
+            json!(["view_channel", "send_message"])
+        );
+    }
+}
@@ -0,0 +1,14 @@
+// Include the generated protobuf code
+pub mod proto {
+    tonic::include_proto!("beep.authz.v1");
+}
+
+pub mod app;
+pub mod config;
+pub mod error;
+pub mod grpc;
+pub mod http;
+pub mod permissions;
+
+// Re-export commonly used items for tests
+pub use config::Config;
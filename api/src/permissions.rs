@@ -0,0 +1,107 @@
+use authz_core::{
+    domain::{
+        common::entities::Consistency,
+        permission::entities::{CheckPermissionInput, Permission, Resource, ResourceType},
+    },
+    infrastructure::common::{
+        permissions::{
+            permission_display_to_channel_permission, permission_display_to_role_permission,
+            permission_display_to_server_permission,
+        },
+        permissions_translations::BeepPermissions,
+    },
+};
+use permission_translation::models::CapilityHexValue;
+
+use crate::error::ApiError;
+
+/// Translate a Beep capability name to the permission checked on the resource type
+pub fn resource_permission(
+    resource_type: ResourceType,
+    beep_permission: &str,
+) -> Result<Permission, ApiError> {
+    let permission = match resource_type {
+        ResourceType::Server => {
+            permission_display_to_server_permission(beep_permission).map(Permission::from)
+        }
+        ResourceType::Channel => {
+            permission_display_to_channel_permission(beep_permission).map(Permission::from)
+        }
+        ResourceType::Role => {
+            permission_display_to_role_permission(beep_permission).map(Permission::from)
+        }
+    };
+    permission.ok_or_else(|| {
+        ApiError::invalid(format!(
+            "{} is not a {:?} permission",
+            beep_permission, resource_type
+        ))
+    })
+}
+
+/// Validate a check request expressed with a Beep capability name
+pub fn check_input(
+    user_id: String,
+    resource: Resource,
+    beep_permission: &str,
+    consistency: Consistency,
+) -> Result<CheckPermissionInput, ApiError> {
+    if user_id.is_empty() {
+        return Err(ApiError::invalid("user_id is required"));
+    }
+    let permission = resource_permission(resource.resource_type(), beep_permission)?;
+    Ok(CheckPermissionInput {
+        user_id,
+        resource,
+        permission,
+        consistency,
+    })
+}
+
+/// Beep capability names set in a bitmask, in `Permissions` order
+pub fn permission_names(bitmask: u64) -> Vec<String> {
+    BeepPermissions::all_permissions()
+        .into_iter()
+        .filter_map(|permission| {
+            let name = permission.to_string();
+            let value: CapilityHexValue = permission.into();
+            (bitmask & value as u64 != 0).then_some(name)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use authz_core::domain::permission::entities::{
+        ChannelPermission, RolePermission, ServerPermission,
+    };
+
+    #[test]
+    fn test_resource_permission_translates_beep_names() {
+        assert_eq!(
+            resource_permission(ResourceType::Channel, "view_channel").unwrap(),
+            ChannelPermission::View.into()
+        );
+        assert_eq!(
+            resource_permission(ResourceType::Server, "view_channel").unwrap(),
+            ServerPermission::ViewChannel.into()
+        );
+        assert_eq!(
+            resource_permission(ResourceType::Role, "manage_role").unwrap(),
+            RolePermission::Manage.into()
+        );
+        assert!(matches!(
+            resource_permission(ResourceType::Channel, "admin"),
+            Err(ApiError::InvalidRequest { .. })
+        ));
+    }
+
+    #[test]
+    fn test_permission_names() {
+        assert_eq!(permission_names(0xC0), vec!["view_channel", "send_message"]);
+        assert_eq!(permission_names(0x1), vec!["admin"]);
+        assert!(permission_names(0).is_empty());
+        assert_eq!(permission_names(0xFFF).len(), 12);
+    }
+}
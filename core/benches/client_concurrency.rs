@@ -0,0 +1,127 @@
+//! Check throughput of a shared `AuthZedClient` as the number of concurrent
+//! handlers grows, against an in-process SpiceDB stand-in answering every
+//! CheckPermission after a fixed latency.
+//!
+//! Run with `cargo bench -p authz_core --bench client_concurrency --features test-fakes`.
+
+use std::{
+    net::SocketAddr,
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
+
+use authz_core::{
+    authzed::api::v1::{
+        CheckPermissionRequest, CheckPermissionResponse, ObjectReference, SubjectReference,
+        check_permission_response::Permissionship,
+        permissions_service_server::{PermissionsService, PermissionsServiceServer},
+    },
+    domain::common::entities::Consistency,
+    infrastructure::authzed::{
+        AuthZedClient, AuthZedConfig, DEFAULT_BULK_CHECK_CHUNK_SIZE,
+        connection::AuthZedConnectionConfig, retry::RetryConfig, tls::AuthZedTlsConfig,
+    },
+};
+use tokio::net::TcpListener;
+use tonic::{
+    Request, Response, Status,
+    transport::{Server, server::TcpIncoming},
+};
+
+/// Simulated SpiceDB response time
+const LATENCY: Duration = Duration::from_millis(2);
+const REQUESTS_PER_HANDLER: usize = 50;
+const CONCURRENCY_LEVELS: [usize; 4] = [1, 8, 32, 128];
+const POOL_SIZES: [usize; 2] = [1, 4];
+
+struct FakeSpiceDb;
+
+#[tonic::async_trait]
+impl PermissionsService for FakeSpiceDb {
+    async fn check_permission(
+        &self,
+        _request: Request<CheckPermissionRequest>,
+    ) -> Result<Response<CheckPermissionResponse>, Status> {
+        tokio::time::sleep(LATENCY).await;
+        Ok(Response::new(CheckPermissionResponse {
+            permissionship: Permissionship::HasPermission as i32,
+            ..Default::default()
+        }))
+    }
+}
+
+async fn start_fake_spicedb() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(PermissionsServiceServer::new(FakeSpiceDb))
+            .serve_with_incoming(incoming),
+    );
+    addr
+}
+
+async fn checks_per_second(client: AuthZedClient, handlers: usize) -> f64 {
+    let start = Instant::now();
+    let tasks: Vec<_> = (0..handlers)
+        .map(|handler| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                for _ in 0..REQUESTS_PER_HANDLER {
+                    client
+                        .check_permission(
+                            ObjectReference {
+                                object_type: "server".to_string(),
+                                object_id: "server_1".to_string(),
+                            },
+                            "view",
+                            SubjectReference {
+                                object: Some(ObjectReference {
+                                    object_type: "user".to_string(),
+                                    object_id: format!("user_{}", handler),
+                                }),
+                                optional_relation: String::new(),
+                            },
+                            Consistency::MinimizeLatency,
+                        )
+                        .await
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    (handlers * REQUESTS_PER_HANDLER) as f64 / start.elapsed().as_secs_f64()
+}
+
+#[tokio::main]
+async fn main() {
+    let addr = start_fake_spicedb().await;
+
+    println!("{:>9} {:>9} {:>12}", "pool_size", "handlers", "checks/s");
+    for pool_size in POOL_SIZES {
+        let client = AuthZedClient::new(AuthZedConfig {
+            endpoint: addr.to_string(),
+            token: None,
+            tls: AuthZedTlsConfig::default(),
+            channel_pool_size: NonZeroUsize::new(pool_size).unwrap(),
+            bulk_check_chunk_size: DEFAULT_BULK_CHECK_CHUNK_SIZE,
+            retry: RetryConfig::default(),
+            connection: AuthZedConnectionConfig::default(),
+        })
+        .await
+        .unwrap();
+
+        // Warm up the HTTP/2 connections before measuring
+        checks_per_second(client.clone(), pool_size).await;
+
+        for handlers in CONCURRENCY_LEVELS {
+            let throughput = checks_per_second(client.clone(), handlers).await;
+            println!("{:>9} {:>9} {:>12.0}", pool_size, handlers, throughput);
+        }
+    }
+}
@@ -1,6 +1,16 @@
 use std::env;
-use std::io::Result;
-use std::path::PathBuf;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+
+// The runtime schema parser, shared so that generated names and startup checks
+// read beep.zed the same way
+#[path = "src/infrastructure/authzed/schema/parser.rs"]
+#[allow(dead_code)]
+mod parser;
+
+use parser::{Schema, SchemaDefinition};
 
 fn main() -> Result<()> {
     // Get the workspace root (parent of core/)
@@ -11,9 +21,13 @@ fn main() -> Result<()> {
 
     let proto_dir = workspace_root.join("proto");
 
+    // Servers only back the in-process SpiceDB fakes of tests and benches
+    let test_fakes = env::var_os("CARGO_FEATURE_TEST_FAKES").is_some();
+
     // Configure tonic-build to compile the AuthZed protobuf files
     tonic_build::configure()
-        .build_server(false) // We only need the client
+        .build_server(test_fakes)
+        .generate_default_stubs(test_fakes) // Fakes only implement the calls they exercise
         .build_client(true)
         .compile_well_known_types(true) // Include google.protobuf types
         .extern_path(".google.protobuf", "::prost_types") // Map google.protobuf to prost_types
@@ -33,5 +47,140 @@ fn main() -> Result<()> {
     // Rerun if proto files change
     println!("cargo:rerun-if-changed={}", proto_dir.display());
 
+    generate_schema_names(
+        &workspace_root.join("authzed/beep.zed"),
+        &PathBuf::from(env::var("OUT_DIR").unwrap()).join("beep_schema.rs"),
+    )?;
+
     Ok(())
 }
+
+/// Write typed definitions, relations and permissions of beep.zed, included by
+/// `infrastructure::common::authzed::beep`
+fn generate_schema_names(schema_path: &Path, out_path: &Path) -> Result<()> {
+    println!("cargo:rerun-if-changed={}", schema_path.display());
+    println!("cargo:rerun-if-changed=src/infrastructure/authzed/schema/parser.rs");
+
+    let schema = Schema::parse(&fs::read_to_string(schema_path)?)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("beep.zed: {}", e)))?;
+
+    let mut out =
+        String::from("// Generated by core/build.rs from authzed/beep.zed, do not edit\n\n");
+    let definitions: Vec<&str> = schema
+        .definitions
+        .iter()
+        .map(|definition| definition.name.as_str())
+        .collect();
+    write_name_enum(
+        &mut out,
+        "Definition",
+        "A definition of beep.zed",
+        &definitions,
+    );
+    for definition in &schema.definitions {
+        write_definition_module(&mut out, definition);
+    }
+
+    fs::write(out_path, out)
+}
+
+fn write_definition_module(out: &mut String, definition: &SchemaDefinition) {
+    let relations: Vec<&str> = definition
+        .relations
+        .iter()
+        .map(|relation| relation.name.as_str())
+        .collect();
+    let permissions: Vec<&str> = definition
+        .permissions
+        .iter()
+        .map(|permission| permission.name.as_str())
+        .collect();
+
+    writeln!(out, "/// Names of `definition {}`", definition.name).unwrap();
+    writeln!(out, "pub mod {} {{", definition.name).unwrap();
+    writeln!(out, "pub const NAME: &str = {:?};\n", definition.name).unwrap();
+    write_name_enum(out, "Relation", "A relation", &relations);
+    writeln!(out, "impl Relation {{").unwrap();
+    writeln!(
+        out,
+        "/// Subjects the relation accepts as written in beep.zed, e.g. \"role#member\""
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "pub const fn subject_types(self) -> &'static [&'static str] {{"
+    )
+    .unwrap();
+    writeln!(out, "match self {{").unwrap();
+    for relation in &definition.relations {
+        writeln!(
+            out,
+            "Relation::{} => &{:?},",
+            variant_name(&relation.name),
+            relation.subject_types
+        )
+        .unwrap();
+    }
+    writeln!(out, "}}\n}}\n}}\n").unwrap();
+    write_name_enum(out, "Permission", "A permission", &permissions);
+    writeln!(out, "}}\n").unwrap();
+}
+
+/// An enum of schema names with `ALL`, `as_str`, `from_name`, `Display` and
+/// `From<_> for String`
+fn write_name_enum(out: &mut String, name: &str, doc: &str, values: &[&str]) {
+    writeln!(out, "/// {}", doc).unwrap();
+    writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]").unwrap();
+    writeln!(out, "pub enum {} {{", name).unwrap();
+    for value in values {
+        writeln!(out, "{},", variant_name(value)).unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    writeln!(out, "impl {} {{", name).unwrap();
+    writeln!(out, "pub const ALL: &[{}] = &[", name).unwrap();
+    for value in values {
+        writeln!(out, "{}::{},", name, variant_name(value)).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+    writeln!(out, "pub const fn as_str(self) -> &'static str {{").unwrap();
+    writeln!(out, "match self {{").unwrap();
+    for value in values {
+        writeln!(out, "{}::{} => {:?},", name, variant_name(value), value).unwrap();
+    }
+    writeln!(out, "}}\n}}\n").unwrap();
+    writeln!(out, "pub fn from_name(name: &str) -> Option<Self> {{").unwrap();
+    writeln!(
+        out,
+        "Self::ALL.iter().copied().find(|value| value.as_str() == name)"
+    )
+    .unwrap();
+    writeln!(out, "}}\n}}\n").unwrap();
+
+    writeln!(out, "impl From<{}> for String {{", name).unwrap();
+    writeln!(out, "fn from(value: {}) -> Self {{", name).unwrap();
+    writeln!(out, "value.as_str().to_string()").unwrap();
+    writeln!(out, "}}\n}}\n").unwrap();
+
+    writeln!(out, "impl std::fmt::Display for {} {{", name).unwrap();
+    writeln!(
+        out,
+        "fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{"
+    )
+    .unwrap();
+    writeln!(out, "f.write_str(self.as_str())").unwrap();
+    writeln!(out, "}}\n}}\n").unwrap();
+}
+
+/// `view_channel_grant` -> `ViewChannelGrant`
+fn variant_name(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -0,0 +1,167 @@
+//! Relationships of an existing community, built for an initial bulk import with
+//! the same entity builders the repositories use
+
+use std::collections::HashSet;
+
+use permission_translation::models::CapabilityDescriptor;
+
+use crate::{
+    authzed::api::v1::Relationship,
+    domain::{
+        channel::entities::CreateChannelInput,
+        permission_override::entities::CreatePermissionOverrideInput,
+        role::entities::{AssignMemberInput, CreateRoleInput},
+        server::entities::CreateServerInput,
+    },
+    infrastructure::{
+        permission_override::repository::authzed::entities::create_override_relationships,
+        role::repository::authzed::entities::{
+            assign_member_to_relationship, create_role_permission_relationships,
+            create_role_server_relationship,
+        },
+    },
+};
+
+/// One object of the community to import
+#[derive(Debug, Clone)]
+pub enum ImportRecord {
+    Server(CreateServerInput),
+    Channel(CreateChannelInput),
+    Role(CreateRoleInput),
+    Member(AssignMemberInput),
+    PermissionOverride(CreatePermissionOverrideInput),
+}
+
+impl ImportRecord {
+    /// The relationships the repository would write when creating this object
+    pub fn relationships(self, descriptor: &CapabilityDescriptor) -> Vec<Relationship> {
+        match self {
+            ImportRecord::Server(input) => vec![input.into()],
+            ImportRecord::Channel(input) => vec![input.into()],
+            ImportRecord::Role(input) => {
+                let mut relationships = vec![create_role_server_relationship(&input)];
+                relationships.extend(create_role_permission_relationships(&input, descriptor));
+                relationships
+            }
+            ImportRecord::Member(input) => vec![assign_member_to_relationship(&input)],
+            ImportRecord::PermissionOverride(input) => {
+                create_override_relationships(&input, descriptor)
+            }
+        }
+    }
+}
+
+/// Relationships of every record, in order and without duplicates, which a bulk
+/// import would reject
+pub fn relationships_to_import(
+    records: impl IntoIterator<Item = ImportRecord>,
+    descriptor: &CapabilityDescriptor,
+) -> Vec<Relationship> {
+    let mut seen = HashSet::new();
+    records
+        .into_iter()
+        .flat_map(|record| record.relationships(descriptor))
+        .filter(|relationship| seen.insert(relationship_key(relationship)))
+        .collect()
+}
+
+/// `type:id#relation@type:id#relation`, caveat and expiration aside
+fn relationship_key(relationship: &Relationship) -> String {
+    let resource = relationship.resource.clone().unwrap_or_default();
+    let subject = relationship.subject.clone().unwrap_or_default();
+    let subject_object = subject.object.unwrap_or_default();
+    format!(
+        "{}:{}#{}@{}:{}#{}",
+        resource.object_type,
+        resource.object_id,
+        relationship.relation,
+        subject_object.object_type,
+        subject_object.object_id,
+        subject.optional_relation
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::permission_override::entities::OverrideTarget;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        let mut descriptor = CapabilityDescriptor::new();
+        descriptor.insert("admin".to_string(), 0x1);
+        descriptor.insert("view_channel".to_string(), 0x40);
+        descriptor.insert("send_message".to_string(), 0x80);
+        descriptor
+    }
+
+    #[test]
+    fn test_relationships_to_import_builds_every_record() {
+        // Arrange
+        let records = vec![
+            ImportRecord::Server(CreateServerInput {
+                owner_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+            }),
+            ImportRecord::Channel(CreateChannelInput {
+                channel_id: "channel_1".to_string(),
+                server_id: "server_1".to_string(),
+            }),
+            ImportRecord::Role(CreateRoleInput {
+                role_id: "role_1".to_string(),
+                server_id: "server_1".to_string(),
+                permissions_bitmask: 0x81, // admin | send_message
+            }),
+            ImportRecord::Member(AssignMemberInput {
+                user_id: "user_2".to_string(),
+                role_id: "role_1".to_string(),
+            }),
+            ImportRecord::PermissionOverride(CreatePermissionOverrideInput {
+                override_id: "override_1".to_string(),
+                channel_id: "channel_1".to_string(),
+                permission_bitmask: 0x40, // view_channel
+                is_allow: false,
+                target: OverrideTarget::Role("role_1".to_string()),
+            }),
+        ];
+
+        // Act
+        let relationships = relationships_to_import(records, &create_test_descriptor());
+
+        // Assert
+        let mut relations: Vec<_> = relationships
+            .iter()
+            .map(|relationship| relationship.relation.as_str())
+            .collect();
+        relations.sort();
+        assert_eq!(
+            relations,
+            vec![
+                "administrator",
+                "channel",
+                "denied_to",
+                "member",
+                "message_sender",
+                "owner",
+                "server",
+                "server",
+                "view_channel_deny",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relationships_to_import_skips_duplicates() {
+        // Arrange
+        let member = ImportRecord::Member(AssignMemberInput {
+            user_id: "user_2".to_string(),
+            role_id: "role_1".to_string(),
+        });
+
+        // Act
+        let relationships =
+            relationships_to_import(vec![member.clone(), member], &create_test_descriptor());
+
+        // Assert
+        assert_eq!(relationships.len(), 1);
+    }
+}
@@ -1,21 +1,36 @@
+pub mod import;
+
 use crate::{
     domain::common::{CoreError, service::Service},
     infrastructure::{
-        authzed::{AuthZedClient, AuthZedConfig},
+        authzed::{
+            AuthZedClient, AuthZedConfig,
+            schema::{BEEP_SCHEMA, Schema, SchemaConfig, SchemaDifference, SchemaMode, diff},
+        },
         channel::repository::authzed::AuthzedChannelRepository,
+        common::schema_requirements::validate,
+        permission::repository::{
+            authzed::AuthzedPermissionRepository,
+            cached::{
+                CachedPermissionRepository, CheckCache, CheckCacheConfig, spawn_invalidation,
+                spawn_stats_logging,
+            },
+        },
         permission_override::repository::authzed::AuthzedPermissionOverrideRepository,
         role::repository::authzed::AuthzedRoleRepository,
         server::repository::authzed::AuthzedServerRepository,
     },
 };
 use permission_translation::models::CapabilityDescriptor;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+use tracing::{error, info, warn};
 
 pub type AuthzService = Service<
     AuthzedServerRepository,
     AuthzedChannelRepository,
     AuthzedRoleRepository,
     AuthzedPermissionOverrideRepository,
+    CachedPermissionRepository<AuthzedPermissionRepository>,
 >;
 
 pub struct AuthzRepositories {
@@ -24,10 +39,14 @@ pub struct AuthzRepositories {
     pub channel_repository: AuthzedChannelRepository,
     pub role_repository: AuthzedRoleRepository,
     pub permission_override_repository: AuthzedPermissionOverrideRepository,
+    pub permission_repository: CachedPermissionRepository<AuthzedPermissionRepository>,
 }
 
+/// Connect to SpiceDB and build the repositories over it. With caching enabled the
+/// check cache follows the Watch API; must be called from within a Tokio runtime.
 pub async fn create_repositories(
     authzed_config: AuthZedConfig,
+    check_cache_config: &CheckCacheConfig,
     permissions_descriptor: Arc<CapabilityDescriptor>,
 ) -> Result<AuthzRepositories, CoreError> {
     let authzed_client = AuthZedClient::new(authzed_config)
@@ -41,16 +60,136 @@ pub async fn create_repositories(
         authzed_client.clone(),
         permissions_descriptor.clone(),
     );
+    let check_cache = check_cache_config.enabled.then(|| {
+        let cache = Arc::new(CheckCache::from_config(check_cache_config));
+        spawn_invalidation(authzed_client.clone(), cache.clone());
+        if check_cache_config.stats_interval_secs > 0 {
+            spawn_stats_logging(
+                cache.clone(),
+                Duration::from_secs(check_cache_config.stats_interval_secs),
+            );
+        }
+        cache
+    });
+    let permission_repository = CachedPermissionRepository::new(
+        AuthzedPermissionRepository::new(authzed_client.clone(), permissions_descriptor.clone()),
+        check_cache,
+    );
     let authz_repositories = AuthzRepositories {
         authzed_client,
         server_repository,
         channel_repository,
         role_repository,
         permission_override_repository,
+        permission_repository,
     };
     Ok(authz_repositories)
 }
 
+impl AuthzRepositories {
+    /// Compare the live SpiceDB schema with the embedded beep.zed and log the
+    /// differences. In verify mode a mismatch fails startup; in apply mode
+    /// beep.zed is written instead. Then check that every relation and
+    /// permission the repositories use exists in the resulting schema.
+    pub async fn ensure_schema(&self, config: &SchemaConfig) -> Result<(), CoreError> {
+        if config.mode == SchemaMode::Skip {
+            info!("Schema verification skipped");
+            return Ok(());
+        }
+
+        let live = self.sync_schema(config.mode).await?;
+        self.validate_mappings(&live)
+    }
+
+    /// The schema SpiceDB runs once beep.zed was compared (and maybe applied)
+    async fn sync_schema(&self, mode: SchemaMode) -> Result<Schema, CoreError> {
+        let startup_error = |msg: String| CoreError::StartupError { msg };
+        let expected = Schema::parse(BEEP_SCHEMA)
+            .map_err(|e| startup_error(format!("Embedded beep.zed is invalid: {}", e)))?;
+        let live = self
+            .authzed_client
+            .read_schema()
+            .await
+            .map_err(|e| startup_error(e.to_string()))?;
+
+        let (actual, differences) = match live {
+            Some(live) => {
+                let actual = Schema::parse(&live)
+                    .map_err(|e| startup_error(format!("Live schema is invalid: {}", e)))?;
+                let differences = diff(&expected, &actual);
+                (actual, differences)
+            }
+            None => {
+                warn!("SpiceDB has no schema");
+                let differences = expected
+                    .definitions
+                    .iter()
+                    .map(|definition| SchemaDifference::MissingDefinition {
+                        definition: definition.name.clone(),
+                    })
+                    .collect();
+                (Schema::default(), differences)
+            }
+        };
+
+        if differences.is_empty() {
+            info!("Live schema matches beep.zed");
+            return Ok(actual);
+        }
+        for difference in &differences {
+            warn!(%difference, "Live schema differs from beep.zed");
+        }
+
+        match mode {
+            SchemaMode::Apply => {
+                let written_at = self
+                    .authzed_client
+                    .write_schema(BEEP_SCHEMA)
+                    .await
+                    .map_err(|e| startup_error(e.to_string()))?;
+                info!(written_at = %written_at.token, "beep.zed applied to SpiceDB");
+                Ok(expected)
+            }
+            _ => {
+                error!(
+                    difference_count = differences.len(),
+                    "Live schema does not match beep.zed, set SCHEMA_MODE=apply to update it"
+                );
+                Err(startup_error(format!(
+                    "Live schema differs from beep.zed: {}",
+                    join(&differences)
+                )))
+            }
+        }
+    }
+
+    fn validate_mappings(&self, schema: &Schema) -> Result<(), CoreError> {
+        let violations = validate(schema, &self.role_repository.permissions_descriptor);
+        if violations.is_empty() {
+            info!("Relation mappings match the live schema");
+            return Ok(());
+        }
+
+        for violation in &violations {
+            error!(%violation, "Relation mapping does not match the live schema");
+        }
+        Err(CoreError::StartupError {
+            msg: format!(
+                "Relation mappings do not match the live schema: {}",
+                join(&violations)
+            ),
+        })
+    }
+}
+
+fn join(items: &[impl ToString]) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 impl Into<AuthzService> for AuthzRepositories {
     fn into(self) -> AuthzService {
         AuthzService {
@@ -58,6 +197,7 @@ impl Into<AuthzService> for AuthzRepositories {
             channel_repository: self.channel_repository,
             role_repository: self.role_repository,
             permission_override_repository: self.permission_override_repository,
+            permission_repository: self.permission_repository,
         }
     }
 }
@@ -1,3 +1,5 @@
+use crate::domain::common::entities::Consistency;
+
 #[derive(Debug, Clone)]
 pub struct CreateChannelInput {
     pub channel_id: String,
@@ -8,3 +10,18 @@ pub struct CreateChannelInput {
 pub struct DeleteChannelInput {
     pub channel_id: String,
 }
+
+#[derive(Debug, Clone)]
+pub struct ListServerChannelsInput {
+    pub server_id: String,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListVisibleChannelsInput {
+    pub user_id: String,
+    pub server_id: String,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
@@ -10,4 +10,9 @@ pub enum ChannelError {
     CreateChannelError { msg: String },
     #[error("Delete channel error: {msg}")]
     DeleteChannelError { msg: String },
+    #[error("List channels error: {msg}")]
+    ListChannelsError { msg: String },
+    /// The permission store could not answer for now; the same call may succeed later
+    #[error("Channels unavailable: {msg}")]
+    Unavailable { msg: String },
 }
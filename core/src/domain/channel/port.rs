@@ -1,14 +1,41 @@
-use crate::domain::channel::{
-    ChannelError,
-    entities::{CreateChannelInput, DeleteChannelInput},
+use crate::domain::{
+    channel::{
+        ChannelError,
+        entities::{
+            CreateChannelInput, DeleteChannelInput, ListServerChannelsInput,
+            ListVisibleChannelsInput,
+        },
+    },
+    common::entities::{DeleteOutput, ZedToken},
 };
 
 pub trait ChannelRepository: Send + Sync {
-    fn create(&self, input: CreateChannelInput) -> impl Future<Output = Result<(), ChannelError>>;
-    fn delete(&self, input: DeleteChannelInput) -> impl Future<Output = Result<(), ChannelError>>;
+    fn create(
+        &self,
+        input: CreateChannelInput,
+    ) -> impl Future<Output = Result<ZedToken, ChannelError>>;
+    fn delete(
+        &self,
+        input: DeleteChannelInput,
+    ) -> impl Future<Output = Result<DeleteOutput, ChannelError>>;
+    fn list_by_server(
+        &self,
+        input: ListServerChannelsInput,
+    ) -> impl Future<Output = Result<Vec<String>, ChannelError>> + Send;
 }
 
 pub trait ChannelService: Send + Sync {
-    fn create(&self, input: CreateChannelInput) -> impl Future<Output = Result<(), ChannelError>>;
-    fn delete(&self, input: DeleteChannelInput) -> impl Future<Output = Result<(), ChannelError>>;
+    fn create(
+        &self,
+        input: CreateChannelInput,
+    ) -> impl Future<Output = Result<ZedToken, ChannelError>>;
+    fn delete(
+        &self,
+        input: DeleteChannelInput,
+    ) -> impl Future<Output = Result<DeleteOutput, ChannelError>>;
+    /// IDs of the channels of a server the user can view
+    fn list_visible_channels(
+        &self,
+        input: ListVisibleChannelsInput,
+    ) -> impl Future<Output = Result<Vec<String>, ChannelError>> + Send;
 }
@@ -1,25 +1,37 @@
 use crate::domain::{
     channel::{
         ChannelError,
-        entities::{CreateChannelInput, DeleteChannelInput},
+        entities::{
+            CreateChannelInput, DeleteChannelInput, ListServerChannelsInput,
+            ListVisibleChannelsInput,
+        },
         port::{ChannelRepository, ChannelService},
     },
-    common::service::Service,
+    common::{
+        entities::{DeleteOutput, ZedToken},
+        service::Service,
+    },
+    permission::{
+        PermissionError,
+        entities::{ChannelPermission, CheckPermissionInput, PermissionCheckResult, Resource},
+        port::PermissionRepository,
+    },
     permission_override::port::PermissionOverrideRepository,
     role::port::RoleRepository,
     server::port::ServerRepository,
 };
 use tracing::{info, instrument};
 
-impl<S, C, R, P> ChannelService for Service<S, C, R, P>
+impl<S, C, R, P, A> ChannelService for Service<S, C, R, P, A>
 where
     S: ServerRepository,
     C: ChannelRepository,
     R: RoleRepository,
     P: PermissionOverrideRepository,
+    A: PermissionRepository,
 {
     #[instrument(skip(self), fields(channel_id = %input.channel_id, server_id = %input.server_id))]
-    async fn create(&self, input: CreateChannelInput) -> Result<(), ChannelError> {
+    async fn create(&self, input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
         info!(
             channel_id = %input.channel_id,
             server_id = %input.server_id,
@@ -34,7 +46,7 @@ where
     }
 
     #[instrument(skip(self), fields(channel_id = %input.channel_id))]
-    async fn delete(&self, input: DeleteChannelInput) -> Result<(), ChannelError> {
+    async fn delete(&self, input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
         info!(
             channel_id = %input.channel_id,
             "Deleting channel in domain service"
@@ -46,12 +58,83 @@ where
         }
         result
     }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, server_id = %input.server_id))]
+    async fn list_visible_channels(
+        &self,
+        input: ListVisibleChannelsInput,
+    ) -> Result<Vec<String>, ChannelError> {
+        info!(
+            user_id = %input.user_id,
+            server_id = %input.server_id,
+            "Listing visible channels in domain service"
+        );
+
+        let server_channels = self
+            .channel_repository
+            .list_by_server(ListServerChannelsInput {
+                server_id: input.server_id,
+                consistency: input.consistency.clone(),
+            })
+            .await?;
+        if server_channels.is_empty() {
+            return Ok(server_channels);
+        }
+
+        // One bulk check over the server's channels only
+        let checks: Vec<CheckPermissionInput> = server_channels
+            .into_iter()
+            .map(|channel_id| CheckPermissionInput {
+                user_id: input.user_id.clone(),
+                resource: Resource::Channel(channel_id),
+                permission: ChannelPermission::View.into(),
+                consistency: input.consistency.clone(),
+            })
+            .collect();
+        let items = self
+            .permission_repository
+            .check_bulk(checks)
+            .await
+            .map_err(|e| match e {
+                PermissionError::Unavailable { msg } => ChannelError::Unavailable { msg },
+                e => ChannelError::ListChannelsError { msg: e.to_string() },
+            })?;
+
+        let mut channels = Vec::new();
+        for item in items {
+            let result = item
+                .result
+                .map_err(|e| ChannelError::ListChannelsError { msg: e.to_string() })?;
+            // A conditional answer lacks caveat context, so the channel stays hidden
+            if let (PermissionCheckResult::Allowed, Resource::Channel(channel_id)) =
+                (result, item.input.resource)
+            {
+                channels.push(channel_id);
+            }
+        }
+
+        info!(
+            channel_count = channels.len(),
+            "Visible channels listed in domain service"
+        );
+        Ok(channels)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::{
+        common::entities::Consistency,
+        permission::{
+            PermissionError,
+            entities::{
+                BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput,
+                LookupResourcesInput, LookupSubjectsInput, PermissionCheckResult,
+                PermissionExplanation, SubjectsLookup,
+            },
+            port::{PermissionChecker, PermissionRepository},
+        },
         permission_override::{
             PermissionOverrideError,
             entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
@@ -59,7 +142,10 @@ mod tests {
         },
         role::{
             RoleError,
-            entities::{AssignMemberInput, CreateRoleInput, DeleteRoleInput, RemoveMemberInput},
+            entities::{
+                AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListServerRolesInput,
+                RemoveMemberInput,
+            },
             port::RoleRepository,
         },
         server::{
@@ -81,6 +167,8 @@ mod tests {
         delete_call_count: Arc<Mutex<usize>>,
         last_create_input: Arc<Mutex<Option<CreateChannelInput>>>,
         last_delete_input: Arc<Mutex<Option<DeleteChannelInput>>>,
+        server_channels: Arc<Mutex<Vec<String>>>,
+        last_list_consistency: Arc<Mutex<Option<Consistency>>>,
     }
 
     impl MockChannelRepository {
@@ -94,9 +182,17 @@ mod tests {
                 delete_call_count: Arc::new(Mutex::new(0)),
                 last_create_input: Arc::new(Mutex::new(None)),
                 last_delete_input: Arc::new(Mutex::new(None)),
+                server_channels: Arc::new(Mutex::new(Vec::new())),
+                last_list_consistency: Arc::new(Mutex::new(None)),
             }
         }
 
+        fn with_server_channels(self, channel_ids: &[&str]) -> Self {
+            *self.server_channels.lock().unwrap() =
+                channel_ids.iter().map(|id| id.to_string()).collect();
+            self
+        }
+
         fn with_create_failure(self, error_msg: &str) -> Self {
             *self.should_fail_create.lock().unwrap() = true;
             *self.create_error_message.lock().unwrap() = error_msg.to_string();
@@ -127,7 +223,7 @@ mod tests {
     }
 
     impl ChannelRepository for MockChannelRepository {
-        async fn create(&self, input: CreateChannelInput) -> Result<(), ChannelError> {
+        async fn create(&self, input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
             *self.create_call_count.lock().unwrap() += 1;
             *self.last_create_input.lock().unwrap() = Some(input);
 
@@ -135,11 +231,11 @@ mod tests {
                 let msg = self.create_error_message.lock().unwrap().clone();
                 Err(ChannelError::CreateChannelError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
 
-        async fn delete(&self, input: DeleteChannelInput) -> Result<(), ChannelError> {
+        async fn delete(&self, input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
             *self.delete_call_count.lock().unwrap() += 1;
             *self.last_delete_input.lock().unwrap() = Some(input);
 
@@ -147,21 +243,35 @@ mod tests {
                 let msg = self.delete_error_message.lock().unwrap().clone();
                 Err(ChannelError::DeleteChannelError { msg })
             } else {
-                Ok(())
+                Ok(DeleteOutput {
+                    deleted_at: ZedToken("zed_token".to_string()),
+                    deleted_count: 3,
+                })
             }
         }
+
+        async fn list_by_server(
+            &self,
+            input: ListServerChannelsInput,
+        ) -> Result<Vec<String>, ChannelError> {
+            *self.last_list_consistency.lock().unwrap() = Some(input.consistency);
+            Ok(self.server_channels.lock().unwrap().clone())
+        }
     }
 
     #[derive(Clone)]
     struct MockServerRepository;
 
     impl ServerRepository for MockServerRepository {
-        async fn create(&self, _input: CreateServerInput) -> Result<(), ServerError> {
-            Ok(())
+        async fn create(&self, _input: CreateServerInput) -> Result<ZedToken, ServerError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn delete(&self, _input: DeleteServerInput) -> Result<(), ServerError> {
-            Ok(())
+        async fn delete(&self, _input: DeleteServerInput) -> Result<DeleteOutput, ServerError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
         }
     }
 
@@ -169,20 +279,27 @@ mod tests {
     struct MockRoleRepository;
 
     impl RoleRepository for MockRoleRepository {
-        async fn create(&self, _input: CreateRoleInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn create(&self, _input: CreateRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn delete(&self, _input: DeleteRoleInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn assign_member(&self, _input: AssignMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn assign_member(&self, _input: AssignMemberInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn remove_member(&self, _input: RemoveMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn remove_member(&self, _input: RemoveMemberInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn list_by_server(
+            &self,
+            _input: ListServerRolesInput,
+        ) -> Result<Vec<String>, RoleError> {
+            Ok(vec![])
         }
     }
 
@@ -193,15 +310,139 @@ mod tests {
         async fn create(
             &self,
             _input: CreatePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
-            Ok(())
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
         async fn delete(
             &self,
             _input: DeletePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
-            Ok(())
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockPermissionRepository;
+
+    impl PermissionRepository for MockPermissionRepository {}
+
+    impl PermissionChecker for MockPermissionRepository {
+        async fn check(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            Ok(PermissionCheckResult::Denied)
+        }
+
+        async fn check_bulk(
+            &self,
+            _inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
+        }
+
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Err(PermissionError::ExplainPermissionError {
+                msg: "not supported by this mock".to_string(),
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
+        }
+    }
+
+    // Permission repository that only answers bulk checks, with a fixed set of visible IDs
+    #[derive(Clone, Default)]
+    struct MockVisibilityRepository {
+        visible_ids: Vec<String>,
+        checked: Arc<Mutex<Vec<CheckPermissionInput>>>,
+    }
+
+    impl PermissionRepository for MockVisibilityRepository {}
+
+    impl PermissionChecker for MockVisibilityRepository {
+        async fn check(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            Ok(PermissionCheckResult::Denied)
+        }
+
+        async fn check_bulk(
+            &self,
+            inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            self.checked.lock().unwrap().extend(inputs.clone());
+            Ok(inputs
+                .into_iter()
+                .map(|input| {
+                    assert_eq!(input.permission, ChannelPermission::View.into());
+                    let result = match &input.resource {
+                        Resource::Channel(id) if id == "channel_broken" => {
+                            Err(PermissionError::BulkCheckPermissionError {
+                                msg: "item failed".to_string(),
+                            })
+                        }
+                        Resource::Channel(id) if self.visible_ids.contains(id) => {
+                            Ok(PermissionCheckResult::Allowed)
+                        }
+                        _ => Ok(PermissionCheckResult::Denied),
+                    };
+                    BulkCheckItem { input, result }
+                })
+                .collect())
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            panic!("visible channels must not be looked up across every server")
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
+        }
+
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Err(PermissionError::ExplainPermissionError {
+                msg: "not supported by this mock".to_string(),
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
         }
     }
 
@@ -215,6 +456,7 @@ mod tests {
             mock_repo.clone(),
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateChannelInput {
@@ -226,7 +468,7 @@ mod tests {
         let result = service.create(input).await;
 
         // Assert
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), ZedToken("zed_token".to_string()));
         assert_eq!(mock_repo.get_create_call_count(), 1);
 
         let last_input = mock_repo.get_last_create_input().unwrap();
@@ -245,6 +487,7 @@ mod tests {
             mock_repo.clone(),
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateChannelInput {
@@ -276,6 +519,7 @@ mod tests {
             mock_repo.clone(),
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = DeleteChannelInput {
@@ -286,7 +530,7 @@ mod tests {
         let result = service.delete(input).await;
 
         // Assert
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap().deleted_count, 3);
         assert_eq!(mock_repo.get_delete_call_count(), 1);
 
         let last_input = mock_repo.get_last_delete_input().unwrap();
@@ -303,6 +547,7 @@ mod tests {
             mock_repo.clone(),
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = DeleteChannelInput {
@@ -334,6 +579,7 @@ mod tests {
             mock_repo,
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateChannelInput {
@@ -365,6 +611,7 @@ mod tests {
             mock_repo,
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = DeleteChannelInput {
@@ -394,6 +641,7 @@ mod tests {
             mock_repo.clone(),
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         // Act - create a channel
@@ -422,4 +670,139 @@ mod tests {
         let last_delete = mock_repo.get_last_delete_input().unwrap();
         assert_eq!(last_delete.channel_id, "channel_1");
     }
+
+    #[tokio::test]
+    async fn test_list_visible_channels_keeps_server_channels_the_user_can_view() {
+        // Arrange
+        let mock_repo = MockChannelRepository::new().with_server_channels(&[
+            "channel_1",
+            "channel_2",
+            "channel_3",
+        ]);
+        let visibility = MockVisibilityRepository {
+            visible_ids: vec!["channel_3".to_string(), "channel_1".to_string()],
+            ..Default::default()
+        };
+        let service = Service::new(
+            MockServerRepository,
+            mock_repo,
+            MockRoleRepository,
+            MockPermissionOverrideRepository,
+            visibility.clone(),
+        );
+
+        // Act
+        let result = service
+            .list_visible_channels(ListVisibleChannelsInput {
+                user_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), vec!["channel_1", "channel_3"]);
+        let checked: Vec<Resource> = visibility
+            .checked
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|input| input.resource.clone())
+            .collect();
+        assert_eq!(
+            checked,
+            vec![
+                Resource::Channel("channel_1".to_string()),
+                Resource::Channel("channel_2".to_string()),
+                Resource::Channel("channel_3".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_visible_channels_fails_when_a_check_fails() {
+        // Arrange
+        let mock_repo =
+            MockChannelRepository::new().with_server_channels(&["channel_1", "channel_broken"]);
+        let visibility = MockVisibilityRepository {
+            visible_ids: vec!["channel_1".to_string()],
+            ..Default::default()
+        };
+        let service = Service::new(
+            MockServerRepository,
+            mock_repo,
+            MockRoleRepository,
+            MockPermissionOverrideRepository,
+            visibility,
+        );
+
+        // Act
+        let result = service
+            .list_visible_channels(ListVisibleChannelsInput {
+                user_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ChannelError::ListChannelsError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_visible_channels_empty_when_nothing_visible() {
+        // Arrange
+        let mock_repo = MockChannelRepository::new().with_server_channels(&["channel_1"]);
+        let service = Service::new(
+            MockServerRepository,
+            mock_repo,
+            MockRoleRepository,
+            MockPermissionOverrideRepository,
+            MockPermissionRepository,
+        );
+
+        // Act
+        let result = service
+            .list_visible_channels(ListVisibleChannelsInput {
+                user_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_visible_channels_forwards_consistency() {
+        // Arrange
+        let mock_repo = MockChannelRepository::new().with_server_channels(&["channel_1"]);
+        let service = Service::new(
+            MockServerRepository,
+            mock_repo.clone(),
+            MockRoleRepository,
+            MockPermissionOverrideRepository,
+            MockPermissionRepository,
+        );
+
+        // Act
+        service
+            .list_visible_channels(ListVisibleChannelsInput {
+                user_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+                consistency: Consistency::FullyConsistent,
+            })
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            *mock_repo.last_list_consistency.lock().unwrap(),
+            Some(Consistency::FullyConsistent)
+        );
+    }
 }
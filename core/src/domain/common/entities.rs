@@ -0,0 +1,83 @@
+use std::fmt;
+
+/// Opaque SpiceDB revision returned by a write, usable to read at least that fresh
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZedToken(pub String);
+
+impl fmt::Display for ZedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for ZedToken {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+/// Outcome of deleting every relationship of a resource
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteOutput {
+    /// Revision covering every deletion
+    pub deleted_at: ZedToken,
+    /// Relationships deleted, as counted by SpiceDB
+    pub deleted_count: u64,
+}
+
+/// How fresh the data behind a check or read must be
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Consistency {
+    /// Let SpiceDB answer from a cached revision
+    #[default]
+    MinimizeLatency,
+    /// Include at least every write up to the given token
+    AtLeastAsFresh(ZedToken),
+    /// Answer from the latest revision, bypassing caches
+    FullyConsistent,
+}
+
+impl Consistency {
+    /// The weakest requirement satisfying both; tokens can't be ordered, so two
+    /// different ones fall back to a fully consistent read
+    pub fn strictest(self, other: Consistency) -> Consistency {
+        match (self, other) {
+            (Consistency::FullyConsistent, _) | (_, Consistency::FullyConsistent) => {
+                Consistency::FullyConsistent
+            }
+            (Consistency::MinimizeLatency, other) | (other, Consistency::MinimizeLatency) => other,
+            (Consistency::AtLeastAsFresh(a), Consistency::AtLeastAsFresh(b)) if a == b => {
+                Consistency::AtLeastAsFresh(a)
+            }
+            _ => Consistency::FullyConsistent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strictest_consistency() {
+        let token = |t: &str| Consistency::AtLeastAsFresh(ZedToken(t.to_string()));
+
+        assert_eq!(
+            Consistency::MinimizeLatency.strictest(token("a")),
+            token("a")
+        );
+        assert_eq!(token("a").strictest(token("a")), token("a"));
+        assert_eq!(
+            token("a").strictest(token("b")),
+            Consistency::FullyConsistent
+        );
+        assert_eq!(
+            token("a").strictest(Consistency::FullyConsistent),
+            Consistency::FullyConsistent
+        );
+        assert_eq!(
+            Consistency::MinimizeLatency.strictest(Consistency::MinimizeLatency),
+            Consistency::MinimizeLatency
+        );
+    }
+}
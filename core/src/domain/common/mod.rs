@@ -1,4 +1,5 @@
 use thiserror::Error;
+pub mod entities;
 pub mod service;
 
 #[derive(Debug, Error)]
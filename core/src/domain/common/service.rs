@@ -1,40 +1,46 @@
 use crate::domain::{
-    channel::port::ChannelRepository, permission_override::port::PermissionOverrideRepository,
-    role::port::RoleRepository, server::port::ServerRepository,
+    channel::port::ChannelRepository, permission::port::PermissionRepository,
+    permission_override::port::PermissionOverrideRepository, role::port::RoleRepository,
+    server::port::ServerRepository,
 };
 
 #[derive(Clone)]
-pub struct Service<S, C, R, P>
+pub struct Service<S, C, R, P, A>
 where
     S: ServerRepository,
     C: ChannelRepository,
     R: RoleRepository,
     P: PermissionOverrideRepository,
+    A: PermissionRepository,
 {
     pub(crate) server_repository: S,
     pub(crate) channel_repository: C,
     pub(crate) role_repository: R,
     pub(crate) permission_override_repository: P,
+    pub(crate) permission_repository: A,
 }
 
-impl<S, C, R, P> Service<S, C, R, P>
+impl<S, C, R, P, A> Service<S, C, R, P, A>
 where
     S: ServerRepository,
     C: ChannelRepository,
     R: RoleRepository,
     P: PermissionOverrideRepository,
+    A: PermissionRepository,
 {
     pub fn new(
         server_repository: S,
         channel_repository: C,
         role_repository: R,
         permission_override_repository: P,
+        permission_repository: A,
     ) -> Self {
         Self {
             server_repository,
             channel_repository,
             role_repository,
             permission_override_repository,
+            permission_repository,
         }
     }
 }
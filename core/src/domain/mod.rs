@@ -1,5 +1,7 @@
 pub mod channel;
 pub mod common;
+pub mod permission;
+pub mod permission_change;
 pub mod permission_override;
 pub mod role;
 pub mod server;
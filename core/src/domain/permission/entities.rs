@@ -0,0 +1,326 @@
+use crate::domain::{common::entities::Consistency, permission::PermissionError};
+use std::fmt;
+
+/// A Beep object a permission can be checked on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Resource {
+    Server(String),
+    Channel(String),
+    Role(String),
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Resource::Server(id) => write!(f, "server:{}", id),
+            Resource::Channel(id) => write!(f, "channel:{}", id),
+            Resource::Role(id) => write!(f, "role:{}", id),
+        }
+    }
+}
+
+impl Resource {
+    pub fn resource_type(&self) -> ResourceType {
+        match self {
+            Resource::Server(_) => ResourceType::Server,
+            Resource::Channel(_) => ResourceType::Channel,
+            Resource::Role(_) => ResourceType::Role,
+        }
+    }
+}
+
+/// The kind of Beep object, without an ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceType {
+    Server,
+    Channel,
+    Role,
+}
+
+/// A permission of a server, granted by its roles or ownership
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerPermission {
+    Admin,
+    Manage,
+    View,
+    ManageRole,
+    ViewRole,
+    CreateInvitation,
+    ManageChannels,
+    ManageWebhooks,
+    ViewChannel,
+    SendMessage,
+    ManageNicknames,
+    ChangeNickname,
+    ManageMessage,
+    AttachFiles,
+}
+
+/// A permission of a channel, with server grants and channel overrides applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelPermission {
+    View,
+    SendMessage,
+    ManageMessage,
+    AttachFiles,
+    ManageWebhooks,
+}
+
+/// A permission of a role, with role-level grants and denies applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RolePermission {
+    Manage,
+    View,
+}
+
+/// A permission together with the kind of resource declaring it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    Server(ServerPermission),
+    Channel(ChannelPermission),
+    Role(RolePermission),
+}
+
+impl Permission {
+    pub fn resource_type(self) -> ResourceType {
+        match self {
+            Permission::Server(_) => ResourceType::Server,
+            Permission::Channel(_) => ResourceType::Channel,
+            Permission::Role(_) => ResourceType::Role,
+        }
+    }
+
+    /// Reject a permission asked on a resource that does not declare it
+    pub fn ensure_declared_on(self, resource: &Resource) -> Result<(), PermissionError> {
+        if self.resource_type() == resource.resource_type() {
+            Ok(())
+        } else {
+            Err(PermissionError::InvalidPermission {
+                msg: format!("{:?} is not a permission of {}", self, resource),
+            })
+        }
+    }
+}
+
+impl From<ServerPermission> for Permission {
+    fn from(permission: ServerPermission) -> Self {
+        Permission::Server(permission)
+    }
+}
+
+impl From<ChannelPermission> for Permission {
+    fn from(permission: ChannelPermission) -> Self {
+        Permission::Channel(permission)
+    }
+}
+
+impl From<RolePermission> for Permission {
+    fn from(permission: RolePermission) -> Self {
+        Permission::Role(permission)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckPermissionInput {
+    pub user_id: String,
+    pub resource: Resource,
+    /// Must be declared on the resource's type
+    pub permission: Permission,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermissionCheckResult {
+    Allowed,
+    Denied,
+    /// The answer depends on caveat context that was not provided
+    Conditional {
+        missing_context: Vec<String>,
+    },
+}
+
+/// Outcome of one item of a bulk check; a failing item does not fail the batch
+#[derive(Debug)]
+pub struct BulkCheckItem {
+    pub input: CheckPermissionInput,
+    pub result: Result<PermissionCheckResult, PermissionError>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LookupResourcesInput {
+    pub user_id: String,
+    /// Resources of the type declaring it are looked up
+    pub permission: Permission,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
+
+#[derive(Debug, Clone)]
+pub struct LookupSubjectsInput {
+    pub resource: Resource,
+    /// Must be declared on the resource's type
+    pub permission: Permission,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
+
+/// Users holding a permission, resolved through roles, overrides and direct grants
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubjectsLookup {
+    pub user_ids: Vec<String>,
+    /// Set when the permission is granted to every user (`user:*`)
+    pub wildcard: Option<WildcardGrant>,
+}
+
+/// A grant to every user, minus the users explicitly excluded from it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WildcardGrant {
+    pub excluded_user_ids: Vec<String>,
+}
+
+/// Who a grant or deny was written for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrantSubject {
+    /// The user directly
+    User,
+    /// Every member of a role
+    RoleMember { role_id: String },
+    /// The targets of a channel permission override
+    PermissionOverride { override_id: String },
+}
+
+/// The relationship that settled a permission check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecidingElement {
+    ServerOwner {
+        server_id: String,
+    },
+    /// A relation granting the permission, e.g. `message_sender@role:X#member` on a server
+    Grant {
+        resource: Resource,
+        relation: String,
+        /// Beep permission the relation stands for, when it maps to one
+        permission: Option<String>,
+        subject: GrantSubject,
+    },
+    /// A `_deny` relation overriding every grant
+    Deny {
+        resource: Resource,
+        relation: String,
+        permission: Option<String>,
+        subject: GrantSubject,
+    },
+    /// Nothing grants the permission to the user
+    NoGrant,
+    /// The trace stopped before reaching a relationship (e.g. a cached sub-result)
+    Unresolved,
+}
+
+impl DecidingElement {
+    fn fmt_relation(
+        f: &mut fmt::Formatter<'_>,
+        resource: &Resource,
+        relation: &str,
+        subject: &GrantSubject,
+        override_relation: &str,
+    ) -> fmt::Result {
+        match subject {
+            GrantSubject::User => write!(f, "{}@user on {}", relation, resource),
+            GrantSubject::RoleMember { role_id } => {
+                write!(f, "{}@role:{}#member on {}", relation, role_id, resource)
+            }
+            GrantSubject::PermissionOverride { override_id } => write!(
+                f,
+                "{}@permission_override:{}#{} on {}",
+                relation, override_id, override_relation, resource
+            ),
+        }
+    }
+}
+
+impl fmt::Display for DecidingElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecidingElement::ServerOwner { server_id } => {
+                write!(f, "owner of server:{}", server_id)
+            }
+            DecidingElement::Grant {
+                resource,
+                relation,
+                permission,
+                subject,
+            } => {
+                Self::fmt_relation(f, resource, relation, subject, "granted_to")?;
+                match permission {
+                    Some(permission) => write!(f, " grants {}", permission),
+                    None => Ok(()),
+                }
+            }
+            DecidingElement::Deny {
+                resource,
+                relation,
+                permission,
+                subject,
+            } => {
+                Self::fmt_relation(f, resource, relation, subject, "denied_to")?;
+                match permission {
+                    Some(permission) => write!(f, " denies {}", permission),
+                    None => Ok(()),
+                }
+            }
+            DecidingElement::NoGrant => write!(f, "no role, grant or ownership applies"),
+            DecidingElement::Unresolved => write!(f, "unresolved (cached sub-result)"),
+        }
+    }
+}
+
+/// One permission or relation evaluated on the way to the deciding element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplanationStep {
+    /// Object the step was evaluated on, as `type:id`
+    pub object: String,
+    /// Permission or relation name on that object
+    pub name: String,
+    pub granted: bool,
+}
+
+impl fmt::Display for ExplanationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = if self.granted { "granted" } else { "denied" };
+        write!(f, "{} {} ({})", self.object, self.name, outcome)
+    }
+}
+
+/// Why a permission check came out the way it did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionExplanation {
+    pub result: PermissionCheckResult,
+    pub deciding_element: DecidingElement,
+    /// Steps from the checked permission down to the deciding element
+    pub chain: Vec<ExplanationStep>,
+}
+
+impl fmt::Display for PermissionExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, step) in self.chain.iter().enumerate() {
+            if index > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", step)?;
+        }
+        if !self.chain.is_empty() {
+            write!(f, ": ")?;
+        }
+        write!(f, "decided by {}", self.deciding_element)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectivePermissionsInput {
+    pub user_id: String,
+    /// A server or a channel; channel bitmasks include server-only capabilities
+    pub resource: Resource,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
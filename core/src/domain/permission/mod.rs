@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+pub mod entities;
+pub mod port;
+pub mod service;
+
+#[derive(Debug, Error)]
+pub enum PermissionError {
+    #[error("Invalid permission: {msg}")]
+    InvalidPermission { msg: String },
+    #[error("Not found: {msg}")]
+    NotFound { msg: String },
+    /// The permission store could not answer for now; the same call may succeed later
+    #[error("Permissions unavailable: {msg}")]
+    Unavailable { msg: String },
+    #[error("Check permission error: {msg}")]
+    CheckPermissionError { msg: String },
+    #[error("Bulk check permission error: {msg}")]
+    BulkCheckPermissionError { msg: String },
+    #[error("Lookup resources error: {msg}")]
+    LookupResourcesError { msg: String },
+    #[error("Lookup subjects error: {msg}")]
+    LookupSubjectsError { msg: String },
+    #[error("Explain permission error: {msg}")]
+    ExplainPermissionError { msg: String },
+    #[error("Effective permissions error: {msg}")]
+    EffectivePermissionsError { msg: String },
+}
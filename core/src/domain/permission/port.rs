@@ -0,0 +1,40 @@
+use crate::domain::permission::{
+    PermissionError,
+    entities::{
+        BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput, LookupResourcesInput,
+        LookupSubjectsInput, PermissionCheckResult, PermissionExplanation, SubjectsLookup,
+    },
+};
+use std::future::Future;
+
+/// Answers "can user X do P on resource R" and the queries built on it
+pub trait PermissionChecker: Send + Sync {
+    fn check(
+        &self,
+        input: CheckPermissionInput,
+    ) -> impl Future<Output = Result<PermissionCheckResult, PermissionError>> + Send;
+    fn check_bulk(
+        &self,
+        inputs: Vec<CheckPermissionInput>,
+    ) -> impl Future<Output = Result<Vec<BulkCheckItem>, PermissionError>> + Send;
+    fn lookup_resources(
+        &self,
+        input: LookupResourcesInput,
+    ) -> impl Future<Output = Result<Vec<String>, PermissionError>> + Send;
+    fn lookup_subjects(
+        &self,
+        input: LookupSubjectsInput,
+    ) -> impl Future<Output = Result<SubjectsLookup, PermissionError>> + Send;
+    fn explain(
+        &self,
+        input: CheckPermissionInput,
+    ) -> impl Future<Output = Result<PermissionExplanation, PermissionError>> + Send;
+    fn effective_permissions(
+        &self,
+        input: EffectivePermissionsInput,
+    ) -> impl Future<Output = Result<u64, PermissionError>> + Send;
+}
+
+/// Adapter answering permission queries from the store, wrapped by the
+/// services exposing `PermissionChecker`
+pub trait PermissionRepository: PermissionChecker {}
@@ -0,0 +1,886 @@
+use crate::domain::{
+    channel::port::ChannelRepository,
+    common::service::Service,
+    permission::{
+        PermissionError,
+        entities::{
+            BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput, LookupResourcesInput,
+            LookupSubjectsInput, PermissionCheckResult, PermissionExplanation, Resource,
+            SubjectsLookup,
+        },
+        port::{PermissionChecker, PermissionRepository},
+    },
+    permission_override::port::PermissionOverrideRepository,
+    role::port::RoleRepository,
+    server::port::ServerRepository,
+};
+use tracing::{info, instrument};
+
+impl<S, C, R, P, A> PermissionChecker for Service<S, C, R, P, A>
+where
+    S: ServerRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    P: PermissionOverrideRepository,
+    A: PermissionRepository,
+{
+    #[instrument(skip(self), fields(user_id = %input.user_id, resource = ?input.resource, permission = ?input.permission))]
+    async fn check(
+        &self,
+        input: CheckPermissionInput,
+    ) -> Result<PermissionCheckResult, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            resource = ?input.resource,
+            permission = ?input.permission,
+            "Checking permission in domain service"
+        );
+        input.permission.ensure_declared_on(&input.resource)?;
+        let result = self.permission_repository.check(input).await;
+        match &result {
+            Ok(outcome) => info!(outcome = ?outcome, "Permission checked in domain service"),
+            Err(e) => info!(error = ?e, "Failed to check permission in domain service"),
+        }
+        result
+    }
+
+    #[instrument(skip(self, inputs), fields(item_count = inputs.len()))]
+    async fn check_bulk(
+        &self,
+        inputs: Vec<CheckPermissionInput>,
+    ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+        info!(
+            item_count = inputs.len(),
+            "Checking permissions in bulk in domain service"
+        );
+        // A permission asked on the wrong resource type fails its own item only
+        let mut items: Vec<Option<BulkCheckItem>> = Vec::with_capacity(inputs.len());
+        let mut valid = Vec::new();
+        for input in inputs {
+            match input.permission.ensure_declared_on(&input.resource) {
+                Ok(()) => {
+                    items.push(None);
+                    valid.push(input);
+                }
+                Err(e) => items.push(Some(BulkCheckItem {
+                    input,
+                    result: Err(e),
+                })),
+            }
+        }
+        let result = if valid.is_empty() {
+            Ok(items.into_iter().flatten().collect())
+        } else {
+            self.permission_repository
+                .check_bulk(valid)
+                .await
+                .map(|checked| {
+                    let mut checked = checked.into_iter();
+                    items
+                        .into_iter()
+                        .filter_map(|item| item.or_else(|| checked.next()))
+                        .collect::<Vec<_>>()
+                })
+        };
+        match &result {
+            Ok(items) => info!(
+                item_count = items.len(),
+                failed_count = items.iter().filter(|item| item.result.is_err()).count(),
+                "Bulk permissions checked in domain service"
+            ),
+            Err(e) => info!(error = ?e, "Failed to check bulk permissions in domain service"),
+        }
+        result
+    }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, permission = ?input.permission))]
+    async fn lookup_resources(
+        &self,
+        input: LookupResourcesInput,
+    ) -> Result<Vec<String>, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            permission = ?input.permission,
+            "Looking up resources in domain service"
+        );
+        let result = self.permission_repository.lookup_resources(input).await;
+        match &result {
+            Ok(ids) => info!(
+                resource_count = ids.len(),
+                "Resources looked up in domain service"
+            ),
+            Err(e) => info!(error = ?e, "Failed to look up resources in domain service"),
+        }
+        result
+    }
+
+    #[instrument(skip(self), fields(resource = ?input.resource, permission = ?input.permission))]
+    async fn lookup_subjects(
+        &self,
+        input: LookupSubjectsInput,
+    ) -> Result<SubjectsLookup, PermissionError> {
+        info!(
+            resource = ?input.resource,
+            permission = ?input.permission,
+            "Looking up subjects in domain service"
+        );
+        input.permission.ensure_declared_on(&input.resource)?;
+        let result = self.permission_repository.lookup_subjects(input).await;
+        match &result {
+            Ok(lookup) => info!(
+                user_count = lookup.user_ids.len(),
+                wildcard = lookup.wildcard.is_some(),
+                "Subjects looked up in domain service"
+            ),
+            Err(e) => info!(error = ?e, "Failed to look up subjects in domain service"),
+        }
+        result
+    }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, resource = ?input.resource, permission = ?input.permission))]
+    async fn explain(
+        &self,
+        input: CheckPermissionInput,
+    ) -> Result<PermissionExplanation, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            resource = ?input.resource,
+            permission = ?input.permission,
+            "Explaining permission in domain service"
+        );
+        input.permission.ensure_declared_on(&input.resource)?;
+        let result = self.permission_repository.explain(input).await;
+        match &result {
+            Ok(explanation) => info!(
+                explanation = %explanation,
+                "Permission explained in domain service"
+            ),
+            Err(e) => info!(error = ?e, "Failed to explain permission in domain service"),
+        }
+        result
+    }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, resource = ?input.resource))]
+    async fn effective_permissions(
+        &self,
+        input: EffectivePermissionsInput,
+    ) -> Result<u64, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            resource = ?input.resource,
+            "Computing effective permissions in domain service"
+        );
+        if let Resource::Role(role_id) = &input.resource {
+            return Err(PermissionError::EffectivePermissionsError {
+                msg: format!(
+                    "effective permissions are defined for servers and channels, not role {}",
+                    role_id
+                ),
+            });
+        }
+        let result = self
+            .permission_repository
+            .effective_permissions(input)
+            .await;
+        match &result {
+            Ok(bitmask) => info!(
+                bitmask = format!("{:#x}", bitmask),
+                "Effective permissions computed in domain service"
+            ),
+            Err(e) => {
+                info!(error = ?e, "Failed to compute effective permissions in domain service")
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        channel::{
+            ChannelError,
+            entities::{CreateChannelInput, DeleteChannelInput, ListServerChannelsInput},
+        },
+        common::entities::{Consistency, DeleteOutput, ZedToken},
+        permission::entities::{
+            ChannelPermission, DecidingElement, ExplanationStep, GrantSubject, Permission,
+            RolePermission, ServerPermission, WildcardGrant,
+        },
+        permission_override::{
+            PermissionOverrideError,
+            entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
+        },
+        role::{
+            RoleError,
+            entities::{
+                AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListServerRolesInput,
+                RemoveMemberInput,
+            },
+        },
+        server::{
+            ServerError,
+            entities::{CreateServerInput, DeleteServerInput},
+        },
+    };
+    use std::sync::{Arc, Mutex};
+
+    // Simple mock repository for testing
+    #[derive(Clone)]
+    struct MockPermissionRepository {
+        outcome: Arc<Mutex<Result<PermissionCheckResult, String>>>,
+        check_call_count: Arc<Mutex<usize>>,
+        check_bulk_call_count: Arc<Mutex<usize>>,
+        last_check_input: Arc<Mutex<Option<CheckPermissionInput>>>,
+        resource_ids: Arc<Mutex<Vec<String>>>,
+        subjects: Arc<Mutex<SubjectsLookup>>,
+        bitmask: Arc<Mutex<u64>>,
+        effective_call_count: Arc<Mutex<usize>>,
+        last_lookup_subjects_input: Arc<Mutex<Option<LookupSubjectsInput>>>,
+    }
+
+    impl MockPermissionRepository {
+        fn new(outcome: PermissionCheckResult) -> Self {
+            Self {
+                outcome: Arc::new(Mutex::new(Ok(outcome))),
+                check_call_count: Arc::new(Mutex::new(0)),
+                check_bulk_call_count: Arc::new(Mutex::new(0)),
+                last_check_input: Arc::new(Mutex::new(None)),
+                resource_ids: Arc::new(Mutex::new(Vec::new())),
+                subjects: Arc::new(Mutex::new(SubjectsLookup::default())),
+                bitmask: Arc::new(Mutex::new(0)),
+                effective_call_count: Arc::new(Mutex::new(0)),
+                last_lookup_subjects_input: Arc::new(Mutex::new(None)),
+            }
+        }
+
+        fn with_resource_ids(self, ids: &[&str]) -> Self {
+            *self.resource_ids.lock().unwrap() = ids.iter().map(|id| id.to_string()).collect();
+            self
+        }
+
+        fn with_check_failure(self, error_msg: &str) -> Self {
+            *self.outcome.lock().unwrap() = Err(error_msg.to_string());
+            self
+        }
+
+        fn with_subjects(self, subjects: SubjectsLookup) -> Self {
+            *self.subjects.lock().unwrap() = subjects;
+            self
+        }
+
+        fn with_bitmask(self, bitmask: u64) -> Self {
+            *self.bitmask.lock().unwrap() = bitmask;
+            self
+        }
+
+        fn get_effective_call_count(&self) -> usize {
+            *self.effective_call_count.lock().unwrap()
+        }
+
+        fn get_last_lookup_subjects_input(&self) -> Option<LookupSubjectsInput> {
+            self.last_lookup_subjects_input.lock().unwrap().clone()
+        }
+
+        fn get_check_call_count(&self) -> usize {
+            *self.check_call_count.lock().unwrap()
+        }
+
+        fn get_check_bulk_call_count(&self) -> usize {
+            *self.check_bulk_call_count.lock().unwrap()
+        }
+
+        fn get_last_check_input(&self) -> Option<CheckPermissionInput> {
+            self.last_check_input.lock().unwrap().clone()
+        }
+    }
+
+    impl PermissionRepository for MockPermissionRepository {}
+
+    impl PermissionChecker for MockPermissionRepository {
+        async fn check(
+            &self,
+            input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            *self.check_call_count.lock().unwrap() += 1;
+            *self.last_check_input.lock().unwrap() = Some(input);
+
+            self.outcome
+                .lock()
+                .unwrap()
+                .clone()
+                .map_err(|msg| PermissionError::CheckPermissionError { msg })
+        }
+
+        async fn check_bulk(
+            &self,
+            inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            *self.check_bulk_call_count.lock().unwrap() += 1;
+
+            // Fail every item on a "broken_" channel, answer the configured outcome otherwise
+            Ok(inputs
+                .into_iter()
+                .map(|input| {
+                    let result = match &input.resource {
+                        Resource::Channel(id) if id.starts_with("broken_") => {
+                            Err(PermissionError::CheckPermissionError {
+                                msg: format!("unknown channel {}", id),
+                            })
+                        }
+                        _ => self
+                            .outcome
+                            .lock()
+                            .unwrap()
+                            .clone()
+                            .map_err(|msg| PermissionError::CheckPermissionError { msg }),
+                    };
+                    BulkCheckItem { input, result }
+                })
+                .collect())
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            Ok(self.resource_ids.lock().unwrap().clone())
+        }
+
+        async fn lookup_subjects(
+            &self,
+            input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            *self.last_lookup_subjects_input.lock().unwrap() = Some(input);
+            Ok(self.subjects.lock().unwrap().clone())
+        }
+
+        async fn explain(
+            &self,
+            input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            *self.last_check_input.lock().unwrap() = Some(input.clone());
+            let result = self
+                .outcome
+                .lock()
+                .unwrap()
+                .clone()
+                .map_err(|msg| PermissionError::ExplainPermissionError { msg })?;
+            let deciding_element = match result {
+                PermissionCheckResult::Denied => DecidingElement::NoGrant,
+                _ => DecidingElement::Grant {
+                    resource: input.resource.clone(),
+                    relation: "send_message_grant".to_string(),
+                    permission: Some("send_message".to_string()),
+                    subject: GrantSubject::User,
+                },
+            };
+            Ok(PermissionExplanation {
+                result,
+                deciding_element,
+                chain: vec![ExplanationStep {
+                    object: input.resource.to_string(),
+                    name: "send_message".to_string(),
+                    granted: true,
+                }],
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            *self.effective_call_count.lock().unwrap() += 1;
+            Ok(*self.bitmask.lock().unwrap())
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockServerRepository;
+
+    impl ServerRepository for MockServerRepository {
+        async fn create(&self, _input: CreateServerInput) -> Result<ZedToken, ServerError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteServerInput) -> Result<DeleteOutput, ServerError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockChannelRepository;
+
+    impl ChannelRepository for MockChannelRepository {
+        async fn create(&self, _input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
+        }
+
+        async fn list_by_server(
+            &self,
+            _input: ListServerChannelsInput,
+        ) -> Result<Vec<String>, ChannelError> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockRoleRepository;
+
+    impl RoleRepository for MockRoleRepository {
+        async fn create(&self, _input: CreateRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn assign_member(&self, _input: AssignMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn remove_member(&self, _input: RemoveMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn list_by_server(
+            &self,
+            _input: ListServerRolesInput,
+        ) -> Result<Vec<String>, RoleError> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockPermissionOverrideRepository;
+
+    impl PermissionOverrideRepository for MockPermissionOverrideRepository {
+        async fn create(
+            &self,
+            _input: CreatePermissionOverrideInput,
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(
+            &self,
+            _input: DeletePermissionOverrideInput,
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+    }
+
+    fn service_with(
+        mock_repo: MockPermissionRepository,
+    ) -> Service<
+        MockServerRepository,
+        MockChannelRepository,
+        MockRoleRepository,
+        MockPermissionOverrideRepository,
+        MockPermissionRepository,
+    > {
+        Service::new(
+            MockServerRepository,
+            MockChannelRepository,
+            MockRoleRepository,
+            MockPermissionOverrideRepository,
+            mock_repo,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_check_permission_allowed() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed);
+        let service = service_with(mock_repo.clone());
+
+        let input = CheckPermissionInput {
+            user_id: "user_123".to_string(),
+            resource: Resource::Channel("channel_456".to_string()),
+            permission: ChannelPermission::SendMessage.into(),
+            consistency: Consistency::default(),
+        };
+
+        // Act
+        let result = service.check(input).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), PermissionCheckResult::Allowed);
+        assert_eq!(mock_repo.get_check_call_count(), 1);
+
+        let last_input = mock_repo.get_last_check_input().unwrap();
+        assert_eq!(last_input.user_id, "user_123");
+        assert_eq!(
+            last_input.resource,
+            Resource::Channel("channel_456".to_string())
+        );
+        assert_eq!(
+            last_input.permission,
+            Permission::Channel(ChannelPermission::SendMessage)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_permission_denied() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Denied);
+        let service = service_with(mock_repo.clone());
+
+        let input = CheckPermissionInput {
+            user_id: "user_123".to_string(),
+            resource: Resource::Server("server_456".to_string()),
+            permission: ServerPermission::Admin.into(),
+            consistency: Consistency::default(),
+        };
+
+        // Act
+        let result = service.check(input).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), PermissionCheckResult::Denied);
+        assert_eq!(mock_repo.get_check_call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_check_permission_conditional() {
+        // Arrange
+        let outcome = PermissionCheckResult::Conditional {
+            missing_context: vec!["current_time".to_string()],
+        };
+        let mock_repo = MockPermissionRepository::new(outcome.clone());
+        let service = service_with(mock_repo);
+
+        let input = CheckPermissionInput {
+            user_id: "user_123".to_string(),
+            resource: Resource::Role("role_789".to_string()),
+            permission: RolePermission::Manage.into(),
+            consistency: Consistency::default(),
+        };
+
+        // Act
+        let result = service.check(input).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), outcome);
+    }
+
+    #[tokio::test]
+    async fn test_check_permission_propagates_error() {
+        // Arrange
+        let error_msg = "SpiceDB unavailable";
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed)
+            .with_check_failure(error_msg);
+        let service = service_with(mock_repo.clone());
+
+        let input = CheckPermissionInput {
+            user_id: "user_123".to_string(),
+            resource: Resource::Channel("channel_456".to_string()),
+            permission: ChannelPermission::View.into(),
+            consistency: Consistency::default(),
+        };
+
+        // Act
+        let result = service.check(input).await;
+
+        // Assert
+        assert_eq!(mock_repo.get_check_call_count(), 1);
+        match result {
+            Err(PermissionError::CheckPermissionError { msg }) => {
+                assert_eq!(msg, error_msg);
+            }
+            _ => panic!("Expected CheckPermissionError"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_permission_rejects_permission_of_another_resource() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed);
+        let service = service_with(mock_repo.clone());
+
+        let input = CheckPermissionInput {
+            user_id: "user_123".to_string(),
+            resource: Resource::Channel("channel_456".to_string()),
+            permission: ServerPermission::Admin.into(),
+            consistency: Consistency::default(),
+        };
+
+        // Act
+        let result = service.check(input).await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(PermissionError::InvalidPermission { .. })
+        ));
+        assert_eq!(mock_repo.get_check_call_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_check_bulk_returns_per_item_results() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed);
+        let service = service_with(mock_repo.clone());
+
+        let inputs = vec![
+            CheckPermissionInput {
+                user_id: "user_123".to_string(),
+                resource: Resource::Channel("channel_1".to_string()),
+                permission: ChannelPermission::View.into(),
+                consistency: Consistency::default(),
+            },
+            CheckPermissionInput {
+                user_id: "user_123".to_string(),
+                resource: Resource::Channel("broken_channel".to_string()),
+                permission: ChannelPermission::View.into(),
+                consistency: Consistency::default(),
+            },
+            CheckPermissionInput {
+                user_id: "user_123".to_string(),
+                resource: Resource::Role("role_1".to_string()),
+                permission: RolePermission::Manage.into(),
+                consistency: Consistency::default(),
+            },
+        ];
+
+        // Act
+        let items = service.check_bulk(inputs).await.unwrap();
+
+        // Assert
+        assert_eq!(mock_repo.get_check_bulk_call_count(), 1);
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[0].result.as_ref().unwrap(),
+            &PermissionCheckResult::Allowed
+        );
+        assert!(matches!(
+            items[1].result,
+            Err(PermissionError::CheckPermissionError { .. })
+        ));
+        assert_eq!(
+            items[1].input.resource,
+            Resource::Channel("broken_channel".to_string())
+        );
+        assert_eq!(
+            items[2].result.as_ref().unwrap(),
+            &PermissionCheckResult::Allowed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_bulk_rejects_mismatched_items_only() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed);
+        let service = service_with(mock_repo.clone());
+
+        let inputs = vec![
+            CheckPermissionInput {
+                user_id: "user_123".to_string(),
+                resource: Resource::Role("role_1".to_string()),
+                permission: ChannelPermission::View.into(),
+                consistency: Consistency::default(),
+            },
+            CheckPermissionInput {
+                user_id: "user_123".to_string(),
+                resource: Resource::Server("server_1".to_string()),
+                permission: ServerPermission::View.into(),
+                consistency: Consistency::default(),
+            },
+        ];
+
+        // Act
+        let items = service.check_bulk(inputs).await.unwrap();
+
+        // Assert
+        assert_eq!(mock_repo.get_check_bulk_call_count(), 1);
+        assert_eq!(items.len(), 2);
+        assert!(matches!(
+            items[0].result,
+            Err(PermissionError::InvalidPermission { .. })
+        ));
+        assert_eq!(
+            items[0].input.resource,
+            Resource::Role("role_1".to_string())
+        );
+        assert_eq!(
+            items[1].result.as_ref().unwrap(),
+            &PermissionCheckResult::Allowed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_bulk_empty_skips_repository() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed);
+        let service = service_with(mock_repo.clone());
+
+        // Act
+        let items = service.check_bulk(Vec::new()).await.unwrap();
+
+        // Assert
+        assert!(items.is_empty());
+        assert_eq!(mock_repo.get_check_bulk_call_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_resources_returns_repository_ids() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Denied)
+            .with_resource_ids(&["channel_1", "channel_2"]);
+        let service = service_with(mock_repo);
+
+        // Act
+        let ids = service
+            .lookup_resources(LookupResourcesInput {
+                user_id: "user_1".to_string(),
+                permission: ChannelPermission::View.into(),
+                consistency: Consistency::default(),
+            })
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(ids, vec!["channel_1", "channel_2"]);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_subjects_returns_users_and_wildcard() {
+        // Arrange
+        let expected = SubjectsLookup {
+            user_ids: vec!["user_1".to_string(), "user_2".to_string()],
+            wildcard: Some(WildcardGrant {
+                excluded_user_ids: vec!["user_3".to_string()],
+            }),
+        };
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Denied)
+            .with_subjects(expected.clone());
+        let service = service_with(mock_repo.clone());
+
+        // Act
+        let lookup = service
+            .lookup_subjects(LookupSubjectsInput {
+                resource: Resource::Channel("channel_1".to_string()),
+                permission: ChannelPermission::ManageMessage.into(),
+                consistency: Consistency::default(),
+            })
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(lookup, expected);
+        let input = mock_repo.get_last_lookup_subjects_input().unwrap();
+        assert_eq!(input.resource, Resource::Channel("channel_1".to_string()));
+        assert_eq!(
+            input.permission,
+            Permission::Channel(ChannelPermission::ManageMessage)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_explain_names_deciding_element() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed);
+        let service = service_with(mock_repo.clone());
+
+        // Act
+        let explanation = service
+            .explain(CheckPermissionInput {
+                user_id: "user_1".to_string(),
+                resource: Resource::Channel("general".to_string()),
+                permission: ChannelPermission::SendMessage.into(),
+                consistency: Consistency::default(),
+            })
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(explanation.result, PermissionCheckResult::Allowed);
+        assert_eq!(
+            explanation.to_string(),
+            "channel:general send_message (granted): decided by \
+             send_message_grant@user on channel:general grants send_message"
+        );
+        assert_eq!(mock_repo.get_last_check_input().unwrap().user_id, "user_1");
+    }
+
+    #[tokio::test]
+    async fn test_explain_failure() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed)
+            .with_check_failure("tracing unavailable");
+        let service = service_with(mock_repo);
+
+        // Act
+        let result = service
+            .explain(CheckPermissionInput {
+                user_id: "user_1".to_string(),
+                resource: Resource::Server("server_1".to_string()),
+                permission: ServerPermission::Admin.into(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(PermissionError::ExplainPermissionError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_effective_permissions_for_channel() {
+        // Arrange
+        let mock_repo =
+            MockPermissionRepository::new(PermissionCheckResult::Allowed).with_bitmask(0xC0);
+        let service = service_with(mock_repo.clone());
+
+        // Act
+        let bitmask = service
+            .effective_permissions(EffectivePermissionsInput {
+                user_id: "user_1".to_string(),
+                resource: Resource::Channel("channel_1".to_string()),
+                consistency: Consistency::default(),
+            })
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(bitmask, 0xC0);
+        assert_eq!(mock_repo.get_effective_call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_effective_permissions_rejects_roles() {
+        // Arrange
+        let mock_repo = MockPermissionRepository::new(PermissionCheckResult::Allowed);
+        let service = service_with(mock_repo.clone());
+
+        // Act
+        let result = service
+            .effective_permissions(EffectivePermissionsInput {
+                user_id: "user_1".to_string(),
+                resource: Resource::Role("role_1".to_string()),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(PermissionError::EffectivePermissionsError { .. })
+        ));
+        assert_eq!(mock_repo.get_effective_call_count(), 0);
+    }
+}
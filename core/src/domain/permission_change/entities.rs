@@ -0,0 +1,234 @@
+use crate::domain::{
+    common::entities::ZedToken, permission::entities::Resource,
+    permission_override::entities::OverrideTarget,
+};
+use std::fmt;
+
+/// Whether a relationship appeared or disappeared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOperation {
+    Added,
+    Removed,
+}
+
+/// Who a grant or deny applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grantee {
+    User(String),
+    /// Every member of the role
+    Role(String),
+    /// Whoever the permission override targets
+    Override(String),
+}
+
+impl fmt::Display for Grantee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Grantee::User(id) => write!(f, "user {}", id),
+            Grantee::Role(id) => write!(f, "role {}", id),
+            Grantee::Override(id) => write!(f, "override {}", id),
+        }
+    }
+}
+
+/// A relationship change translated back to Beep terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermissionChangeEvent {
+    ServerOwner {
+        server_id: String,
+        user_id: String,
+    },
+    RoleMember {
+        role_id: String,
+        user_id: String,
+    },
+    RoleServer {
+        role_id: String,
+        server_id: String,
+    },
+    ChannelServer {
+        channel_id: String,
+        server_id: String,
+    },
+    OverrideChannel {
+        override_id: String,
+        channel_id: String,
+    },
+    OverrideTarget {
+        override_id: String,
+        target: OverrideTarget,
+        is_allow: bool,
+    },
+    /// A permission granted or denied on a server, channel or role
+    Permission {
+        resource: Resource,
+        /// Permission Display name (e.g. "send_message")
+        permission: String,
+        grantee: Grantee,
+        is_allow: bool,
+    },
+    /// A relationship the schema mapping does not know about
+    Unmapped {
+        resource: String,
+        relation: String,
+        subject: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionChange {
+    pub operation: ChangeOperation,
+    pub event: PermissionChangeEvent,
+}
+
+impl fmt::Display for PermissionChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let added = self.operation == ChangeOperation::Added;
+        match &self.event {
+            PermissionChangeEvent::ServerOwner { server_id, user_id } => match added {
+                true => write!(f, "user {} became owner of server {}", user_id, server_id),
+                false => write!(
+                    f,
+                    "user {} is no longer owner of server {}",
+                    user_id, server_id
+                ),
+            },
+            PermissionChangeEvent::RoleMember { role_id, user_id } => match added {
+                true => write!(f, "user {} joined role {}", user_id, role_id),
+                false => write!(f, "user {} left role {}", user_id, role_id),
+            },
+            PermissionChangeEvent::RoleServer { role_id, server_id } => match added {
+                true => write!(f, "role {} added to server {}", role_id, server_id),
+                false => write!(f, "role {} removed from server {}", role_id, server_id),
+            },
+            PermissionChangeEvent::ChannelServer {
+                channel_id,
+                server_id,
+            } => match added {
+                true => write!(f, "channel {} added to server {}", channel_id, server_id),
+                false => write!(
+                    f,
+                    "channel {} removed from server {}",
+                    channel_id, server_id
+                ),
+            },
+            PermissionChangeEvent::OverrideChannel {
+                override_id,
+                channel_id,
+            } => match added {
+                true => write!(
+                    f,
+                    "override {} added to channel {}",
+                    override_id, channel_id
+                ),
+                false => write!(
+                    f,
+                    "override {} removed from channel {}",
+                    override_id, channel_id
+                ),
+            },
+            PermissionChangeEvent::OverrideTarget {
+                override_id,
+                target,
+                is_allow,
+            } => {
+                let target = match target {
+                    OverrideTarget::User(id) => Grantee::User(id.clone()),
+                    OverrideTarget::Role(id) => Grantee::Role(id.clone()),
+                };
+                let effect = if *is_allow { "allows" } else { "denies" };
+                match added {
+                    true => write!(f, "override {} now {} {}", override_id, effect, target),
+                    false => write!(
+                        f,
+                        "override {} no longer {} {}",
+                        override_id, effect, target
+                    ),
+                }
+            }
+            PermissionChangeEvent::Permission {
+                resource,
+                permission,
+                grantee,
+                is_allow,
+            } => {
+                let resource = match resource {
+                    Resource::Server(id) => format!("server {}", id),
+                    Resource::Channel(id) => format!("channel {}", id),
+                    Resource::Role(id) => format!("role {}", id),
+                };
+                match (added, is_allow) {
+                    (true, true) => write!(f, "{} gained {} on {}", grantee, permission, resource),
+                    (false, true) => write!(f, "{} lost {} on {}", grantee, permission, resource),
+                    (true, false) => {
+                        write!(f, "{} denied {} on {}", grantee, permission, resource)
+                    }
+                    (false, false) => write!(
+                        f,
+                        "{} no longer denied {} on {}",
+                        grantee, permission, resource
+                    ),
+                }
+            }
+            PermissionChangeEvent::Unmapped {
+                resource,
+                relation,
+                subject,
+            } => {
+                let operation = if added { "added" } else { "removed" };
+                write!(
+                    f,
+                    "relationship {}#{}@{} {}",
+                    resource, relation, subject, operation
+                )
+            }
+        }
+    }
+}
+
+/// Changes committed together in SpiceDB
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionChangeBatch {
+    pub changes: Vec<PermissionChange>,
+    /// Revision the batch was committed at, to resume after or deduplicate on
+    pub revision: ZedToken,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_role_lost_server_permission() {
+        let change = PermissionChange {
+            operation: ChangeOperation::Removed,
+            event: PermissionChangeEvent::Permission {
+                resource: Resource::Server("server_1".to_string()),
+                permission: "send_message".to_string(),
+                grantee: Grantee::Role("role_1".to_string()),
+                is_allow: true,
+            },
+        };
+
+        assert_eq!(
+            change.to_string(),
+            "role role_1 lost send_message on server server_1"
+        );
+    }
+
+    #[test]
+    fn test_display_override_removed_from_channel() {
+        let change = PermissionChange {
+            operation: ChangeOperation::Removed,
+            event: PermissionChangeEvent::OverrideChannel {
+                override_id: "override_1".to_string(),
+                channel_id: "channel_1".to_string(),
+            },
+        };
+
+        assert_eq!(
+            change.to_string(),
+            "override override_1 removed from channel channel_1"
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+pub mod entities;
+pub mod port;
+pub mod service;
+
+#[derive(Debug, Error)]
+pub enum PermissionChangeError {
+    #[error("Watch permission changes error: {msg}")]
+    WatchError { msg: String },
+    #[error("Publish permission change error: {msg}")]
+    PublishError { msg: String },
+    #[error("Checkpoint error: {msg}")]
+    CheckpointError { msg: String },
+}
@@ -0,0 +1,32 @@
+use crate::domain::{
+    common::entities::ZedToken,
+    permission_change::{PermissionChangeError, entities::PermissionChangeBatch},
+};
+use futures::stream::BoxStream;
+
+pub type PermissionChangeStream =
+    BoxStream<'static, Result<PermissionChangeBatch, PermissionChangeError>>;
+
+pub trait PermissionChangeSource: Send + Sync {
+    /// Changes committed after `after`, or from now on when `None`
+    fn subscribe(
+        &self,
+        after: Option<ZedToken>,
+    ) -> impl Future<Output = Result<PermissionChangeStream, PermissionChangeError>> + Send;
+}
+
+pub trait PermissionChangePublisher: Send + Sync {
+    fn publish(
+        &self,
+        batch: &PermissionChangeBatch,
+    ) -> impl Future<Output = Result<(), PermissionChangeError>> + Send;
+}
+
+/// Where the revision of the last published batch is kept across restarts
+pub trait CheckpointStore: Send + Sync {
+    fn load(&self) -> impl Future<Output = Result<Option<ZedToken>, PermissionChangeError>> + Send;
+    fn save(
+        &self,
+        revision: &ZedToken,
+    ) -> impl Future<Output = Result<(), PermissionChangeError>> + Send;
+}
@@ -0,0 +1,451 @@
+use crate::domain::{
+    common::entities::ZedToken,
+    permission_change::{
+        PermissionChangeError,
+        port::{CheckpointStore, PermissionChangePublisher, PermissionChangeSource},
+    },
+};
+use futures::StreamExt;
+use std::{sync::Mutex, time::Duration};
+use tracing::{debug, error, info, instrument, warn};
+
+/// Delay before resubscribing after the stream ended or failed
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Consecutive checkpoint saves that may fail before the watcher gives up
+const DEFAULT_MAX_SAVE_FAILURES: u32 = 3;
+
+/// Forwards permission changes from a source to a publisher, checkpointing the
+/// revision of every batch once published. Delivery is at-least-once: a crash
+/// between publishing and saving the checkpoint republishes everything since
+/// the last saved revision, so consumers must deduplicate on the revision. The
+/// last published revision is also kept in memory, so a resubscription never
+/// skips or replays changes, and the watcher stops once the checkpoint fails to
+/// save too many times in a row rather than letting the replay window grow.
+pub struct PermissionChangeWatcher<W, P, C>
+where
+    W: PermissionChangeSource,
+    P: PermissionChangePublisher,
+    C: CheckpointStore,
+{
+    source: W,
+    publisher: P,
+    checkpoint_store: C,
+    retry_delay: Duration,
+    max_save_failures: u32,
+    save_failures: Mutex<u32>,
+    last_revision: Mutex<Option<ZedToken>>,
+}
+
+impl<W, P, C> PermissionChangeWatcher<W, P, C>
+where
+    W: PermissionChangeSource,
+    P: PermissionChangePublisher,
+    C: CheckpointStore,
+{
+    pub fn new(source: W, publisher: P, checkpoint_store: C) -> Self {
+        Self {
+            source,
+            publisher,
+            checkpoint_store,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            max_save_failures: DEFAULT_MAX_SAVE_FAILURES,
+            save_failures: Mutex::new(0),
+            last_revision: Mutex::new(None),
+        }
+    }
+
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    pub fn with_max_save_failures(mut self, max_save_failures: u32) -> Self {
+        self.max_save_failures = max_save_failures;
+        self
+    }
+
+    /// Publish changes from the last published revision, or the saved
+    /// checkpoint on the first run, until the stream ends or fails. Fails with
+    /// [`PermissionChangeError::CheckpointError`] once the checkpoint could not
+    /// be saved `max_save_failures` times in a row
+    #[instrument(skip(self))]
+    pub async fn run_once(&self) -> Result<(), PermissionChangeError> {
+        let last_revision = self.last_revision.lock().unwrap().clone();
+        let checkpoint = match last_revision {
+            Some(revision) => Some(revision),
+            None => self.checkpoint_store.load().await?,
+        };
+        info!(checkpoint = ?checkpoint, "Subscribing to permission changes");
+
+        let mut stream = self.source.subscribe(checkpoint).await?;
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            if !batch.changes.is_empty() {
+                self.publisher.publish(&batch).await?;
+                debug!(
+                    revision = %batch.revision,
+                    change_count = batch.changes.len(),
+                    "Permission changes published"
+                );
+            }
+            *self.last_revision.lock().unwrap() = Some(batch.revision.clone());
+            self.save_checkpoint(&batch.revision).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn save_checkpoint(&self, revision: &ZedToken) -> Result<(), PermissionChangeError> {
+        let Err(e) = self.checkpoint_store.save(revision).await else {
+            *self.save_failures.lock().unwrap() = 0;
+            return Ok(());
+        };
+
+        let failures = {
+            let mut save_failures = self.save_failures.lock().unwrap();
+            *save_failures += 1;
+            *save_failures
+        };
+        error!(
+            error = %e,
+            revision = %revision,
+            failures,
+            "Failed to save permission change checkpoint"
+        );
+        if failures >= self.max_save_failures {
+            return Err(PermissionChangeError::CheckpointError {
+                msg: format!("{failures} consecutive saves failed, last with: {e}"),
+            });
+        }
+        Ok(())
+    }
+
+    /// Publish changes, resubscribing from the last revision whenever the
+    /// stream breaks. Only returns once the checkpoint keeps failing to save
+    #[instrument(skip(self))]
+    pub async fn run(&self) -> Result<(), PermissionChangeError> {
+        loop {
+            match self.run_once().await {
+                Ok(()) => warn!("Permission change stream ended"),
+                Err(e @ PermissionChangeError::CheckpointError { .. }) => return Err(e),
+                Err(e) => error!(error = %e, "Permission change watcher failed"),
+            }
+            tokio::time::sleep(self.retry_delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::permission_change::{
+        entities::{
+            ChangeOperation, PermissionChange, PermissionChangeBatch, PermissionChangeEvent,
+        },
+        port::PermissionChangeStream,
+    };
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct MockSource {
+        batches: Vec<Result<PermissionChangeBatch, String>>,
+        subscribed_after: Arc<Mutex<Vec<Option<ZedToken>>>>,
+    }
+
+    impl PermissionChangeSource for MockSource {
+        async fn subscribe(
+            &self,
+            after: Option<ZedToken>,
+        ) -> Result<PermissionChangeStream, PermissionChangeError> {
+            self.subscribed_after.lock().unwrap().push(after);
+            let batches = self
+                .batches
+                .clone()
+                .into_iter()
+                .map(|batch| batch.map_err(|msg| PermissionChangeError::WatchError { msg }));
+            Ok(futures::stream::iter(batches).boxed())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockPublisher {
+        published: Arc<Mutex<Vec<PermissionChangeBatch>>>,
+        fail: bool,
+    }
+
+    impl PermissionChangePublisher for MockPublisher {
+        async fn publish(
+            &self,
+            batch: &PermissionChangeBatch,
+        ) -> Result<(), PermissionChangeError> {
+            if self.fail {
+                return Err(PermissionChangeError::PublishError {
+                    msg: "broker unavailable".to_string(),
+                });
+            }
+            self.published.lock().unwrap().push(batch.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockCheckpointStore {
+        checkpoint: Arc<Mutex<Option<ZedToken>>>,
+        fail_save: bool,
+    }
+
+    impl CheckpointStore for MockCheckpointStore {
+        async fn load(&self) -> Result<Option<ZedToken>, PermissionChangeError> {
+            Ok(self.checkpoint.lock().unwrap().clone())
+        }
+
+        async fn save(&self, revision: &ZedToken) -> Result<(), PermissionChangeError> {
+            if self.fail_save {
+                return Err(PermissionChangeError::CheckpointError {
+                    msg: "read-only file system".to_string(),
+                });
+            }
+            *self.checkpoint.lock().unwrap() = Some(revision.clone());
+            Ok(())
+        }
+    }
+
+    fn batch(revision: &str, change_count: usize) -> PermissionChangeBatch {
+        let change = PermissionChange {
+            operation: ChangeOperation::Removed,
+            event: PermissionChangeEvent::OverrideChannel {
+                override_id: "override_1".to_string(),
+                channel_id: "channel_1".to_string(),
+            },
+        };
+        PermissionChangeBatch {
+            changes: vec![change; change_count],
+            revision: ZedToken(revision.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_once_publishes_batches_and_checkpoints_last_revision() {
+        // Arrange
+        let source = MockSource {
+            batches: vec![Ok(batch("rev_1", 1)), Ok(batch("rev_2", 2))],
+            ..Default::default()
+        };
+        let publisher = MockPublisher::default();
+        let checkpoint_store = MockCheckpointStore::default();
+        let watcher = PermissionChangeWatcher::new(
+            source.clone(),
+            publisher.clone(),
+            checkpoint_store.clone(),
+        );
+
+        // Act
+        let result = watcher.run_once().await;
+
+        // Assert
+        assert!(result.is_ok());
+        let published = publisher.published.lock().unwrap();
+        assert_eq!(published.len(), 2);
+        assert_eq!(published[1].changes.len(), 2);
+        assert_eq!(
+            *checkpoint_store.checkpoint.lock().unwrap(),
+            Some(ZedToken("rev_2".to_string()))
+        );
+        assert_eq!(*source.subscribed_after.lock().unwrap(), vec![None]);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_resumes_after_saved_checkpoint() {
+        // Arrange
+        let source = MockSource::default();
+        let checkpoint_store = MockCheckpointStore {
+            checkpoint: Arc::new(Mutex::new(Some(ZedToken("rev_7".to_string())))),
+            ..Default::default()
+        };
+        let watcher = PermissionChangeWatcher::new(
+            source.clone(),
+            MockPublisher::default(),
+            checkpoint_store,
+        );
+
+        // Act
+        let result = watcher.run_once().await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(
+            *source.subscribed_after.lock().unwrap(),
+            vec![Some(ZedToken("rev_7".to_string()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_once_keeps_checkpoint_when_publish_fails() {
+        // Arrange
+        let source = MockSource {
+            batches: vec![Ok(batch("rev_1", 1))],
+            ..Default::default()
+        };
+        let publisher = MockPublisher {
+            fail: true,
+            ..Default::default()
+        };
+        let checkpoint_store = MockCheckpointStore::default();
+        let watcher = PermissionChangeWatcher::new(source, publisher, checkpoint_store.clone());
+
+        // Act
+        let result = watcher.run_once().await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(PermissionChangeError::PublishError { .. })
+        ));
+        assert_eq!(*checkpoint_store.checkpoint.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_checkpoints_empty_batches_without_publishing() {
+        // Arrange
+        let source = MockSource {
+            batches: vec![Ok(batch("rev_1", 0))],
+            ..Default::default()
+        };
+        let publisher = MockPublisher::default();
+        let checkpoint_store = MockCheckpointStore::default();
+        let watcher =
+            PermissionChangeWatcher::new(source, publisher.clone(), checkpoint_store.clone());
+
+        // Act
+        let result = watcher.run_once().await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(publisher.published.lock().unwrap().is_empty());
+        assert_eq!(
+            *checkpoint_store.checkpoint.lock().unwrap(),
+            Some(ZedToken("rev_1".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_once_stops_at_stream_error() {
+        // Arrange
+        let source = MockSource {
+            batches: vec![
+                Ok(batch("rev_1", 1)),
+                Err("connection reset".to_string()),
+                Ok(batch("rev_2", 1)),
+            ],
+            ..Default::default()
+        };
+        let publisher = MockPublisher::default();
+        let checkpoint_store = MockCheckpointStore::default();
+        let watcher =
+            PermissionChangeWatcher::new(source, publisher.clone(), checkpoint_store.clone());
+
+        // Act
+        let result = watcher.run_once().await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(PermissionChangeError::WatchError { .. })
+        ));
+        assert_eq!(publisher.published.lock().unwrap().len(), 1);
+        assert_eq!(
+            *checkpoint_store.checkpoint.lock().unwrap(),
+            Some(ZedToken("rev_1".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_once_resumes_after_published_revision_when_save_fails() {
+        // Arrange
+        let source = MockSource {
+            batches: vec![Ok(batch("rev_1", 1)), Ok(batch("rev_2", 1))],
+            ..Default::default()
+        };
+        let publisher = MockPublisher::default();
+        let checkpoint_store = MockCheckpointStore {
+            fail_save: true,
+            ..Default::default()
+        };
+        let watcher =
+            PermissionChangeWatcher::new(source.clone(), publisher.clone(), checkpoint_store)
+                .with_max_save_failures(5);
+
+        // Act
+        let first = watcher.run_once().await;
+        let second = watcher.run_once().await;
+
+        // Assert
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert_eq!(publisher.published.lock().unwrap().len(), 4);
+        assert_eq!(
+            *source.subscribed_after.lock().unwrap(),
+            vec![None, Some(ZedToken("rev_2".to_string()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_once_fails_after_consecutive_save_failures() {
+        // Arrange
+        let source = MockSource {
+            batches: vec![
+                Ok(batch("rev_1", 1)),
+                Ok(batch("rev_2", 1)),
+                Ok(batch("rev_3", 1)),
+            ],
+            ..Default::default()
+        };
+        let publisher = MockPublisher::default();
+        let checkpoint_store = MockCheckpointStore {
+            fail_save: true,
+            ..Default::default()
+        };
+        let watcher = PermissionChangeWatcher::new(source, publisher.clone(), checkpoint_store)
+            .with_max_save_failures(2);
+
+        // Act
+        let result = watcher.run_once().await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(PermissionChangeError::CheckpointError { .. })
+        ));
+        assert_eq!(publisher.published.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_when_checkpoint_cannot_be_saved() {
+        // Arrange
+        let source = MockSource {
+            batches: vec![Ok(batch("rev_1", 1))],
+            ..Default::default()
+        };
+        let checkpoint_store = MockCheckpointStore {
+            fail_save: true,
+            ..Default::default()
+        };
+        let watcher = PermissionChangeWatcher::new(
+            source.clone(),
+            MockPublisher::default(),
+            checkpoint_store,
+        )
+        .with_retry_delay(Duration::ZERO);
+
+        // Act
+        let result = watcher.run().await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(PermissionChangeError::CheckpointError { .. })
+        ));
+        assert_eq!(source.subscribed_after.lock().unwrap().len(), 3);
+    }
+}
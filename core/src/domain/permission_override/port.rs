@@ -1,6 +1,9 @@
-use crate::domain::permission_override::{
-    PermissionOverrideError,
-    entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
+use crate::domain::{
+    common::entities::ZedToken,
+    permission_override::{
+        PermissionOverrideError,
+        entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
+    },
 };
 use std::future::Future;
 
@@ -8,20 +11,20 @@ pub trait PermissionOverrideRepository: Send + Sync {
     fn create(
         &self,
         input: CreatePermissionOverrideInput,
-    ) -> impl Future<Output = Result<(), PermissionOverrideError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, PermissionOverrideError>> + Send;
     fn delete(
         &self,
         input: DeletePermissionOverrideInput,
-    ) -> impl Future<Output = Result<(), PermissionOverrideError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, PermissionOverrideError>> + Send;
 }
 
 pub trait PermissionOverrideService: Send + Sync {
     fn create(
         &self,
         input: CreatePermissionOverrideInput,
-    ) -> impl Future<Output = Result<(), PermissionOverrideError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, PermissionOverrideError>> + Send;
     fn delete(
         &self,
         input: DeletePermissionOverrideInput,
-    ) -> impl Future<Output = Result<(), PermissionOverrideError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, PermissionOverrideError>> + Send;
 }
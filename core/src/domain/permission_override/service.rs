@@ -1,6 +1,7 @@
 use crate::domain::{
     channel::port::ChannelRepository,
-    common::service::Service,
+    common::{entities::ZedToken, service::Service},
+    permission::port::PermissionRepository,
     permission_override::{
         PermissionOverrideError,
         entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
@@ -11,18 +12,19 @@ use crate::domain::{
 };
 use tracing::{info, instrument};
 
-impl<S, C, R, P> PermissionOverrideService for Service<S, C, R, P>
+impl<S, C, R, P, A> PermissionOverrideService for Service<S, C, R, P, A>
 where
     S: ServerRepository,
     C: ChannelRepository,
     R: RoleRepository,
     P: PermissionOverrideRepository,
+    A: PermissionRepository,
 {
     #[instrument(skip(self), fields(override_id = %input.override_id, channel_id = %input.channel_id))]
     async fn create(
         &self,
         input: CreatePermissionOverrideInput,
-    ) -> Result<(), PermissionOverrideError> {
+    ) -> Result<ZedToken, PermissionOverrideError> {
         info!(
             override_id = %input.override_id,
             channel_id = %input.channel_id,
@@ -42,7 +44,7 @@ where
     async fn delete(
         &self,
         input: DeletePermissionOverrideInput,
-    ) -> Result<(), PermissionOverrideError> {
+    ) -> Result<ZedToken, PermissionOverrideError> {
         info!(
             override_id = %input.override_id,
             "Deleting permission override in domain service"
@@ -62,13 +64,26 @@ mod tests {
     use crate::domain::{
         channel::{
             ChannelError,
-            entities::{CreateChannelInput, DeleteChannelInput},
+            entities::{CreateChannelInput, DeleteChannelInput, ListServerChannelsInput},
             port::ChannelRepository,
         },
+        common::entities::DeleteOutput,
+        permission::{
+            PermissionError,
+            entities::{
+                BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput,
+                LookupResourcesInput, LookupSubjectsInput, PermissionCheckResult,
+                PermissionExplanation, SubjectsLookup,
+            },
+            port::{PermissionChecker, PermissionRepository},
+        },
         permission_override::entities::OverrideTarget,
         role::{
             RoleError,
-            entities::{AssignMemberInput, CreateRoleInput, DeleteRoleInput, RemoveMemberInput},
+            entities::{
+                AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListServerRolesInput,
+                RemoveMemberInput,
+            },
             port::RoleRepository,
         },
         server::{
@@ -123,7 +138,7 @@ mod tests {
         async fn create(
             &self,
             input: CreatePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
+        ) -> Result<ZedToken, PermissionOverrideError> {
             *self.call_count.lock().unwrap() += 1;
             *self.last_create_input.lock().unwrap() = Some(input);
 
@@ -131,14 +146,14 @@ mod tests {
                 let msg = self.error_message.lock().unwrap().clone();
                 Err(PermissionOverrideError::CreateOverrideError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
 
         async fn delete(
             &self,
             input: DeletePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
+        ) -> Result<ZedToken, PermissionOverrideError> {
             *self.call_count.lock().unwrap() += 1;
             *self.last_delete_input.lock().unwrap() = Some(input);
 
@@ -146,7 +161,7 @@ mod tests {
                 let msg = self.error_message.lock().unwrap().clone();
                 Err(PermissionOverrideError::DeleteOverrideError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
     }
@@ -156,12 +171,15 @@ mod tests {
     struct MockServerRepository;
 
     impl ServerRepository for MockServerRepository {
-        async fn create(&self, _input: CreateServerInput) -> Result<(), ServerError> {
-            Ok(())
+        async fn create(&self, _input: CreateServerInput) -> Result<ZedToken, ServerError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn delete(&self, _input: DeleteServerInput) -> Result<(), ServerError> {
-            Ok(())
+        async fn delete(&self, _input: DeleteServerInput) -> Result<DeleteOutput, ServerError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
         }
     }
 
@@ -169,12 +187,22 @@ mod tests {
     struct MockChannelRepository;
 
     impl ChannelRepository for MockChannelRepository {
-        async fn create(&self, _input: CreateChannelInput) -> Result<(), ChannelError> {
-            Ok(())
+        async fn create(&self, _input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
         }
 
-        async fn delete(&self, _input: DeleteChannelInput) -> Result<(), ChannelError> {
-            Ok(())
+        async fn list_by_server(
+            &self,
+            _input: ListServerChannelsInput,
+        ) -> Result<Vec<String>, ChannelError> {
+            Ok(vec![])
         }
     }
 
@@ -182,20 +210,78 @@ mod tests {
     struct MockRoleRepository;
 
     impl RoleRepository for MockRoleRepository {
-        async fn create(&self, _input: CreateRoleInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn create(&self, _input: CreateRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn assign_member(&self, _input: AssignMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn remove_member(&self, _input: RemoveMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn list_by_server(
+            &self,
+            _input: ListServerRolesInput,
+        ) -> Result<Vec<String>, RoleError> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockPermissionRepository;
+
+    impl PermissionRepository for MockPermissionRepository {}
+
+    impl PermissionChecker for MockPermissionRepository {
+        async fn check(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            Ok(PermissionCheckResult::Denied)
+        }
+
+        async fn check_bulk(
+            &self,
+            _inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            Ok(vec![])
         }
 
-        async fn delete(&self, _input: DeleteRoleInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
         }
 
-        async fn assign_member(&self, _input: AssignMemberInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Err(PermissionError::ExplainPermissionError {
+                msg: "not supported by this mock".to_string(),
+            })
         }
 
-        async fn remove_member(&self, _input: RemoveMemberInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
         }
     }
 
@@ -208,6 +294,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         let input = CreatePermissionOverrideInput {
@@ -242,6 +329,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         let input = CreatePermissionOverrideInput {
@@ -275,6 +363,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         let input = CreatePermissionOverrideInput {
@@ -308,6 +397,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo,
+            MockPermissionRepository,
         );
 
         let input = CreatePermissionOverrideInput {
@@ -340,6 +430,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         // Act - create multiple overrides
@@ -383,6 +474,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         let input = DeletePermissionOverrideInput {
@@ -415,6 +507,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         let input = DeletePermissionOverrideInput {
@@ -448,6 +541,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         // Act - perform multiple operations
@@ -486,6 +580,7 @@ mod tests {
             MockChannelRepository,
             MockRoleRepository,
             mock_override_repo.clone(),
+            MockPermissionRepository,
         );
 
         let input = CreatePermissionOverrideInput {
@@ -1,3 +1,5 @@
+use crate::domain::common::entities::Consistency;
+
 #[derive(Debug, Clone)]
 pub struct CreateRoleInput {
     pub role_id: String,
@@ -21,3 +23,18 @@ pub struct RemoveMemberInput {
     pub user_id: String,
     pub role_id: String,
 }
+
+#[derive(Debug, Clone)]
+pub struct ListServerRolesInput {
+    pub server_id: String,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListManageableRolesInput {
+    pub user_id: String,
+    pub server_id: String,
+    /// Freshness required from SpiceDB
+    pub consistency: Consistency,
+}
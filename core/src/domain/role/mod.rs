@@ -14,4 +14,6 @@ pub enum RoleError {
     AssignMemberError { msg: String },
     #[error("Remove member error: {msg}")]
     RemoveMemberError { msg: String },
+    #[error("List roles error: {msg}")]
+    ListRolesError { msg: String },
 }
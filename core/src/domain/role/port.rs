@@ -1,31 +1,58 @@
-use crate::domain::role::{
-    RoleError,
-    entities::{AssignMemberInput, CreateRoleInput, DeleteRoleInput, RemoveMemberInput},
+use crate::domain::{
+    common::entities::ZedToken,
+    role::{
+        RoleError,
+        entities::{
+            AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListManageableRolesInput,
+            ListServerRolesInput, RemoveMemberInput,
+        },
+    },
 };
 use std::future::Future;
 
 pub trait RoleRepository: Send + Sync {
-    fn create(&self, input: CreateRoleInput) -> impl Future<Output = Result<(), RoleError>> + Send;
-    fn delete(&self, input: DeleteRoleInput) -> impl Future<Output = Result<(), RoleError>> + Send;
+    fn create(
+        &self,
+        input: CreateRoleInput,
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
+    fn delete(
+        &self,
+        input: DeleteRoleInput,
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
     fn assign_member(
         &self,
         input: AssignMemberInput,
-    ) -> impl Future<Output = Result<(), RoleError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
     fn remove_member(
         &self,
         input: RemoveMemberInput,
-    ) -> impl Future<Output = Result<(), RoleError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
+    fn list_by_server(
+        &self,
+        input: ListServerRolesInput,
+    ) -> impl Future<Output = Result<Vec<String>, RoleError>> + Send;
 }
 
 pub trait RoleService: Send + Sync {
-    fn create(&self, input: CreateRoleInput) -> impl Future<Output = Result<(), RoleError>> + Send;
-    fn delete(&self, input: DeleteRoleInput) -> impl Future<Output = Result<(), RoleError>> + Send;
+    fn create(
+        &self,
+        input: CreateRoleInput,
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
+    fn delete(
+        &self,
+        input: DeleteRoleInput,
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
     fn assign_member(
         &self,
         input: AssignMemberInput,
-    ) -> impl Future<Output = Result<(), RoleError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
     fn remove_member(
         &self,
         input: RemoveMemberInput,
-    ) -> impl Future<Output = Result<(), RoleError>> + Send;
+    ) -> impl Future<Output = Result<ZedToken, RoleError>> + Send;
+    /// IDs of the roles of a server the user can manage
+    fn list_manageable_roles(
+        &self,
+        input: ListManageableRolesInput,
+    ) -> impl Future<Output = Result<Vec<String>, RoleError>> + Send;
 }
@@ -1,25 +1,33 @@
 use crate::domain::{
     channel::port::ChannelRepository,
-    common::service::Service,
+    common::{entities::ZedToken, service::Service},
+    permission::{
+        entities::{CheckPermissionInput, PermissionCheckResult, Resource, RolePermission},
+        port::PermissionRepository,
+    },
     permission_override::port::PermissionOverrideRepository,
     role::{
         RoleError,
-        entities::{AssignMemberInput, CreateRoleInput, DeleteRoleInput, RemoveMemberInput},
+        entities::{
+            AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListManageableRolesInput,
+            ListServerRolesInput, RemoveMemberInput,
+        },
         port::{RoleRepository, RoleService},
     },
     server::port::ServerRepository,
 };
 use tracing::{info, instrument};
 
-impl<S, C, R, P> RoleService for Service<S, C, R, P>
+impl<S, C, R, P, A> RoleService for Service<S, C, R, P, A>
 where
     S: ServerRepository,
     C: ChannelRepository,
     R: RoleRepository,
     P: PermissionOverrideRepository,
+    A: PermissionRepository,
 {
     #[instrument(skip(self), fields(role_id = %input.role_id, server_id = %input.server_id, permissions_bitmask = %input.permissions_bitmask))]
-    async fn create(&self, input: CreateRoleInput) -> Result<(), RoleError> {
+    async fn create(&self, input: CreateRoleInput) -> Result<ZedToken, RoleError> {
         info!(
             role_id = %input.role_id,
             server_id = %input.server_id,
@@ -35,7 +43,7 @@ where
     }
 
     #[instrument(skip(self), fields(role_id = %input.role_id))]
-    async fn delete(&self, input: DeleteRoleInput) -> Result<(), RoleError> {
+    async fn delete(&self, input: DeleteRoleInput) -> Result<ZedToken, RoleError> {
         info!(
             role_id = %input.role_id,
             "Deleting role in domain service"
@@ -49,7 +57,7 @@ where
     }
 
     #[instrument(skip(self), fields(user_id = %input.user_id, role_id = %input.role_id))]
-    async fn assign_member(&self, input: AssignMemberInput) -> Result<(), RoleError> {
+    async fn assign_member(&self, input: AssignMemberInput) -> Result<ZedToken, RoleError> {
         info!(
             user_id = %input.user_id,
             role_id = %input.role_id,
@@ -64,7 +72,7 @@ where
     }
 
     #[instrument(skip(self), fields(user_id = %input.user_id, role_id = %input.role_id))]
-    async fn remove_member(&self, input: RemoveMemberInput) -> Result<(), RoleError> {
+    async fn remove_member(&self, input: RemoveMemberInput) -> Result<ZedToken, RoleError> {
         info!(
             user_id = %input.user_id,
             role_id = %input.role_id,
@@ -77,6 +85,64 @@ where
         }
         result
     }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, server_id = %input.server_id))]
+    async fn list_manageable_roles(
+        &self,
+        input: ListManageableRolesInput,
+    ) -> Result<Vec<String>, RoleError> {
+        info!(
+            user_id = %input.user_id,
+            server_id = %input.server_id,
+            "Listing manageable roles in domain service"
+        );
+
+        let server_roles = self
+            .role_repository
+            .list_by_server(ListServerRolesInput {
+                server_id: input.server_id,
+                consistency: input.consistency.clone(),
+            })
+            .await?;
+        if server_roles.is_empty() {
+            return Ok(server_roles);
+        }
+
+        // One bulk check over the server's roles only
+        let checks: Vec<CheckPermissionInput> = server_roles
+            .into_iter()
+            .map(|role_id| CheckPermissionInput {
+                user_id: input.user_id.clone(),
+                resource: Resource::Role(role_id),
+                permission: RolePermission::Manage.into(),
+                consistency: input.consistency.clone(),
+            })
+            .collect();
+        let items = self
+            .permission_repository
+            .check_bulk(checks)
+            .await
+            .map_err(|e| RoleError::ListRolesError { msg: e.to_string() })?;
+
+        let mut roles = Vec::new();
+        for item in items {
+            let result = item
+                .result
+                .map_err(|e| RoleError::ListRolesError { msg: e.to_string() })?;
+            // A conditional answer lacks caveat context, so the role stays out
+            if let (PermissionCheckResult::Allowed, Resource::Role(role_id)) =
+                (result, item.input.resource)
+            {
+                roles.push(role_id);
+            }
+        }
+
+        info!(
+            role_count = roles.len(),
+            "Manageable roles listed in domain service"
+        );
+        Ok(roles)
+    }
 }
 
 #[cfg(test)]
@@ -85,9 +151,18 @@ mod tests {
     use crate::domain::{
         channel::{
             ChannelError,
-            entities::{CreateChannelInput, DeleteChannelInput},
+            entities::{CreateChannelInput, DeleteChannelInput, ListServerChannelsInput},
             port::ChannelRepository,
         },
+        common::entities::{Consistency, DeleteOutput},
+        permission::{
+            PermissionError,
+            entities::{
+                BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput,
+                LookupResourcesInput, LookupSubjectsInput, PermissionExplanation, SubjectsLookup,
+            },
+            port::{PermissionChecker, PermissionRepository},
+        },
         permission_override::{
             PermissionOverrideError,
             entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
@@ -110,6 +185,7 @@ mod tests {
         last_create_input: Arc<Mutex<Option<CreateRoleInput>>>,
         last_assign_input: Arc<Mutex<Option<AssignMemberInput>>>,
         last_remove_input: Arc<Mutex<Option<RemoveMemberInput>>>,
+        server_roles: Arc<Mutex<Vec<String>>>,
     }
 
     impl MockRoleRepository {
@@ -121,9 +197,15 @@ mod tests {
                 last_create_input: Arc::new(Mutex::new(None)),
                 last_assign_input: Arc::new(Mutex::new(None)),
                 last_remove_input: Arc::new(Mutex::new(None)),
+                server_roles: Arc::new(Mutex::new(Vec::new())),
             }
         }
 
+        fn with_server_roles(self, role_ids: &[&str]) -> Self {
+            *self.server_roles.lock().unwrap() = role_ids.iter().map(|id| id.to_string()).collect();
+            self
+        }
+
         fn with_failure(self, error_msg: &str) -> Self {
             *self.should_fail.lock().unwrap() = true;
             *self.error_message.lock().unwrap() = error_msg.to_string();
@@ -148,7 +230,7 @@ mod tests {
     }
 
     impl RoleRepository for MockRoleRepository {
-        async fn create(&self, input: CreateRoleInput) -> Result<(), RoleError> {
+        async fn create(&self, input: CreateRoleInput) -> Result<ZedToken, RoleError> {
             *self.call_count.lock().unwrap() += 1;
             *self.last_create_input.lock().unwrap() = Some(input);
 
@@ -156,21 +238,21 @@ mod tests {
                 let msg = self.error_message.lock().unwrap().clone();
                 Err(RoleError::CreateRoleError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
 
-        async fn delete(&self, _input: DeleteRoleInput) -> Result<(), RoleError> {
+        async fn delete(&self, _input: DeleteRoleInput) -> Result<ZedToken, RoleError> {
             *self.call_count.lock().unwrap() += 1;
             if *self.should_fail.lock().unwrap() {
                 let msg = self.error_message.lock().unwrap().clone();
                 Err(RoleError::DeleteRoleError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
 
-        async fn assign_member(&self, input: AssignMemberInput) -> Result<(), RoleError> {
+        async fn assign_member(&self, input: AssignMemberInput) -> Result<ZedToken, RoleError> {
             *self.call_count.lock().unwrap() += 1;
             *self.last_assign_input.lock().unwrap() = Some(input);
 
@@ -178,11 +260,11 @@ mod tests {
                 let msg = self.error_message.lock().unwrap().clone();
                 Err(RoleError::AssignMemberError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
 
-        async fn remove_member(&self, input: RemoveMemberInput) -> Result<(), RoleError> {
+        async fn remove_member(&self, input: RemoveMemberInput) -> Result<ZedToken, RoleError> {
             *self.call_count.lock().unwrap() += 1;
             *self.last_remove_input.lock().unwrap() = Some(input);
 
@@ -190,9 +272,16 @@ mod tests {
                 let msg = self.error_message.lock().unwrap().clone();
                 Err(RoleError::RemoveMemberError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
+
+        async fn list_by_server(
+            &self,
+            _input: ListServerRolesInput,
+        ) -> Result<Vec<String>, RoleError> {
+            Ok(self.server_roles.lock().unwrap().clone())
+        }
     }
 
     // Stub repositories for other dependencies
@@ -200,12 +289,15 @@ mod tests {
     struct MockServerRepository;
 
     impl ServerRepository for MockServerRepository {
-        async fn create(&self, _input: CreateServerInput) -> Result<(), ServerError> {
-            Ok(())
+        async fn create(&self, _input: CreateServerInput) -> Result<ZedToken, ServerError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn delete(&self, _input: DeleteServerInput) -> Result<(), ServerError> {
-            Ok(())
+        async fn delete(&self, _input: DeleteServerInput) -> Result<DeleteOutput, ServerError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
         }
     }
 
@@ -213,12 +305,22 @@ mod tests {
     struct MockChannelRepository;
 
     impl ChannelRepository for MockChannelRepository {
-        async fn create(&self, _input: CreateChannelInput) -> Result<(), ChannelError> {
-            Ok(())
+        async fn create(&self, _input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
         }
 
-        async fn delete(&self, _input: DeleteChannelInput) -> Result<(), ChannelError> {
-            Ok(())
+        async fn list_by_server(
+            &self,
+            _input: ListServerChannelsInput,
+        ) -> Result<Vec<String>, ChannelError> {
+            Ok(vec![])
         }
     }
 
@@ -229,15 +331,139 @@ mod tests {
         async fn create(
             &self,
             _input: CreatePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
-            Ok(())
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
         async fn delete(
             &self,
             _input: DeletePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
-            Ok(())
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockPermissionRepository;
+
+    impl PermissionRepository for MockPermissionRepository {}
+
+    impl PermissionChecker for MockPermissionRepository {
+        async fn check(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            Ok(PermissionCheckResult::Denied)
+        }
+
+        async fn check_bulk(
+            &self,
+            _inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
+        }
+
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Err(PermissionError::ExplainPermissionError {
+                msg: "not supported by this mock".to_string(),
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
+        }
+    }
+
+    // Permission repository that only answers bulk checks, with a fixed set of manageable IDs
+    #[derive(Clone, Default)]
+    struct MockManageabilityRepository {
+        manageable_ids: Vec<String>,
+        checked: Arc<Mutex<Vec<CheckPermissionInput>>>,
+    }
+
+    impl PermissionRepository for MockManageabilityRepository {}
+
+    impl PermissionChecker for MockManageabilityRepository {
+        async fn check(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            Ok(PermissionCheckResult::Denied)
+        }
+
+        async fn check_bulk(
+            &self,
+            inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            self.checked.lock().unwrap().extend(inputs.clone());
+            Ok(inputs
+                .into_iter()
+                .map(|input| {
+                    assert_eq!(input.permission, RolePermission::Manage.into());
+                    let result = match &input.resource {
+                        Resource::Role(id) if id == "role_broken" => {
+                            Err(PermissionError::BulkCheckPermissionError {
+                                msg: "item failed".to_string(),
+                            })
+                        }
+                        Resource::Role(id) if self.manageable_ids.contains(id) => {
+                            Ok(PermissionCheckResult::Allowed)
+                        }
+                        _ => Ok(PermissionCheckResult::Denied),
+                    };
+                    BulkCheckItem { input, result }
+                })
+                .collect())
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            panic!("manageable roles must not be looked up across every server")
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
+        }
+
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Err(PermissionError::ExplainPermissionError {
+                msg: "not supported by this mock".to_string(),
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
         }
     }
 
@@ -253,6 +479,7 @@ mod tests {
             mock_channel_repo,
             mock_role_repo.clone(),
             mock_override_repo,
+            MockPermissionRepository,
         );
 
         let input = CreateRoleInput {
@@ -283,6 +510,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateRoleInput {
@@ -314,6 +542,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateRoleInput {
@@ -344,6 +573,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateRoleInput {
@@ -374,6 +604,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         // Act - create multiple roles
@@ -412,6 +643,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = DeleteRoleInput {
@@ -435,6 +667,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = AssignMemberInput {
@@ -463,6 +696,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = AssignMemberInput {
@@ -493,6 +727,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = RemoveMemberInput {
@@ -521,6 +756,7 @@ mod tests {
             MockChannelRepository,
             mock_role_repo.clone(),
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         // Act - perform multiple operations
@@ -559,4 +795,105 @@ mod tests {
         assert!(delete_result.is_ok());
         assert_eq!(mock_role_repo.get_call_count(), 4);
     }
+
+    #[tokio::test]
+    async fn test_list_manageable_roles_keeps_server_roles_the_user_can_manage() {
+        // Arrange
+        let mock_role_repo =
+            MockRoleRepository::new().with_server_roles(&["role_admin", "role_mod", "role_member"]);
+        let manageability = MockManageabilityRepository {
+            manageable_ids: vec!["role_member".to_string(), "role_mod".to_string()],
+            ..Default::default()
+        };
+        let service = Service::new(
+            MockServerRepository,
+            MockChannelRepository,
+            mock_role_repo,
+            MockPermissionOverrideRepository,
+            manageability.clone(),
+        );
+
+        // Act
+        let result = service
+            .list_manageable_roles(ListManageableRolesInput {
+                user_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), vec!["role_mod", "role_member"]);
+        let checked: Vec<Resource> = manageability
+            .checked
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|input| input.resource.clone())
+            .collect();
+        assert_eq!(
+            checked,
+            vec![
+                Resource::Role("role_admin".to_string()),
+                Resource::Role("role_mod".to_string()),
+                Resource::Role("role_member".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_manageable_roles_fails_when_a_check_fails() {
+        // Arrange
+        let mock_role_repo =
+            MockRoleRepository::new().with_server_roles(&["role_1", "role_broken"]);
+        let manageability = MockManageabilityRepository {
+            manageable_ids: vec!["role_1".to_string()],
+            ..Default::default()
+        };
+        let service = Service::new(
+            MockServerRepository,
+            MockChannelRepository,
+            mock_role_repo,
+            MockPermissionOverrideRepository,
+            manageability,
+        );
+
+        // Act
+        let result = service
+            .list_manageable_roles(ListManageableRolesInput {
+                user_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(RoleError::ListRolesError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_list_manageable_roles_skips_check_without_roles() {
+        // Arrange
+        let manageability = MockManageabilityRepository::default();
+        let service = Service::new(
+            MockServerRepository,
+            MockChannelRepository,
+            MockRoleRepository::new(),
+            MockPermissionOverrideRepository,
+            manageability.clone(),
+        );
+
+        // Act
+        let result = service
+            .list_manageable_roles(ListManageableRolesInput {
+                user_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(result.unwrap().is_empty());
+        assert!(manageability.checked.lock().unwrap().is_empty());
+    }
 }
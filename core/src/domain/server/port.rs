@@ -1,14 +1,29 @@
-use crate::domain::server::{
-    ServerError,
-    entities::{CreateServerInput, DeleteServerInput},
+use crate::domain::{
+    common::entities::{DeleteOutput, ZedToken},
+    server::{
+        ServerError,
+        entities::{CreateServerInput, DeleteServerInput},
+    },
 };
 
 pub trait ServerRepository: Send + Sync {
-    fn create(&self, input: CreateServerInput) -> impl Future<Output = Result<(), ServerError>>;
-    fn delete(&self, input: DeleteServerInput) -> impl Future<Output = Result<(), ServerError>>;
+    fn create(
+        &self,
+        input: CreateServerInput,
+    ) -> impl Future<Output = Result<ZedToken, ServerError>>;
+    fn delete(
+        &self,
+        input: DeleteServerInput,
+    ) -> impl Future<Output = Result<DeleteOutput, ServerError>>;
 }
 
 pub trait ServerService: Send + Sync {
-    fn create(&self, input: CreateServerInput) -> impl Future<Output = Result<(), ServerError>>;
-    fn delete(&self, input: DeleteServerInput) -> impl Future<Output = Result<(), ServerError>>;
+    fn create(
+        &self,
+        input: CreateServerInput,
+    ) -> impl Future<Output = Result<ZedToken, ServerError>>;
+    fn delete(
+        &self,
+        input: DeleteServerInput,
+    ) -> impl Future<Output = Result<DeleteOutput, ServerError>>;
 }
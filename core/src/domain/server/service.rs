@@ -1,6 +1,10 @@
 use crate::domain::{
     channel::port::ChannelRepository,
-    common::service::Service,
+    common::{
+        entities::{DeleteOutput, ZedToken},
+        service::Service,
+    },
+    permission::port::PermissionRepository,
     permission_override::port::PermissionOverrideRepository,
     role::port::RoleRepository,
     server::{
@@ -11,15 +15,16 @@ use crate::domain::{
 };
 use tracing::{info, instrument};
 
-impl<S, C, R, P> ServerService for Service<S, C, R, P>
+impl<S, C, R, P, A> ServerService for Service<S, C, R, P, A>
 where
     S: ServerRepository,
     C: ChannelRepository,
     R: RoleRepository,
     P: PermissionOverrideRepository,
+    A: PermissionRepository,
 {
     #[instrument(skip(self), fields(server_id = %input.server_id, owner_id = %input.owner_id))]
-    async fn create(&self, input: CreateServerInput) -> Result<(), ServerError> {
+    async fn create(&self, input: CreateServerInput) -> Result<ZedToken, ServerError> {
         info!(
             server_id = %input.server_id,
             owner_id = %input.owner_id,
@@ -34,7 +39,7 @@ where
     }
 
     #[instrument(skip(self), fields(server_id = %input.server_id))]
-    async fn delete(&self, input: DeleteServerInput) -> Result<(), ServerError> {
+    async fn delete(&self, input: DeleteServerInput) -> Result<DeleteOutput, ServerError> {
         info!(
             server_id = %input.server_id,
             "Delete server in domain service"
@@ -54,9 +59,18 @@ mod tests {
     use crate::domain::{
         channel::{
             ChannelError,
-            entities::{CreateChannelInput, DeleteChannelInput},
+            entities::{CreateChannelInput, DeleteChannelInput, ListServerChannelsInput},
             port::ChannelRepository,
         },
+        permission::{
+            PermissionError,
+            entities::{
+                BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput,
+                LookupResourcesInput, LookupSubjectsInput, PermissionCheckResult,
+                PermissionExplanation, SubjectsLookup,
+            },
+            port::{PermissionChecker, PermissionRepository},
+        },
         permission_override::{
             PermissionOverrideError,
             entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
@@ -64,7 +78,10 @@ mod tests {
         },
         role::{
             RoleError,
-            entities::{AssignMemberInput, CreateRoleInput, DeleteRoleInput, RemoveMemberInput},
+            entities::{
+                AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListServerRolesInput,
+                RemoveMemberInput,
+            },
             port::RoleRepository,
         },
     };
@@ -105,7 +122,7 @@ mod tests {
     }
 
     impl ServerRepository for MockServerRepository {
-        async fn create(&self, input: CreateServerInput) -> Result<(), ServerError> {
+        async fn create(&self, input: CreateServerInput) -> Result<ZedToken, ServerError> {
             *self.call_count.lock().unwrap() += 1;
             *self.last_input.lock().unwrap() = Some(input);
 
@@ -113,12 +130,15 @@ mod tests {
                 let msg = self.error_message.lock().unwrap().clone();
                 Err(ServerError::CreateServerError { msg })
             } else {
-                Ok(())
+                Ok(ZedToken("zed_token".to_string()))
             }
         }
 
-        async fn delete(&self, _input: DeleteServerInput) -> Result<(), ServerError> {
-            Ok(())
+        async fn delete(&self, _input: DeleteServerInput) -> Result<DeleteOutput, ServerError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
         }
     }
 
@@ -126,12 +146,22 @@ mod tests {
     struct MockChannelRepository;
 
     impl ChannelRepository for MockChannelRepository {
-        async fn create(&self, _input: CreateChannelInput) -> Result<(), ChannelError> {
-            Ok(())
+        async fn create(&self, _input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
+            Ok(DeleteOutput {
+                deleted_at: ZedToken("zed_token".to_string()),
+                deleted_count: 1,
+            })
         }
 
-        async fn delete(&self, _input: DeleteChannelInput) -> Result<(), ChannelError> {
-            Ok(())
+        async fn list_by_server(
+            &self,
+            _input: ListServerChannelsInput,
+        ) -> Result<Vec<String>, ChannelError> {
+            Ok(vec![])
         }
     }
 
@@ -139,20 +169,27 @@ mod tests {
     struct MockRoleRepository;
 
     impl RoleRepository for MockRoleRepository {
-        async fn create(&self, _input: CreateRoleInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn create(&self, _input: CreateRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+
+        async fn delete(&self, _input: DeleteRoleInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn delete(&self, _input: DeleteRoleInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn assign_member(&self, _input: AssignMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn assign_member(&self, _input: AssignMemberInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn remove_member(&self, _input: RemoveMemberInput) -> Result<ZedToken, RoleError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
-        async fn remove_member(&self, _input: RemoveMemberInput) -> Result<(), RoleError> {
-            Ok(())
+        async fn list_by_server(
+            &self,
+            _input: ListServerRolesInput,
+        ) -> Result<Vec<String>, RoleError> {
+            Ok(vec![])
         }
     }
 
@@ -163,15 +200,66 @@ mod tests {
         async fn create(
             &self,
             _input: CreatePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
-            Ok(())
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
         }
 
         async fn delete(
             &self,
             _input: DeletePermissionOverrideInput,
-        ) -> Result<(), PermissionOverrideError> {
-            Ok(())
+        ) -> Result<ZedToken, PermissionOverrideError> {
+            Ok(ZedToken("zed_token".to_string()))
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockPermissionRepository;
+
+    impl PermissionRepository for MockPermissionRepository {}
+
+    impl PermissionChecker for MockPermissionRepository {
+        async fn check(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            Ok(PermissionCheckResult::Denied)
+        }
+
+        async fn check_bulk(
+            &self,
+            _inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
+        }
+
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Err(PermissionError::ExplainPermissionError {
+                msg: "not supported by this mock".to_string(),
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
         }
     }
 
@@ -185,6 +273,7 @@ mod tests {
             mock_channel_repo,
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateServerInput {
@@ -214,6 +303,7 @@ mod tests {
             mock_channel_repo,
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateServerInput {
@@ -245,6 +335,7 @@ mod tests {
             mock_channel_repo,
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateServerInput {
@@ -274,6 +365,7 @@ mod tests {
             mock_channel_repo,
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         let input = CreateServerInput {
@@ -304,6 +396,7 @@ mod tests {
             mock_channel_repo,
             MockRoleRepository,
             MockPermissionOverrideRepository,
+            MockPermissionRepository,
         );
 
         // Act - create multiple servers
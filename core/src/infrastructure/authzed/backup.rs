@@ -0,0 +1,332 @@
+//! Versioned backups of the Beep relationships: a few `//` header lines, then one
+//! relationship per line in zed syntax, e.g. `server:server_1#owner@user:user_1`
+
+use std::{
+    io::{self, BufRead, Write},
+    time::SystemTime,
+};
+
+use futures::TryStreamExt;
+use prost_types::Timestamp;
+use thiserror::Error;
+use tracing::info;
+
+use crate::{
+    authzed::api::v1::{ContextualizedCaveat, ObjectReference, Relationship, SubjectReference},
+    domain::common::entities::Consistency,
+    infrastructure::authzed::{AuthZedClient, entities::ImportSummary, error::AuthzedError},
+};
+
+pub const BACKUP_HEADER: &str = "// beep-authz relationships backup";
+/// Format version written by `export_backup`, the only one `read_backup` accepts
+pub const BACKUP_VERSION: u32 = 1;
+/// Definitions whose relationships are backed up
+pub const BACKUP_DEFINITIONS: [&str; 4] = ["server", "channel", "role", "permission_override"];
+
+const VERSION_PREFIX: &str = "// version: ";
+
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("Could not access backup file: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Authzed(#[from] AuthzedError),
+
+    #[error("Backup has no version line")]
+    MissingVersion,
+
+    #[error("Unsupported backup version {version}, expected {BACKUP_VERSION}")]
+    UnsupportedVersion { version: String },
+
+    #[error("Invalid relationship on line {line}: {text}")]
+    InvalidLine { line: usize, text: String },
+
+    #[error("Cannot back up relationship {relationship}: {msg}")]
+    Unsupported { relationship: String, msg: String },
+}
+
+/// Outcome of an export
+#[derive(Debug, Clone, Default)]
+pub struct BackupSummary {
+    pub exported: u64,
+    /// Relationships of definitions outside `BACKUP_DEFINITIONS`
+    pub skipped: u64,
+}
+
+/// Write every relationship of `BACKUP_DEFINITIONS` to `writer`, read from a single
+/// SpiceDB revision as it streams in
+pub async fn export_backup(
+    client: &AuthZedClient,
+    mut writer: impl Write,
+) -> Result<BackupSummary, BackupError> {
+    writeln!(writer, "{}", BACKUP_HEADER)?;
+    writeln!(writer, "{}{}", VERSION_PREFIX, BACKUP_VERSION)?;
+    writeln!(
+        writer,
+        "// exported_at: {}",
+        Timestamp::from(SystemTime::now())
+    )?;
+
+    let mut summary = BackupSummary::default();
+    let mut batches = client
+        .export_relationships_stream(Consistency::FullyConsistent)
+        .await?;
+    while let Some(batch) = batches.try_next().await? {
+        for relationship in batch {
+            let resource_type = relationship
+                .resource
+                .as_ref()
+                .map(|resource| resource.object_type.as_str());
+            if !resource_type.is_some_and(|t| BACKUP_DEFINITIONS.contains(&t)) {
+                summary.skipped += 1;
+                continue;
+            }
+            writeln!(writer, "{}", format_relationship(&relationship)?)?;
+            summary.exported += 1;
+        }
+    }
+    writer.flush()?;
+
+    info!(
+        exported = summary.exported,
+        skipped = summary.skipped,
+        "Relationships backed up"
+    );
+    Ok(summary)
+}
+
+/// The relationships of a backup written by `export_backup`
+pub fn read_backup(reader: impl BufRead) -> Result<Vec<Relationship>, BackupError> {
+    let mut version_checked = false;
+    let mut relationships = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if let Some(version) = line.strip_prefix(VERSION_PREFIX) {
+            if version != BACKUP_VERSION.to_string() {
+                return Err(BackupError::UnsupportedVersion {
+                    version: version.to_string(),
+                });
+            }
+            version_checked = true;
+            continue;
+        }
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        if !version_checked {
+            return Err(BackupError::MissingVersion);
+        }
+        let relationship = parse_relationship(line).ok_or_else(|| BackupError::InvalidLine {
+            line: index + 1,
+            text: line.to_string(),
+        })?;
+        relationships.push(relationship);
+    }
+    Ok(relationships)
+}
+
+/// Load a backup into SpiceDB through the bulk import path
+pub async fn restore_backup(
+    client: &AuthZedClient,
+    reader: impl BufRead,
+) -> Result<ImportSummary, BackupError> {
+    let relationships = read_backup(reader)?;
+    info!(
+        count = relationships.len(),
+        "Restoring relationships backup"
+    );
+    Ok(client.import_relationships(relationships).await?)
+}
+
+/// `type:id#relation@type:id[#relation][caveat][expiration:time]`; caveat contexts
+/// have no zed syntax and are refused rather than silently dropped
+pub fn format_relationship(relationship: &Relationship) -> Result<String, BackupError> {
+    let resource = relationship.resource.clone().unwrap_or_default();
+    let subject = relationship.subject.clone().unwrap_or_default();
+    let subject_object = subject.object.unwrap_or_default();
+    let mut line = format!(
+        "{}:{}#{}@{}:{}",
+        resource.object_type,
+        resource.object_id,
+        relationship.relation,
+        subject_object.object_type,
+        subject_object.object_id
+    );
+    if !subject.optional_relation.is_empty() {
+        line.push('#');
+        line.push_str(&subject.optional_relation);
+    }
+    if let Some(caveat) = &relationship.optional_caveat {
+        if caveat
+            .context
+            .as_ref()
+            .is_some_and(|c| !c.fields.is_empty())
+        {
+            return Err(BackupError::Unsupported {
+                relationship: line,
+                msg: format!("caveat {} has a context", caveat.caveat_name),
+            });
+        }
+        line.push_str(&format!("[{}]", caveat.caveat_name));
+    }
+    if let Some(expires_at) = &relationship.optional_expires_at {
+        line.push_str(&format!("[expiration:{}]", expires_at));
+    }
+    Ok(line)
+}
+
+/// Inverse of `format_relationship`, `None` if the line is not a relationship
+pub fn parse_relationship(line: &str) -> Option<Relationship> {
+    let (tuple, mut suffixes) = line.split_at(line.find('[').unwrap_or(line.len()));
+    let (resource, subject) = tuple.split_once('@')?;
+    let (resource, relation) = resource.split_once('#')?;
+    let (resource_type, resource_id) = resource.split_once(':')?;
+    let (subject, subject_relation) = subject.split_once('#').unwrap_or((subject, ""));
+    let (subject_type, subject_id) = subject.split_once(':')?;
+    if [
+        resource_type,
+        resource_id,
+        relation,
+        subject_type,
+        subject_id,
+    ]
+    .iter()
+    .any(|part| part.is_empty())
+    {
+        return None;
+    }
+
+    let mut relationship = Relationship {
+        resource: Some(ObjectReference {
+            object_type: resource_type.to_string(),
+            object_id: resource_id.to_string(),
+        }),
+        relation: relation.to_string(),
+        subject: Some(SubjectReference {
+            object: Some(ObjectReference {
+                object_type: subject_type.to_string(),
+                object_id: subject_id.to_string(),
+            }),
+            optional_relation: subject_relation.to_string(),
+        }),
+        optional_caveat: None,
+        optional_expires_at: None,
+    };
+    while !suffixes.is_empty() {
+        let (suffix, rest) = suffixes.strip_prefix('[')?.split_once(']')?;
+        match suffix.strip_prefix("expiration:") {
+            Some(expires_at) => relationship.optional_expires_at = Some(expires_at.parse().ok()?),
+            None => {
+                relationship.optional_caveat = Some(ContextualizedCaveat {
+                    caveat_name: suffix.to_string(),
+                    context: None,
+                })
+            }
+        }
+        suffixes = rest;
+    }
+    Some(relationship)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::authzed::{
+        entities::ImportPath,
+        fake::{FakeSpiceDb, fast_retries},
+    };
+    use std::sync::Arc;
+
+    fn relationship(line: &str) -> Relationship {
+        parse_relationship(line).unwrap()
+    }
+
+    #[test]
+    fn test_format_and_parse_round_trip() {
+        for line in [
+            "server:server_1#owner@user:user_1",
+            "server:server_1#message_sender@role:role_1#member",
+            "channel:channel_1#view_channel_deny@permission_override:override_1#denied_to",
+            "role:role_1#member@user:user_2[expiration:2030-01-01T00:00:00Z]",
+            "role:role_1#member@user:user_3[during_event][expiration:2030-01-01T00:00:00Z]",
+        ] {
+            let relationship = relationship(line);
+
+            assert_eq!(format_relationship(&relationship).unwrap(), line);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        for line in [
+            "server:server_1#owner",
+            "server:server_1@user:user_1",
+            "server#owner@user:user_1",
+            "server:server_1#owner@user:",
+            "role:role_1#member@user:user_2[expiration:tomorrow]",
+        ] {
+            assert!(parse_relationship(line).is_none(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_read_backup_rejects_other_versions() {
+        let backup = format!("{}\n// version: 2\nserver:s#owner@user:u\n", BACKUP_HEADER);
+
+        let error = read_backup(backup.as_bytes()).unwrap_err();
+
+        assert!(matches!(error, BackupError::UnsupportedVersion { .. }));
+    }
+
+    #[test]
+    fn test_read_backup_requires_a_version() {
+        let error = read_backup("server:s#owner@user:u\n".as_bytes()).unwrap_err();
+
+        assert!(matches!(error, BackupError::MissingVersion));
+    }
+
+    #[tokio::test]
+    async fn test_restore_recovers_exported_relationships() {
+        // Arrange
+        let lost = Arc::new(FakeSpiceDb::default());
+        let lost_client = lost.serve(fast_retries(0)).await;
+        let beep_relationships: Vec<_> = (0..250)
+            .map(|i| {
+                relationship(&format!(
+                    "server:server_1#message_sender@role:role_{}#member",
+                    i
+                ))
+            })
+            .chain([
+                relationship("server:server_1#owner@user:user_1"),
+                relationship("channel:channel_1#server@server:server_1"),
+                relationship("role:role_1#server@server:server_1"),
+                relationship("permission_override:override_1#channel@channel:channel_1"),
+            ])
+            .collect();
+        let mut relationships = beep_relationships.clone();
+        relationships.push(relationship("document:doc_1#viewer@user:user_1"));
+        lost_client
+            .import_relationships(relationships)
+            .await
+            .unwrap();
+        let mut backup = Vec::new();
+
+        // Act
+        let exported = export_backup(&lost_client, &mut backup).await.unwrap();
+        let recovered = Arc::new(FakeSpiceDb::default());
+        let recovered_client = recovered.serve(fast_retries(0)).await;
+        let restored = restore_backup(&recovered_client, backup.as_slice())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(exported.exported, 254);
+        assert_eq!(exported.skipped, 1);
+        assert_eq!(restored.path, ImportPath::BulkImport);
+        assert_eq!(restored.loaded, 254);
+        assert_eq!(recovered.relationships(), beep_relationships);
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use clap::Parser;
+use tonic::transport::Endpoint;
+
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5_000;
+const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_KEEPALIVE_INTERVAL_SECS: u64 = 30;
+const DEFAULT_KEEPALIVE_TIMEOUT_SECS: u64 = 10;
+
+/// Timeouts and liveness checks of the SpiceDB connection
+#[derive(Debug, Clone, Parser)]
+pub struct AuthZedConnectionConfig {
+    /// Time allowed to establish the connection
+    #[arg(
+        long = "authzed-connect-timeout-ms",
+        env = "AUTHZED_CONNECT_TIMEOUT_MS",
+        default_value_t = DEFAULT_CONNECT_TIMEOUT_MS
+    )]
+    pub connect_timeout_ms: u64,
+
+    /// Deadline of every call except the Watch stream; 0 disables it
+    #[arg(
+        long = "authzed-request-timeout-ms",
+        env = "AUTHZED_REQUEST_TIMEOUT_MS",
+        default_value_t = DEFAULT_REQUEST_TIMEOUT_MS
+    )]
+    pub request_timeout_ms: u64,
+
+    /// Interval of HTTP/2 pings detecting a dead connection; 0 disables them
+    #[arg(
+        long = "authzed-keepalive-interval-secs",
+        env = "AUTHZED_KEEPALIVE_INTERVAL_SECS",
+        default_value_t = DEFAULT_KEEPALIVE_INTERVAL_SECS
+    )]
+    pub keepalive_interval_secs: u64,
+
+    /// Time to wait for a ping acknowledgement before closing the connection
+    #[arg(
+        long = "authzed-keepalive-timeout-secs",
+        env = "AUTHZED_KEEPALIVE_TIMEOUT_SECS",
+        default_value_t = DEFAULT_KEEPALIVE_TIMEOUT_SECS
+    )]
+    pub keepalive_timeout_secs: u64,
+
+    /// Connect on the first call instead of at startup, so the service starts while SpiceDB is down
+    #[arg(
+        long = "authzed-lazy-connect",
+        env = "AUTHZED_LAZY_CONNECT",
+        default_value_t = false
+    )]
+    pub lazy_connect: bool,
+}
+
+impl Default for AuthZedConnectionConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: DEFAULT_CONNECT_TIMEOUT_MS,
+            request_timeout_ms: DEFAULT_REQUEST_TIMEOUT_MS,
+            keepalive_interval_secs: DEFAULT_KEEPALIVE_INTERVAL_SECS,
+            keepalive_timeout_secs: DEFAULT_KEEPALIVE_TIMEOUT_SECS,
+            lazy_connect: false,
+        }
+    }
+}
+
+impl AuthZedConnectionConfig {
+    /// Deadline of a call, `None` when disabled
+    pub fn request_timeout(&self) -> Option<Duration> {
+        (self.request_timeout_ms > 0).then(|| Duration::from_millis(self.request_timeout_ms))
+    }
+
+    /// The endpoint with the connect timeout and keepalive settings applied
+    pub fn apply(&self, endpoint: Endpoint) -> Endpoint {
+        let endpoint = endpoint.connect_timeout(Duration::from_millis(self.connect_timeout_ms));
+        if self.keepalive_interval_secs == 0 {
+            return endpoint;
+        }
+        endpoint
+            .http2_keep_alive_interval(Duration::from_secs(self.keepalive_interval_secs))
+            .keep_alive_timeout(Duration::from_secs(self.keepalive_timeout_secs))
+            // Idle connections are the ones that silently die behind load balancers
+            .keep_alive_while_idle(true)
+    }
+}
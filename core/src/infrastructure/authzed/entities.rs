@@ -1,6 +1,12 @@
 use std::fmt::Display;
 
-use crate::authzed::api::v1::{Relationship, RelationshipUpdate};
+use crate::{
+    authzed::api::v1::{
+        Consistency, Cursor, Precondition, Relationship, RelationshipFilter, RelationshipUpdate,
+        SubjectFilter, ZedToken, consistency, precondition, subject_filter,
+    },
+    domain::common::entities as domain,
+};
 
 #[derive(Debug)]
 pub enum Operation {
@@ -67,3 +73,339 @@ impl<T: Into<Relationship> + Clone> Action for T {
         relationship_update
     }
 }
+
+/// One page of a cursor-paginated SpiceDB call
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor to resume after the last item, `None` once the results are exhausted
+    pub next_cursor: Option<Cursor>,
+}
+
+/// Outcome of a filtered delete sent in batches
+#[derive(Debug, Clone)]
+pub struct DeleteSummary {
+    /// Token of the last batch, covering every deletion
+    pub deleted_at: ZedToken,
+    /// Relationships deleted, as counted by SpiceDB
+    pub deleted_count: u64,
+    /// DeleteRelationships calls sent
+    pub batches: u32,
+}
+
+impl From<DeleteSummary> for domain::DeleteOutput {
+    fn from(summary: DeleteSummary) -> Self {
+        Self {
+            deleted_at: summary.deleted_at.into(),
+            deleted_count: summary.deleted_count,
+        }
+    }
+}
+
+/// How `import_relationships` loaded the relationships
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportPath {
+    /// One ImportBulkRelationships transaction
+    BulkImport,
+    /// Chunked WriteRelationships calls touching the relationships
+    ChunkedWrites,
+}
+
+/// Outcome of a bulk import
+#[derive(Debug, Clone)]
+pub struct ImportSummary {
+    /// Relationships loaded, as counted by SpiceDB for a bulk import
+    pub loaded: u64,
+    pub path: ImportPath,
+}
+
+/// Updates turning one set of relationships into another in a single write,
+/// with the preconditions making that write fail if the state it was computed from changed meanwhile
+#[derive(Debug, Clone, Default)]
+pub struct RelationshipDiff {
+    pub updates: Vec<RelationshipUpdate>,
+    pub preconditions: Vec<Precondition>,
+}
+
+impl RelationshipDiff {
+    /// Delete what is in `existing` only and touch what is in `desired` only.
+    /// The write only applies to the exact state that was read: every relationship of
+    /// `existing` must still exist, and every `vacant` filter, covering what the read
+    /// found empty, must still match nothing. A concurrent writer working from the same
+    /// read thus makes one of the writes fail, even when they change different relationships.
+    /// A created relationship only gets its own precondition when no `vacant` filter covers it.
+    pub fn between(
+        existing: &[Relationship],
+        desired: &[Relationship],
+        vacant: impl IntoIterator<Item = RelationshipFilter>,
+    ) -> Self {
+        let vacant: Vec<_> = vacant.into_iter().collect();
+        let mut diff = Self::default();
+        for relationship in existing {
+            if !desired.contains(relationship) {
+                diff.updates.push(relationship.delete());
+            }
+            diff.preconditions.push(Precondition {
+                operation: precondition::Operation::MustMatch.into(),
+                filter: Some(exact_filter(relationship)),
+            });
+        }
+        for relationship in desired.iter().filter(|r| !existing.contains(r)) {
+            diff.updates.push(relationship.touch());
+            if vacant
+                .iter()
+                .any(|filter| filter_matches(filter, relationship))
+            {
+                continue;
+            }
+            diff.preconditions.push(Precondition {
+                operation: precondition::Operation::MustNotMatch.into(),
+                filter: Some(exact_filter(relationship)),
+            });
+        }
+        for filter in vacant {
+            diff.preconditions.push(Precondition {
+                operation: precondition::Operation::MustNotMatch.into(),
+                filter: Some(filter),
+            });
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+}
+
+/// Filter matching exactly one relationship, caveat and expiration aside
+pub fn exact_filter(relationship: &Relationship) -> RelationshipFilter {
+    let resource = relationship.resource.clone().unwrap_or_default();
+    let subject = relationship.subject.clone().unwrap_or_default();
+    let subject_object = subject.object.unwrap_or_default();
+    RelationshipFilter {
+        resource_type: resource.object_type,
+        optional_resource_id: resource.object_id,
+        optional_relation: relationship.relation.clone(),
+        optional_subject_filter: Some(SubjectFilter {
+            subject_type: subject_object.object_type,
+            optional_subject_id: subject_object.object_id,
+            optional_relation: Some(subject_filter::RelationFilter {
+                relation: subject.optional_relation,
+            }),
+        }),
+        optional_resource_id_prefix: String::new(),
+    }
+}
+
+/// Whether `relationship` is one of those `filter` matches, empty fields matching anything
+fn filter_matches(filter: &RelationshipFilter, relationship: &Relationship) -> bool {
+    let matches = |expected: &str, actual: &str| expected.is_empty() || expected == actual;
+    let resource = relationship.resource.clone().unwrap_or_default();
+    let subject = relationship.subject.clone().unwrap_or_default();
+    let subject_object = subject.object.unwrap_or_default();
+    matches(&filter.resource_type, &resource.object_type)
+        && matches(&filter.optional_resource_id, &resource.object_id)
+        && resource
+            .object_id
+            .starts_with(&filter.optional_resource_id_prefix)
+        && matches(&filter.optional_relation, &relationship.relation)
+        && filter
+            .optional_subject_filter
+            .as_ref()
+            .is_none_or(|subject_filter| {
+                matches(&subject_filter.subject_type, &subject_object.object_type)
+                    && matches(
+                        &subject_filter.optional_subject_id,
+                        &subject_object.object_id,
+                    )
+                    && subject_filter
+                        .optional_relation
+                        .as_ref()
+                        .is_none_or(|relation| relation.relation == subject.optional_relation)
+            })
+}
+
+impl From<ZedToken> for domain::ZedToken {
+    fn from(token: ZedToken) -> Self {
+        Self(token.token)
+    }
+}
+
+impl From<domain::ZedToken> for ZedToken {
+    fn from(token: domain::ZedToken) -> Self {
+        Self { token: token.0 }
+    }
+}
+
+impl From<domain::Consistency> for Consistency {
+    fn from(consistency: domain::Consistency) -> Self {
+        let requirement = match consistency {
+            domain::Consistency::MinimizeLatency => consistency::Requirement::MinimizeLatency(true),
+            domain::Consistency::AtLeastAsFresh(token) => {
+                consistency::Requirement::AtLeastAsFresh(token.into())
+            }
+            domain::Consistency::FullyConsistent => consistency::Requirement::FullyConsistent(true),
+        };
+        Self {
+            requirement: Some(requirement),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authzed::api::v1::{ObjectReference, SubjectReference, relationship_update};
+
+    fn member_relationship(relation: &str) -> Relationship {
+        Relationship {
+            resource: Some(ObjectReference {
+                object_type: "server".to_string(),
+                object_id: "server_1".to_string(),
+            }),
+            relation: relation.to_string(),
+            subject: Some(SubjectReference {
+                object: Some(ObjectReference {
+                    object_type: "role".to_string(),
+                    object_id: "role_1".to_string(),
+                }),
+                optional_relation: "member".to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_diff_deletes_removed_and_touches_added_relationships() {
+        let existing = [member_relationship("admin"), member_relationship("sender")];
+        let desired = [
+            member_relationship("sender"),
+            member_relationship("inviter"),
+        ];
+
+        let vacant = RelationshipFilter {
+            optional_relation: "inviter".to_string(),
+            ..Default::default()
+        };
+
+        let diff = RelationshipDiff::between(&existing, &desired, [vacant]);
+
+        let updates: Vec<_> = diff
+            .updates
+            .iter()
+            .map(|u| (u.operation(), u.relationship.clone().unwrap().relation))
+            .collect();
+        assert_eq!(
+            updates,
+            vec![
+                (relationship_update::Operation::Delete, "admin".to_string()),
+                (relationship_update::Operation::Touch, "inviter".to_string()),
+            ]
+        );
+        let preconditions: Vec<_> = diff
+            .preconditions
+            .iter()
+            .map(|p| (p.operation(), p.filter.clone().unwrap().optional_relation))
+            .collect();
+        assert_eq!(
+            preconditions,
+            vec![
+                (precondition::Operation::MustMatch, "admin".to_string()),
+                (precondition::Operation::MustMatch, "sender".to_string()),
+                (precondition::Operation::MustNotMatch, "inviter".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_guards_uncovered_created_relationships_exactly() {
+        let desired = [member_relationship("inviter")];
+        let vacant = RelationshipFilter {
+            optional_relation: "sender".to_string(),
+            ..Default::default()
+        };
+
+        let diff = RelationshipDiff::between(&[], &desired, [vacant.clone()]);
+
+        assert_eq!(
+            diff.preconditions,
+            vec![
+                Precondition {
+                    operation: precondition::Operation::MustNotMatch.into(),
+                    filter: Some(exact_filter(&desired[0])),
+                },
+                Precondition {
+                    operation: precondition::Operation::MustNotMatch.into(),
+                    filter: Some(vacant),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_matches_only_set_fields() {
+        let relationship = member_relationship("admin");
+        let other_subject = RelationshipFilter {
+            resource_type: "server".to_string(),
+            optional_subject_filter: Some(SubjectFilter {
+                subject_type: "role".to_string(),
+                optional_subject_id: "role_2".to_string(),
+                optional_relation: None,
+            }),
+            ..Default::default()
+        };
+
+        assert!(filter_matches(&exact_filter(&relationship), &relationship));
+        assert!(filter_matches(
+            &RelationshipFilter::default(),
+            &relationship
+        ));
+        assert!(!filter_matches(
+            &exact_filter(&member_relationship("sender")),
+            &relationship
+        ));
+        assert!(!filter_matches(&other_subject, &relationship));
+    }
+
+    #[test]
+    fn test_diff_of_identical_sets_is_empty_but_guarded() {
+        let relationships = [member_relationship("admin")];
+
+        let diff = RelationshipDiff::between(&relationships, &relationships, []);
+
+        assert!(diff.is_empty());
+        let preconditions: Vec<_> = diff.preconditions.iter().map(|p| p.operation()).collect();
+        assert_eq!(preconditions, vec![precondition::Operation::MustMatch]);
+    }
+
+    #[test]
+    fn test_exact_filter_pins_every_field() {
+        let filter = exact_filter(&member_relationship("admin"));
+
+        assert_eq!(filter.resource_type, "server");
+        assert_eq!(filter.optional_resource_id, "server_1");
+        assert_eq!(filter.optional_relation, "admin");
+        let subject_filter = filter.optional_subject_filter.unwrap();
+        assert_eq!(subject_filter.subject_type, "role");
+        assert_eq!(subject_filter.optional_subject_id, "role_1");
+        assert_eq!(subject_filter.optional_relation.unwrap().relation, "member");
+    }
+
+    #[test]
+    fn test_consistency_always_sets_a_requirement() {
+        let fresh: Consistency =
+            domain::Consistency::AtLeastAsFresh(domain::ZedToken("GhUKEzE3".to_string())).into();
+        let default: Consistency = domain::Consistency::default().into();
+
+        assert_eq!(
+            fresh.requirement,
+            Some(consistency::Requirement::AtLeastAsFresh(ZedToken {
+                token: "GhUKEzE3".to_string()
+            }))
+        );
+        assert_eq!(
+            default.requirement,
+            Some(consistency::Requirement::MinimizeLatency(true))
+        );
+    }
+}
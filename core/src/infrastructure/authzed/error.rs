@@ -1,16 +1,198 @@
 use thiserror::Error;
+use tonic::{Code, Status, TimeoutExpired};
 
 #[derive(Error, Debug)]
 pub enum AuthzedError {
     #[error("Could not connect to spicedb: {msg}")]
     ConnectionError { msg: String },
 
+    #[error("Invalid TLS configuration: {msg}")]
+    TlsConfigError { msg: String },
+
+    /// The call deadline expired, on the client or in SpiceDB
+    #[error("SpiceDB call timed out: {msg}")]
+    TimeoutError { msg: String },
+
     #[error("Could not write relationship: {msg}")]
-    WriteRelationshipError { msg: String },
+    WriteRelationshipError { code: Code, msg: String },
 
     #[error("Could not write relationships: {msg}")]
-    WriteRelationshipsError { msg: String },
+    WriteRelationshipsError { code: Code, msg: String },
 
     #[error("Could not delete relationship: {msg}")]
-    DeleteRelationshipError { msg: String },
+    DeleteRelationshipError { code: Code, msg: String },
+
+    #[error("Could not read relationships: {msg}")]
+    ReadRelationshipsError { code: Code, msg: String },
+
+    #[error("Could not import relationships: {msg}")]
+    ImportRelationshipsError { code: Code, msg: String },
+
+    #[error("Could not export relationships: {msg}")]
+    ExportRelationshipsError { code: Code, msg: String },
+
+    #[error("Could not check permission: {msg}")]
+    CheckPermissionError { code: Code, msg: String },
+
+    #[error("Could not lookup resources: {msg}")]
+    LookupResourcesError { code: Code, msg: String },
+
+    #[error("Could not lookup subjects: {msg}")]
+    LookupSubjectsError { code: Code, msg: String },
+
+    #[error("Could not watch relationship changes: {msg}")]
+    WatchError { code: Code, msg: String },
+
+    #[error("Could not read schema: {msg}")]
+    ReadSchemaError { code: Code, msg: String },
+
+    #[error("Could not write schema: {msg}")]
+    WriteSchemaError { code: Code, msg: String },
+}
+
+impl AuthzedError {
+    /// `TimeoutError` when the call deadline expired, the error built by `other` otherwise
+    pub fn from_status(status: Status, other: impl FnOnce(Code, String) -> Self) -> Self {
+        // The channel reports its own expired deadline as a cancellation
+        let client_timeout =
+            status.code() == Code::Cancelled && status.message() == TimeoutExpired(()).to_string();
+        if status.code() == Code::DeadlineExceeded || client_timeout {
+            AuthzedError::TimeoutError {
+                msg: status.to_string(),
+            }
+        } else {
+            other(status.code(), status.to_string())
+        }
+    }
+
+    /// gRPC status code of the failed call, `None` for errors raised before any call
+    pub fn code(&self) -> Option<Code> {
+        match self {
+            AuthzedError::ConnectionError { .. } | AuthzedError::TlsConfigError { .. } => None,
+            AuthzedError::TimeoutError { .. } => Some(Code::DeadlineExceeded),
+            AuthzedError::WriteRelationshipError { code, .. }
+            | AuthzedError::WriteRelationshipsError { code, .. }
+            | AuthzedError::DeleteRelationshipError { code, .. }
+            | AuthzedError::ReadRelationshipsError { code, .. }
+            | AuthzedError::ImportRelationshipsError { code, .. }
+            | AuthzedError::ExportRelationshipsError { code, .. }
+            | AuthzedError::CheckPermissionError { code, .. }
+            | AuthzedError::LookupResourcesError { code, .. }
+            | AuthzedError::LookupSubjectsError { code, .. }
+            | AuthzedError::WatchError { code, .. }
+            | AuthzedError::ReadSchemaError { code, .. }
+            | AuthzedError::WriteSchemaError { code, .. } => Some(*code),
+        }
+    }
+
+    /// Whether sending the same request again may succeed
+    pub fn is_retryable(&self) -> bool {
+        self.code().is_some_and(is_retryable)
+    }
+
+    /// Whether SpiceDB could not answer for now: unreachable, restarting, overloaded or too slow
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, AuthzedError::ConnectionError { .. }) || self.is_retryable()
+    }
+}
+
+/// Transient failures: SpiceDB is restarting, overloaded or answered too late.
+/// Anything else, e.g. InvalidArgument or FailedPrecondition, fails again the same way.
+pub fn is_retryable(code: Code) -> bool {
+    matches!(
+        code,
+        Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_codes_are_retryable() {
+        for code in [
+            Code::Unavailable,
+            Code::DeadlineExceeded,
+            Code::ResourceExhausted,
+        ] {
+            let error = AuthzedError::WriteRelationshipError {
+                code,
+                msg: "transient".to_string(),
+            };
+            assert!(error.is_retryable(), "{:?}", code);
+        }
+    }
+
+    #[test]
+    fn test_request_errors_are_permanent() {
+        for code in [
+            Code::InvalidArgument,
+            Code::FailedPrecondition,
+            Code::AlreadyExists,
+            Code::PermissionDenied,
+            Code::Internal,
+        ] {
+            let error = AuthzedError::WriteRelationshipError {
+                code,
+                msg: "permanent".to_string(),
+            };
+            assert!(!error.is_retryable(), "{:?}", code);
+        }
+    }
+
+    #[test]
+    fn test_unreachable_spicedb_is_unavailable() {
+        let connection = AuthzedError::ConnectionError {
+            msg: "connection refused".to_string(),
+        };
+        let invalid = AuthzedError::CheckPermissionError {
+            code: Code::InvalidArgument,
+            msg: "unknown permission".to_string(),
+        };
+
+        assert!(connection.is_unavailable());
+        assert!(!invalid.is_unavailable());
+    }
+
+    #[test]
+    fn test_expired_deadlines_are_timeouts() {
+        for status in [
+            Status::deadline_exceeded("deadline exceeded"),
+            Status::cancelled(TimeoutExpired(()).to_string()),
+        ] {
+            let error = AuthzedError::from_status(status, |code, msg| {
+                AuthzedError::CheckPermissionError { code, msg }
+            });
+
+            assert!(matches!(error, AuthzedError::TimeoutError { .. }));
+            assert!(error.is_retryable());
+        }
+    }
+
+    #[test]
+    fn test_other_statuses_keep_their_variant() {
+        let error =
+            AuthzedError::from_status(Status::cancelled("client went away"), |code, msg| {
+                AuthzedError::CheckPermissionError { code, msg }
+            });
+
+        assert!(matches!(
+            error,
+            AuthzedError::CheckPermissionError {
+                code: Code::Cancelled,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_configuration_errors_are_permanent() {
+        let error = AuthzedError::TlsConfigError {
+            msg: "missing CA".to_string(),
+        };
+
+        assert_eq!(error.code(), None);
+        assert!(!error.is_retryable());
+    }
 }
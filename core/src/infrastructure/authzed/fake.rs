@@ -0,0 +1,397 @@
+//! In-process SpiceDB stand-in for exercising `AuthZedClient` over a real gRPC connection
+
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+
+use futures::{
+    StreamExt, TryStreamExt,
+    stream::{self, BoxStream},
+};
+use tokio::{net::TcpListener, sync::Barrier};
+use tonic::{
+    Code, Request, Response, Status, Streaming,
+    transport::{Server, server::TcpIncoming},
+};
+
+use crate::{
+    authzed::api::v1::{
+        CheckBulkPermissionsPair, CheckBulkPermissionsRequest, CheckBulkPermissionsResponse,
+        CheckBulkPermissionsResponseItem, CheckPermissionRequest, CheckPermissionResponse, Cursor,
+        DeleteRelationshipsRequest, DeleteRelationshipsResponse, ExportBulkRelationshipsRequest,
+        ExportBulkRelationshipsResponse, ImportBulkRelationshipsRequest,
+        ImportBulkRelationshipsResponse, ReadRelationshipsRequest, ReadRelationshipsResponse,
+        Relationship, RelationshipFilter, SubjectFilter, WriteRelationshipsRequest,
+        WriteRelationshipsResponse, ZedToken, check_bulk_permissions_pair,
+        check_permission_response::Permissionship,
+        delete_relationships_response::DeletionProgress,
+        permissions_service_server::{PermissionsService, PermissionsServiceServer},
+        precondition, relationship_update,
+    },
+    infrastructure::authzed::{
+        AuthZedClient, AuthZedConfig, DEFAULT_BULK_CHECK_CHUNK_SIZE,
+        connection::AuthZedConnectionConfig, retry::RetryConfig, tls::AuthZedTlsConfig,
+    },
+};
+
+/// Relationships per batch of an export without limit
+const EXPORT_BATCH_SIZE: usize = 100;
+
+/// Stores relationships in memory and fails calls on demand
+#[derive(Default)]
+pub struct FakeSpiceDb {
+    /// Outcomes of the next calls, in order: `None` answers normally
+    failures: Mutex<VecDeque<Option<Code>>>,
+    calls: AtomicU32,
+    relationships: Mutex<Vec<Relationship>>,
+    revision: AtomicU32,
+    /// Time taken by every answer
+    latency: Mutex<Duration>,
+    /// Writes left to hold back, and the barrier releasing them once all arrived
+    held_writes: Mutex<Option<(usize, Arc<Barrier>)>>,
+    /// Item count of every bulk check received
+    bulk_check_sizes: Mutex<Vec<usize>>,
+}
+
+impl FakeSpiceDb {
+    /// Fail the next `times` calls with `code`, then answer normally again
+    pub fn fail_next(&self, times: u32, code: Code) {
+        self.fail_after(0, times, code);
+    }
+
+    /// Answer the next `skipped` calls, then fail `times` calls with `code`
+    pub fn fail_after(&self, skipped: u32, times: u32, code: Code) {
+        let mut failures = self.failures.lock().unwrap();
+        failures.clear();
+        failures.extend(std::iter::repeat_n(None, skipped as usize));
+        failures.extend(std::iter::repeat_n(Some(code), times as usize));
+    }
+
+    /// Hold the next `writes` writes until all of them arrived, so that callers racing
+    /// each other have all read the state before any of their writes applies
+    pub fn hold_writes(&self, writes: usize) {
+        *self.held_writes.lock().unwrap() = Some((writes, Arc::new(Barrier::new(writes))));
+    }
+
+    pub fn set_latency(&self, latency: Duration) {
+        *self.latency.lock().unwrap() = latency;
+    }
+
+    /// Number of calls received, failed ones included
+    pub fn calls(&self) -> u32 {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    pub fn bulk_check_sizes(&self) -> Vec<usize> {
+        self.bulk_check_sizes.lock().unwrap().clone()
+    }
+
+    pub fn relationships(&self) -> Vec<Relationship> {
+        self.relationships.lock().unwrap().clone()
+    }
+
+    /// Serve on a random local port and connect a client to it
+    pub async fn serve(self: &Arc<Self>, retry: RetryConfig) -> AuthZedClient {
+        self.serve_with(|config| config.retry = retry).await
+    }
+
+    /// Serve on a random local port and connect a client configured by `configure`,
+    /// starting from defaults without retries
+    pub async fn serve_with(
+        self: &Arc<Self>,
+        configure: impl FnOnce(&mut AuthZedConfig),
+    ) -> AuthZedClient {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(PermissionsServiceServer::from_arc(self.clone()))
+                .serve_with_incoming(incoming),
+        );
+
+        let mut config = AuthZedConfig {
+            endpoint: addr.to_string(),
+            token: None,
+            tls: AuthZedTlsConfig::default(),
+            channel_pool_size: NonZeroUsize::MIN,
+            bulk_check_chunk_size: DEFAULT_BULK_CHECK_CHUNK_SIZE,
+            retry: RetryConfig::disabled(),
+            connection: AuthZedConnectionConfig::default(),
+        };
+        configure(&mut config);
+        AuthZedClient::new(config).await.unwrap()
+    }
+
+    /// Count the call, wait for the latency, and the status to fail it with while failures are left
+    async fn injected_failure(&self) -> Option<Status> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let latency = *self.latency.lock().unwrap();
+        tokio::time::sleep(latency).await;
+        let code = self.failures.lock().unwrap().pop_front().flatten()?;
+        Some(Status::new(code, "injected failure"))
+    }
+
+    fn next_token(&self) -> ZedToken {
+        ZedToken {
+            token: (self.revision.fetch_add(1, Ordering::SeqCst) + 1).to_string(),
+        }
+    }
+}
+
+/// Retries without noticeable delays
+pub fn fast_retries(max_retries: u32) -> RetryConfig {
+    RetryConfig {
+        max_retries,
+        initial_backoff_ms: 1,
+        max_backoff_ms: 1,
+    }
+}
+
+#[tonic::async_trait]
+impl PermissionsService for FakeSpiceDb {
+    async fn check_permission(
+        &self,
+        _request: Request<CheckPermissionRequest>,
+    ) -> Result<Response<CheckPermissionResponse>, Status> {
+        if let Some(status) = self.injected_failure().await {
+            return Err(status);
+        }
+        Ok(Response::new(CheckPermissionResponse {
+            permissionship: Permissionship::HasPermission as i32,
+            ..Default::default()
+        }))
+    }
+
+    async fn check_bulk_permissions(
+        &self,
+        request: Request<CheckBulkPermissionsRequest>,
+    ) -> Result<Response<CheckBulkPermissionsResponse>, Status> {
+        if let Some(status) = self.injected_failure().await {
+            return Err(status);
+        }
+        let request = request.into_inner();
+        self.bulk_check_sizes
+            .lock()
+            .unwrap()
+            .push(request.items.len());
+        let pairs = request
+            .items
+            .into_iter()
+            .map(|item| CheckBulkPermissionsPair {
+                request: Some(item),
+                response: Some(check_bulk_permissions_pair::Response::Item(
+                    CheckBulkPermissionsResponseItem {
+                        permissionship: Permissionship::HasPermission as i32,
+                        ..Default::default()
+                    },
+                )),
+            })
+            .collect();
+        Ok(Response::new(CheckBulkPermissionsResponse {
+            pairs,
+            ..Default::default()
+        }))
+    }
+
+    async fn write_relationships(
+        &self,
+        request: Request<WriteRelationshipsRequest>,
+    ) -> Result<Response<WriteRelationshipsResponse>, Status> {
+        if let Some(status) = self.injected_failure().await {
+            return Err(status);
+        }
+        let barrier = {
+            let mut held_writes = self.held_writes.lock().unwrap();
+            let barrier = held_writes.as_ref().map(|(_, barrier)| barrier.clone());
+            if let Some((left, _)) = held_writes.as_mut() {
+                *left -= 1;
+                if *left == 0 {
+                    *held_writes = None;
+                }
+            }
+            barrier
+        };
+        if let Some(barrier) = barrier {
+            barrier.wait().await;
+        }
+        let request = request.into_inner();
+        let mut relationships = self.relationships.lock().unwrap();
+        for precondition in &request.optional_preconditions {
+            let filter = precondition.filter.clone().unwrap_or_default();
+            let matched = relationships.iter().any(|r| matches(&filter, r));
+            if matched != (precondition.operation() == precondition::Operation::MustMatch) {
+                return Err(Status::failed_precondition("unsatisfied precondition"));
+            }
+        }
+        for update in request.updates {
+            let operation = update.operation();
+            let relationship = update.relationship.unwrap_or_default();
+            relationships.retain(|existing| existing != &relationship);
+            if operation != relationship_update::Operation::Delete {
+                relationships.push(relationship);
+            }
+        }
+        Ok(Response::new(WriteRelationshipsResponse {
+            written_at: Some(self.next_token()),
+        }))
+    }
+
+    async fn delete_relationships(
+        &self,
+        request: Request<DeleteRelationshipsRequest>,
+    ) -> Result<Response<DeleteRelationshipsResponse>, Status> {
+        if let Some(status) = self.injected_failure().await {
+            return Err(status);
+        }
+        let request = request.into_inner();
+        let filter = request.relationship_filter.unwrap_or_default();
+        let mut relationships = self.relationships.lock().unwrap();
+        let matching = relationships.iter().filter(|r| matches(&filter, r)).count();
+        let limit = match request.optional_limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        if matching > limit && !request.optional_allow_partial_deletions {
+            return Err(Status::failed_precondition("deletion limit exceeded"));
+        }
+
+        let mut deleted = 0;
+        relationships.retain(|relationship| {
+            let delete = deleted < limit && matches(&filter, relationship);
+            deleted += usize::from(delete);
+            !delete
+        });
+        let deletion_progress = if matching > limit {
+            DeletionProgress::Partial
+        } else {
+            DeletionProgress::Complete
+        };
+        Ok(Response::new(DeleteRelationshipsResponse {
+            deleted_at: Some(self.next_token()),
+            deletion_progress: deletion_progress.into(),
+            relationships_deleted_count: deleted as u64,
+        }))
+    }
+
+    async fn import_bulk_relationships(
+        &self,
+        request: Request<Streaming<ImportBulkRelationshipsRequest>>,
+    ) -> Result<Response<ImportBulkRelationshipsResponse>, Status> {
+        if let Some(status) = self.injected_failure().await {
+            return Err(status);
+        }
+        let imported: Vec<_> = request
+            .into_inner()
+            .map_ok(|request| request.relationships)
+            .try_concat()
+            .await?;
+        // One transaction: nothing is loaded if any relationship exists
+        let mut relationships = self.relationships.lock().unwrap();
+        if imported.iter().any(|r| relationships.contains(r)) {
+            return Err(Status::already_exists("relationship already exists"));
+        }
+        relationships.extend(imported.iter().cloned());
+        Ok(Response::new(ImportBulkRelationshipsResponse {
+            num_loaded: imported.len() as u64,
+        }))
+    }
+
+    async fn export_bulk_relationships(
+        &self,
+        request: Request<ExportBulkRelationshipsRequest>,
+    ) -> Result<Response<BoxStream<'static, Result<ExportBulkRelationshipsResponse, Status>>>, Status>
+    {
+        if let Some(status) = self.injected_failure().await {
+            return Err(status);
+        }
+        let batch_size = match request.into_inner().optional_limit {
+            0 => EXPORT_BATCH_SIZE,
+            limit => limit as usize,
+        };
+        let responses: Vec<_> = self
+            .relationships()
+            .chunks(batch_size)
+            .map(|batch| ExportBulkRelationshipsResponse {
+                after_result_cursor: None,
+                relationships: batch.to_vec(),
+            })
+            .collect();
+        Ok(Response::new(stream::iter(responses).map(Ok).boxed()))
+    }
+
+    async fn read_relationships(
+        &self,
+        request: Request<ReadRelationshipsRequest>,
+    ) -> Result<Response<BoxStream<'static, Result<ReadRelationshipsResponse, Status>>>, Status>
+    {
+        if let Some(status) = self.injected_failure().await {
+            return Err(status);
+        }
+        let request = request.into_inner();
+        let filter = request.relationship_filter.unwrap_or_default();
+        // Cursors are positions among the matching relationships
+        let start = request
+            .optional_cursor
+            .map_or(0, |cursor| cursor.token.parse().unwrap());
+        let limit = match request.optional_limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        let read_at = ZedToken {
+            token: self.revision.load(Ordering::SeqCst).to_string(),
+        };
+        let responses: Vec<_> = self
+            .relationships()
+            .into_iter()
+            .filter(|relationship| matches(&filter, relationship))
+            .enumerate()
+            .skip(start)
+            .take(limit)
+            .map(|(position, relationship)| ReadRelationshipsResponse {
+                read_at: Some(read_at.clone()),
+                relationship: Some(relationship),
+                after_result_cursor: Some(Cursor {
+                    token: (position + 1).to_string(),
+                }),
+            })
+            .collect();
+        Ok(Response::new(stream::iter(responses).map(Ok).boxed()))
+    }
+}
+
+fn matches(filter: &RelationshipFilter, relationship: &Relationship) -> bool {
+    let Some(resource) = &relationship.resource else {
+        return false;
+    };
+    (filter.resource_type.is_empty() || resource.object_type == filter.resource_type)
+        && (filter.optional_resource_id.is_empty()
+            || resource.object_id == filter.optional_resource_id)
+        && (filter.optional_relation.is_empty()
+            || relationship.relation == filter.optional_relation)
+        && filter
+            .optional_subject_filter
+            .as_ref()
+            .is_none_or(|subject_filter| subject_matches(subject_filter, relationship))
+}
+
+fn subject_matches(filter: &SubjectFilter, relationship: &Relationship) -> bool {
+    let Some(subject) = &relationship.subject else {
+        return false;
+    };
+    let Some(object) = &subject.object else {
+        return false;
+    };
+    object.object_type == filter.subject_type
+        && (filter.optional_subject_id.is_empty() || object.object_id == filter.optional_subject_id)
+        && filter
+            .optional_relation
+            .as_ref()
+            .is_none_or(|relation| subject.optional_relation == relation.relation)
+}
@@ -1,23 +1,66 @@
-use std::sync::Arc;
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
 
 use clap::Parser;
-use tokio::sync::RwLock;
-use tonic::transport::Channel;
+use tonic::{Code, service::interceptor::InterceptedService, transport::Channel};
 
 use crate::{
-    PermissionsServiceClient,
+    PermissionsServiceClient, SchemaServiceClient, WatchServiceClient,
     authzed::api::v1::{
-        DeleteRelationshipsRequest, ReadRelationshipsRequest, Relationship, RelationshipFilter,
-        RelationshipUpdate, WriteRelationshipsRequest,
+        CheckBulkPermissionsPair, CheckBulkPermissionsRequest, CheckBulkPermissionsRequestItem,
+        CheckPermissionRequest, CheckPermissionResponse, Consistency, Cursor,
+        DeleteRelationshipsRequest, ExportBulkRelationshipsRequest, ImportBulkRelationshipsRequest,
+        LookupPermissionship, LookupResourcesRequest, LookupResourcesResponse,
+        LookupSubjectsRequest, LookupSubjectsResponse, ObjectReference, Precondition,
+        ReadRelationshipsRequest, ReadRelationshipsResponse, ReadSchemaRequest, Relationship,
+        RelationshipFilter, RelationshipUpdate, SubjectReference, WatchRequest, WatchResponse,
+        WriteRelationshipsRequest, WriteSchemaRequest, ZedToken,
+        delete_relationships_response::DeletionProgress, lookup_subjects_request,
+        relationship_update,
+    },
+    infrastructure::authzed::{
+        connection::AuthZedConnectionConfig,
+        entities::{Action, DeleteSummary, ImportPath, ImportSummary, Page},
+        error::AuthzedError,
+        retry::{RetryConfig, with_retries},
+        tls::{AuthZedTlsConfig, endpoint_url},
     },
-    infrastructure::authzed::{entities::Action, error::AuthzedError},
 };
-use futures::StreamExt;
+use futures::{
+    StreamExt, TryStreamExt, future,
+    stream::{self, BoxStream},
+};
 use tonic::service::Interceptor;
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
+pub mod backup;
+pub mod connection;
 pub mod entities;
 pub mod error;
+#[cfg(test)]
+pub(crate) mod fake;
+pub mod retry;
+pub mod schema;
+pub mod tls;
+
+/// Page size of jobs walking relationships with `for_each_relationship_page`
+pub const READ_PAGE_SIZE: u32 = 1_000;
+
+/// Relationships deleted per call by `filtered_delete`, SpiceDB's default deletion limit
+pub const DELETE_BATCH_SIZE: u32 = 1_000;
+
+/// Relationships per message of a bulk import and per write of its fallback,
+/// SpiceDB's default limit of updates per write
+pub const IMPORT_BATCH_SIZE: usize = 1_000;
+
+/// Default items per bulk check request, within SpiceDB's per-request item limit
+pub const DEFAULT_BULK_CHECK_CHUNK_SIZE: NonZeroUsize = NonZeroUsize::new(1_000).unwrap();
 
 /// AuthZed client configuration
 #[derive(Debug, Clone, Parser)]
@@ -33,31 +76,58 @@ pub struct AuthZedConfig {
     /// The preshared key for authentication
     #[arg(long = "authzed-token", env = "AUTHZED_TOKEN")]
     pub token: Option<String>,
+
+    #[command(flatten)]
+    pub tls: AuthZedTlsConfig,
+
+    /// Number of HTTP/2 connections requests are spread over. One connection
+    /// already multiplexes concurrent requests; more help once a single
+    /// connection's stream limit or bandwidth becomes the bottleneck.
+    #[arg(
+        long = "authzed-channel-pool-size",
+        env = "AUTHZED_CHANNEL_POOL_SIZE",
+        default_value = "1"
+    )]
+    pub channel_pool_size: NonZeroUsize,
+
+    /// Most items sent in one bulk check; larger batches are split into requests
+    /// sent concurrently. Must not exceed SpiceDB's per-request item limit.
+    #[arg(
+        long = "authzed-bulk-check-chunk-size",
+        env = "AUTHZED_BULK_CHECK_CHUNK_SIZE",
+        default_value_t = DEFAULT_BULK_CHECK_CHUNK_SIZE
+    )]
+    pub bulk_check_chunk_size: NonZeroUsize,
+
+    #[command(flatten)]
+    pub retry: RetryConfig,
+
+    #[command(flatten)]
+    pub connection: AuthZedConnectionConfig,
 }
 
-/// Main AuthZed client with all service clients
+type AuthedChannel = InterceptedService<Channel, AuthInterceptor>;
+
+/// Main AuthZed client with all service clients.
+/// Cloning is cheap and clones share the same connections; every call works on
+/// its own copy of a service client, so concurrent calls never wait on each other.
 #[derive(Clone)]
 pub struct AuthZedClient {
-    permissions: Arc<
-        RwLock<
-            PermissionsServiceClient<
-                tonic::service::interceptor::InterceptedService<Channel, AuthInterceptor>,
-            >,
-        >,
-    >,
-    // schema: SchemaServiceClient<Channel>,
+    permissions: Arc<[PermissionsServiceClient<AuthedChannel>]>,
+    next_permissions: Arc<AtomicUsize>,
+    /// Permissions client without deadline, for bulk transfers
+    bulk: PermissionsServiceClient<AuthedChannel>,
+    watch: WatchServiceClient<AuthedChannel>,
+    schema: SchemaServiceClient<AuthedChannel>,
+    retry: RetryConfig,
+    bulk_check_chunk_size: usize,
 }
 
 impl AuthZedClient {
-    async fn permissions(
-        &self,
-    ) -> tokio::sync::RwLockWriteGuard<
-        '_,
-        PermissionsServiceClient<
-            tonic::service::interceptor::InterceptedService<Channel, AuthInterceptor>,
-        >,
-    > {
-        self.permissions.write().await
+    /// A permissions client on the next connection of the pool, round-robin
+    fn permissions(&self) -> PermissionsServiceClient<AuthedChannel> {
+        let index = self.next_permissions.fetch_add(1, Ordering::Relaxed) % self.permissions.len();
+        self.permissions[index].clone()
     }
 
     /// Create a new AuthZed client with the given configuration
@@ -65,43 +135,80 @@ impl AuthZedClient {
     pub async fn new(config: AuthZedConfig) -> Result<Self, AuthzedError> {
         info!(endpoint = %config.endpoint, "Creating AuthZed client");
 
-        let channel = Self::create_channel(&config).await?;
+        let pool_size = config.channel_pool_size.get();
+        let mut channels = Vec::with_capacity(pool_size);
+        for _ in 0..pool_size {
+            channels.push(Self::create_channel(&config).await?);
+        }
 
         // Always use an interceptor, even if token is empty
         let token = config.token.unwrap_or_default();
         let has_token = !token.is_empty();
-        debug!(has_token, "Configuring authentication interceptor");
+        debug!(
+            has_token,
+            pool_size, "Configuring authentication interceptor"
+        );
 
-        let interceptor = AuthInterceptor { token };
-        let permissions = Arc::new(RwLock::new(PermissionsServiceClient::with_interceptor(
-            channel.clone(),
-            interceptor,
-        )));
+        let interceptor = AuthInterceptor {
+            token,
+            deadline: config.connection.request_timeout(),
+        };
+        let permissions = channels
+            .iter()
+            .map(|channel| {
+                PermissionsServiceClient::with_interceptor(channel.clone(), interceptor.clone())
+            })
+            .collect();
+        // Long-lived streams and rare schema calls stay on the first connection;
+        // the Watch stream and bulk transfers are meant to outlive any deadline
+        let without_deadline = AuthInterceptor {
+            deadline: None,
+            ..interceptor.clone()
+        };
+        let bulk = PermissionsServiceClient::with_interceptor(
+            channels[0].clone(),
+            without_deadline.clone(),
+        );
+        let watch = WatchServiceClient::with_interceptor(channels[0].clone(), without_deadline);
+        let schema = SchemaServiceClient::with_interceptor(channels[0].clone(), interceptor);
 
-        info!("AuthZed client created successfully");
+        info!(pool_size, "AuthZed client created successfully");
         Ok(Self {
             permissions,
-            // schema: SchemaServiceClient::new(channel.clone()),
+            next_permissions: Arc::new(AtomicUsize::new(0)),
+            bulk,
+            watch,
+            schema,
+            retry: config.retry,
+            bulk_check_chunk_size: config.bulk_check_chunk_size.get(),
         })
     }
 
     #[instrument(skip_all, fields(endpoint = %config.endpoint))]
     async fn create_channel(config: &AuthZedConfig) -> Result<Channel, AuthzedError> {
-        // Add http:// scheme if not present
-        let endpoint_url =
-            if config.endpoint.starts_with("http://") || config.endpoint.starts_with("https://") {
-                config.endpoint.clone()
-            } else {
-                format!("http://{}", config.endpoint)
-            };
+        // Add a scheme matching the TLS mode if not present
+        let endpoint_url = endpoint_url(&config.endpoint, config.tls.is_enabled())?;
 
-        debug!(endpoint_url = %endpoint_url, "Creating gRPC channel");
+        debug!(endpoint_url = %endpoint_url, tls_mode = ?config.tls.mode, "Creating gRPC channel");
 
-        let endpoint = Channel::from_shared(endpoint_url.clone()).map_err(|e| {
+        let mut endpoint = Channel::from_shared(endpoint_url.clone()).map_err(|e| {
             error!(endpoint_url = %endpoint_url, error = %e, "Invalid endpoint URL");
             AuthzedError::ConnectionError { msg: e.to_string() }
         })?;
 
+        if let Some(tls) = config.tls.client_tls_config().await? {
+            endpoint = endpoint.tls_config(tls).map_err(|e| {
+                error!(error = %e, "Invalid TLS configuration");
+                AuthzedError::TlsConfigError { msg: e.to_string() }
+            })?;
+        }
+
+        let endpoint = config.connection.apply(endpoint);
+        if config.connection.lazy_connect {
+            info!(endpoint_url = %endpoint_url, "AuthZed connection deferred to the first call");
+            return Ok(endpoint.connect_lazy());
+        }
+
         info!(endpoint_url = %endpoint_url, "Connecting to AuthZed");
         let channel = endpoint.connect().await.map_err(|e| {
             error!(endpoint_url = %endpoint_url, error = %e, "Failed to connect to AuthZed");
@@ -112,11 +219,24 @@ impl AuthZedClient {
         Ok(channel)
     }
 
+    /// Retries of a write: none when it creates a relationship, since a create whose
+    /// response was lost may have been applied and would fail with AlreadyExists if retried
+    fn write_retry(&self, updates: &[RelationshipUpdate]) -> RetryConfig {
+        if updates
+            .iter()
+            .any(|update| update.operation() == relationship_update::Operation::Create)
+        {
+            RetryConfig::disabled()
+        } else {
+            self.retry.clone()
+        }
+    }
+
     #[instrument(skip_all)]
     pub async fn create_relationship(
         &self,
         relationship: impl Into<Relationship>,
-    ) -> Result<(), AuthzedError> {
+    ) -> Result<ZedToken, AuthzedError> {
         let relationship: Relationship = relationship.into();
         debug!(
             resource_type = relationship.resource.as_ref().map(|r| r.object_type.as_str()),
@@ -124,16 +244,16 @@ impl AuthZedClient {
             relation = %relationship.relation,
             "Creating relationship"
         );
-        self.write_relationship(relationship.create()).await?;
-        info!("Relationship created successfully");
-        Ok(())
+        let written_at = self.write_relationship(relationship.create()).await?;
+        info!(written_at = %written_at.token, "Relationship created successfully");
+        Ok(written_at)
     }
 
     #[instrument(skip_all)]
     pub async fn delete_relationship(
         &self,
         relationship: impl Into<Relationship>,
-    ) -> Result<(), AuthzedError> {
+    ) -> Result<ZedToken, AuthzedError> {
         let relationship: Relationship = relationship.into();
         debug!(
             resource_type = relationship.resource.as_ref().map(|r| r.object_type.as_str()),
@@ -141,16 +261,16 @@ impl AuthZedClient {
             relation = %relationship.relation,
             "Deleting relationship"
         );
-        self.write_relationship(relationship.delete()).await?;
-        info!("Relationship deleted successfully");
-        Ok(())
+        let written_at = self.write_relationship(relationship.delete()).await?;
+        info!(written_at = %written_at.token, "Relationship deleted successfully");
+        Ok(written_at)
     }
 
     #[instrument(skip_all)]
     pub async fn touch_relationship(
         &self,
         relationship: impl Into<Relationship>,
-    ) -> Result<(), AuthzedError> {
+    ) -> Result<ZedToken, AuthzedError> {
         let relationship: Relationship = relationship.into();
         debug!(
             resource_type = relationship.resource.as_ref().map(|r| r.object_type.as_str()),
@@ -158,44 +278,87 @@ impl AuthZedClient {
             relation = %relationship.relation,
             "Touching relationship"
         );
-        self.write_relationship(relationship.touch()).await?;
-        info!("Relationship touched successfully");
-        Ok(())
+        let written_at = self.write_relationship(relationship.touch()).await?;
+        info!(written_at = %written_at.token, "Relationship touched successfully");
+        Ok(written_at)
     }
 
     #[instrument(skip_all, fields(update_count = updates.len()))]
     pub async fn write_relationships(
         &self,
         updates: Vec<RelationshipUpdate>,
-    ) -> Result<(), AuthzedError> {
+    ) -> Result<ZedToken, AuthzedError> {
+        self.write_relationships_with_preconditions(updates, Vec::new())
+            .await
+    }
+
+    /// Apply every update atomically, failing with `FailedPrecondition` and writing
+    /// nothing unless every precondition holds
+    #[instrument(skip_all, fields(update_count = updates.len(), precondition_count = preconditions.len()))]
+    pub async fn write_relationships_with_preconditions(
+        &self,
+        updates: Vec<RelationshipUpdate>,
+        preconditions: Vec<Precondition>,
+    ) -> Result<ZedToken, AuthzedError> {
         info!(
             update_count = updates.len(),
+            precondition_count = preconditions.len(),
             "Writing multiple relationships"
         );
 
+        let retry = self.write_retry(&updates);
         let request = WriteRelationshipsRequest {
             updates,
+            optional_preconditions: preconditions,
             ..Default::default()
         };
 
-        self.permissions()
-            .await
-            .write_relationships(request)
-            .await
-            .map_err(|e| {
-                error!(error = %e, "Failed to write relationships");
-                AuthzedError::WriteRelationshipError { msg: e.to_string() }
-            })?;
+        let request = &request;
+        let written_at = with_retries(&retry, "write_relationships", || async move {
+            self.permissions()
+                .write_relationships(request.clone())
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to write relationships");
+                    AuthzedError::from_status(e, |code, msg| AuthzedError::WriteRelationshipError {
+                        code,
+                        msg,
+                    })
+                })?
+                .into_inner()
+                .written_at
+                .ok_or_else(|| AuthzedError::WriteRelationshipError {
+                    code: Code::Internal,
+                    msg: "SpiceDB returned no written_at token".to_string(),
+                })
+        })
+        .await?;
 
-        info!("Relationships written successfully");
-        Ok(())
+        info!(written_at = %written_at.token, "Relationships written successfully");
+        Ok(written_at)
     }
 
-    #[instrument(skip_all)]
+    /// Delete every relationship matching the filter, `DELETE_BATCH_SIZE` at a time
     pub async fn filtered_delete(
         &self,
         relationship_filter: impl Into<RelationshipFilter>,
-    ) -> Result<(), AuthzedError> {
+    ) -> Result<DeleteSummary, AuthzedError> {
+        self.filtered_delete_in_batches(relationship_filter, DELETE_BATCH_SIZE, |_| {})
+            .await
+    }
+
+    /// Delete every relationship matching the filter with calls deleting at most `batch_size`
+    /// of them, until none is left. SpiceDB refuses a single call deleting more than its
+    /// deletion limit, which a large server easily exceeds. `on_batch` receives the running
+    /// totals after each call. Batches are retried on their own; if one finally fails, the
+    /// earlier ones stay deleted and calling again resumes the deletion.
+    #[instrument(skip_all, fields(batch_size))]
+    pub async fn filtered_delete_in_batches(
+        &self,
+        relationship_filter: impl Into<RelationshipFilter>,
+        batch_size: u32,
+        mut on_batch: impl FnMut(&DeleteSummary),
+    ) -> Result<DeleteSummary, AuthzedError> {
         let relationship_filter: RelationshipFilter = relationship_filter.into();
         info!(
             resource_type = %relationship_filter.resource_type,
@@ -205,52 +368,205 @@ impl AuthZedClient {
 
         let request = DeleteRelationshipsRequest {
             relationship_filter: Some(relationship_filter),
+            optional_limit: batch_size,
+            optional_allow_partial_deletions: true,
             ..Default::default()
         };
 
-        self.permissions()
-            .await
-            .delete_relationships(request)
+        let request = &request;
+        let mut deleted_count = 0;
+        let mut batches = 0;
+        loop {
+            let response = with_retries(&self.retry, "delete_relationships", || async move {
+                self.permissions()
+                    .delete_relationships(request.clone())
+                    .await
+                    .map(|response| response.into_inner())
+                    .map_err(|e| {
+                        error!(error = %e, "Failed to delete relationships with filter");
+                        AuthzedError::from_status(e, |code, msg| {
+                            AuthzedError::DeleteRelationshipError { code, msg }
+                        })
+                    })
+            })
+            .await?;
+            // Complete, or unspecified from a SpiceDB ignoring the limit
+            let done = response.deletion_progress() != DeletionProgress::Partial;
+            let deleted_at =
+                response
+                    .deleted_at
+                    .ok_or_else(|| AuthzedError::DeleteRelationshipError {
+                        code: Code::Internal,
+                        msg: "SpiceDB returned no deleted_at token".to_string(),
+                    })?;
+
+            deleted_count += response.relationships_deleted_count;
+            batches += 1;
+            let summary = DeleteSummary {
+                deleted_at,
+                deleted_count,
+                batches,
+            };
+            debug!(batches, deleted_count, "Relationship batch deleted");
+            on_batch(&summary);
+
+            if done {
+                info!(
+                    deleted_at = %summary.deleted_at.token,
+                    deleted_count,
+                    batches,
+                    "Filtered relationships deleted successfully"
+                );
+                return Ok(summary);
+            }
+        }
+    }
+
+    /// Create many relationships at once, e.g. to onboard an existing community.
+    /// Sends them with ImportBulkRelationships, a single transaction failing as a whole if
+    /// any of them already exists. When SpiceDB lacks that API or some relationships exist,
+    /// falls back to touching them in chunked writes: slower, but safe to run again.
+    #[instrument(skip_all, fields(count = relationships.len()))]
+    pub async fn import_relationships(
+        &self,
+        relationships: Vec<Relationship>,
+    ) -> Result<ImportSummary, AuthzedError> {
+        info!(count = relationships.len(), "Importing relationships");
+
+        let requests: Vec<_> = relationships
+            .chunks(IMPORT_BATCH_SIZE)
+            .map(|chunk| ImportBulkRelationshipsRequest {
+                relationships: chunk.to_vec(),
+            })
+            .collect();
+        match self
+            .bulk
+            .clone()
+            .import_bulk_relationships(stream::iter(requests))
             .await
-            .map_err(|e| {
-                error!(error = %e, "Failed to delete relationships with filter");
-                AuthzedError::DeleteRelationshipError { msg: e.to_string() }
-            })?;
+        {
+            Ok(response) => {
+                let loaded = response.into_inner().num_loaded;
+                info!(loaded, "Relationships imported in bulk");
+                return Ok(ImportSummary {
+                    loaded,
+                    path: ImportPath::BulkImport,
+                });
+            }
+            Err(e) if matches!(e.code(), Code::Unimplemented | Code::AlreadyExists) => {
+                warn!(error = %e, "Bulk import rejected, falling back to chunked writes");
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to import relationships");
+                return Err(AuthzedError::from_status(e, |code, msg| {
+                    AuthzedError::ImportRelationshipsError { code, msg }
+                }));
+            }
+        }
+
+        let mut loaded = 0;
+        for chunk in relationships.chunks(IMPORT_BATCH_SIZE) {
+            self.write_relationships(chunk.iter().map(Action::touch).collect())
+                .await?;
+            loaded += chunk.len() as u64;
+            info!(
+                loaded,
+                total = relationships.len(),
+                "Relationship chunk imported"
+            );
+        }
+        Ok(ImportSummary {
+            loaded,
+            path: ImportPath::ChunkedWrites,
+        })
+    }
+
+    /// Stream every relationship SpiceDB holds, in batches, all read at the same revision.
+    /// Like `read_relationships_stream`, the stream is not retried.
+    #[instrument(skip_all)]
+    pub async fn export_relationships_stream(
+        &self,
+        consistency: impl Into<Consistency>,
+    ) -> Result<BoxStream<'static, Result<Vec<Relationship>, AuthzedError>>, AuthzedError> {
+        info!("Exporting relationships");
+
+        let request = ExportBulkRelationshipsRequest {
+            consistency: Some(consistency.into()),
+            ..Default::default()
+        };
+        let stream =
+            self.bulk
+                .clone()
+                .export_bulk_relationships(request)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to export relationships");
+                    AuthzedError::from_status(e, |code, msg| {
+                        AuthzedError::ExportRelationshipsError { code, msg }
+                    })
+                })?
+                .into_inner();
 
-        info!("Filtered relationships deleted successfully");
-        Ok(())
+        Ok(stream
+            .map(|response| {
+                response
+                    .map(|response| response.relationships)
+                    .map_err(|e| {
+                        error!(error = %e, "Error in export stream");
+                        AuthzedError::from_status(e, |code, msg| {
+                            AuthzedError::ExportRelationshipsError { code, msg }
+                        })
+                    })
+            })
+            .boxed())
     }
 
     #[instrument(skip_all)]
     pub async fn write_relationship(
         &self,
         relationship_update: RelationshipUpdate,
-    ) -> Result<(), AuthzedError> {
+    ) -> Result<ZedToken, AuthzedError> {
         debug!("Writing single relationship");
 
+        let retry = self.write_retry(std::slice::from_ref(&relationship_update));
         let request = WriteRelationshipsRequest {
             updates: vec![relationship_update],
             ..Default::default()
         };
 
-        self.permissions()
-            .await
-            .write_relationships(request)
-            .await
-            .map_err(|e| {
-                error!(error = %e, "Failed to write relationship");
-                AuthzedError::WriteRelationshipError { msg: e.to_string() }
-            })?;
+        let request = &request;
+        let written_at = with_retries(&retry, "write_relationship", || async move {
+            self.permissions()
+                .write_relationships(request.clone())
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to write relationship");
+                    AuthzedError::from_status(e, |code, msg| AuthzedError::WriteRelationshipError {
+                        code,
+                        msg,
+                    })
+                })?
+                .into_inner()
+                .written_at
+                .ok_or_else(|| AuthzedError::WriteRelationshipError {
+                    code: Code::Internal,
+                    msg: "SpiceDB returned no written_at token".to_string(),
+                })
+        })
+        .await?;
 
         debug!("Relationship written successfully");
-        Ok(())
+        Ok(written_at)
     }
 
-    /// Read relationships matching the given filter
+    /// Read relationships matching the given filter.
+    /// Buffers every match: use `read_relationships_stream` or `read_relationships_page`
+    /// when the filter can match more than a handful of relationships.
     #[instrument(skip_all)]
     pub async fn read_relationships(
         &self,
         relationship_filter: impl Into<RelationshipFilter>,
+        consistency: impl Into<Consistency>,
     ) -> Result<Vec<Relationship>, AuthzedError> {
         let relationship_filter: RelationshipFilter = relationship_filter.into();
         info!(
@@ -260,42 +576,630 @@ impl AuthZedClient {
         );
 
         let request = ReadRelationshipsRequest {
+            consistency: Some(consistency.into()),
             relationship_filter: Some(relationship_filter),
             ..Default::default()
         };
 
-        let mut stream = self
+        let request = &request;
+        let relationships = with_retries(&self.retry, "read_relationships", || async move {
+            self.send_read_relationships(request.clone())
+                .await?
+                .map(|response| response.and_then(relationship_of))
+                .try_collect::<Vec<_>>()
+                .await
+        })
+        .await?;
+
+        info!(
+            relationship_count = relationships.len(),
+            "Successfully read relationships"
+        );
+        Ok(relationships)
+    }
+
+    /// Stream relationships matching the filter as SpiceDB sends them, without buffering.
+    /// A `limit` of 0 streams every match; `cursor` resumes after a previous page.
+    /// The stream is not retried: on error, resume from the last page cursor instead.
+    #[instrument(skip_all, fields(limit))]
+    pub async fn read_relationships_stream(
+        &self,
+        relationship_filter: impl Into<RelationshipFilter>,
+        limit: u32,
+        cursor: Option<Cursor>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<BoxStream<'static, Result<Relationship, AuthzedError>>, AuthzedError> {
+        let request = read_relationships_request(
+            relationship_filter.into(),
+            limit,
+            cursor,
+            consistency.into(),
+        );
+        Ok(self
+            .send_read_relationships(request)
+            .await?
+            .map(|response| response.and_then(relationship_of))
+            .boxed())
+    }
+
+    /// Fetch one page of `read_relationships_stream`, returning the cursor for the next page
+    #[instrument(skip_all, fields(limit))]
+    pub async fn read_relationships_page(
+        &self,
+        relationship_filter: impl Into<RelationshipFilter>,
+        limit: u32,
+        cursor: Option<Cursor>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<Page<Relationship>, AuthzedError> {
+        let request = read_relationships_request(
+            relationship_filter.into(),
+            limit,
+            cursor,
+            consistency.into(),
+        );
+
+        let request = &request;
+        let responses = with_retries(&self.retry, "read_relationships_page", || async move {
+            self.send_read_relationships(request.clone())
+                .await?
+                .try_collect::<Vec<_>>()
+                .await
+        })
+        .await?;
+
+        // A short page means SpiceDB has nothing left to return
+        let next_cursor = if limit > 0 && responses.len() as u32 >= limit {
+            responses.last().and_then(|r| r.after_result_cursor.clone())
+        } else {
+            None
+        };
+        let items = responses
+            .into_iter()
+            .map(relationship_of)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        debug!(
+            item_count = items.len(),
+            has_next = next_cursor.is_some(),
+            "Relationship page read"
+        );
+        Ok(Page { items, next_cursor })
+    }
+
+    /// Hand every relationship matching the filter to `on_page`, `page_size` at a time.
+    /// Memory stays bounded by one page and a failed page is retried on its own,
+    /// which suits jobs walking every relationship of a large server.
+    #[instrument(skip_all, fields(page_size))]
+    pub async fn for_each_relationship_page(
+        &self,
+        relationship_filter: impl Into<RelationshipFilter>,
+        page_size: u32,
+        consistency: impl Into<Consistency>,
+        mut on_page: impl FnMut(Vec<Relationship>),
+    ) -> Result<usize, AuthzedError> {
+        let relationship_filter: RelationshipFilter = relationship_filter.into();
+        let consistency: Consistency = consistency.into();
+
+        let mut total = 0;
+        let mut cursor = None;
+        loop {
+            let page = self
+                .read_relationships_page(
+                    relationship_filter.clone(),
+                    page_size,
+                    cursor,
+                    consistency.clone(),
+                )
+                .await?;
+            total += page.items.len();
+            on_page(page.items);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        debug!(total, "Walked every relationship page");
+        Ok(total)
+    }
+
+    async fn send_read_relationships(
+        &self,
+        request: ReadRelationshipsRequest,
+    ) -> Result<BoxStream<'static, Result<ReadRelationshipsResponse, AuthzedError>>, AuthzedError>
+    {
+        debug!(
+            limit = request.optional_limit,
+            has_cursor = request.optional_cursor.is_some(),
+            "Reading relationships"
+        );
+
+        let stream = self
             .permissions()
-            .await
             .read_relationships(request)
             .await
             .map_err(|e| {
                 error!(error = %e, "Failed to read relationships");
-                AuthzedError::ConnectionError { msg: e.to_string() }
+                AuthzedError::from_status(e, |code, msg| AuthzedError::ReadRelationshipsError {
+                    code,
+                    msg,
+                })
             })?
             .into_inner();
 
-        let mut relationships = Vec::new();
+        Ok(stream
+            .map(|response| {
+                response.map_err(|e| {
+                    error!(error = %e, "Error in relationship stream");
+                    AuthzedError::from_status(e, |code, msg| AuthzedError::ReadRelationshipsError {
+                        code,
+                        msg,
+                    })
+                })
+            })
+            .boxed())
+    }
+
+    /// Check whether a subject holds a permission on a resource
+    #[instrument(skip_all, fields(permission = %permission))]
+    pub async fn check_permission(
+        &self,
+        resource: impl Into<ObjectReference>,
+        permission: &str,
+        subject: impl Into<SubjectReference>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<CheckPermissionResponse, AuthzedError> {
+        self.send_check_permission(
+            resource.into(),
+            permission,
+            subject.into(),
+            consistency.into(),
+            false,
+        )
+        .await
+    }
+
+    /// Same as `check_permission`, with SpiceDB's debug trace of how the answer was reached
+    #[instrument(skip_all, fields(permission = %permission))]
+    pub async fn check_permission_with_trace(
+        &self,
+        resource: impl Into<ObjectReference>,
+        permission: &str,
+        subject: impl Into<SubjectReference>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<CheckPermissionResponse, AuthzedError> {
+        self.send_check_permission(
+            resource.into(),
+            permission,
+            subject.into(),
+            consistency.into(),
+            true,
+        )
+        .await
+    }
+
+    async fn send_check_permission(
+        &self,
+        resource: ObjectReference,
+        permission: &str,
+        subject: SubjectReference,
+        consistency: Consistency,
+        with_tracing: bool,
+    ) -> Result<CheckPermissionResponse, AuthzedError> {
+        debug!(
+            resource_type = %resource.object_type,
+            resource_id = %resource.object_id,
+            subject_type = subject.object.as_ref().map(|o| o.object_type.as_str()),
+            subject_id = subject.object.as_ref().map(|o| o.object_id.as_str()),
+            with_tracing,
+            "Checking permission"
+        );
+
+        let request = CheckPermissionRequest {
+            consistency: Some(consistency),
+            resource: Some(resource),
+            permission: permission.to_string(),
+            subject: Some(subject),
+            with_tracing,
+            ..Default::default()
+        };
+
+        let response = self
+            .permissions()
+            .check_permission(request)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to check permission");
+                AuthzedError::from_status(e, |code, msg| AuthzedError::CheckPermissionError {
+                    code,
+                    msg,
+                })
+            })?
+            .into_inner();
+
+        debug!(
+            permissionship = ?response.permissionship(),
+            "Permission checked successfully"
+        );
+        Ok(response)
+    }
+
+    /// Check many (resource, permission, subject) triples, in requests of at most
+    /// `bulk_check_chunk_size` items sent concurrently.
+    /// Pairs are returned in request order, each carrying either a result or its own error.
+    #[instrument(skip_all, fields(item_count = items.len()))]
+    pub async fn check_bulk_permissions(
+        &self,
+        items: Vec<CheckBulkPermissionsRequestItem>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<Vec<CheckBulkPermissionsPair>, AuthzedError> {
+        let consistency = consistency.into();
+        let mut chunks = Vec::new();
+        let mut items = items.into_iter().peekable();
+        while items.peek().is_some() {
+            chunks.push(items.by_ref().take(self.bulk_check_chunk_size).collect());
+        }
+        info!(chunk_count = chunks.len(), "Checking permissions in bulk");
+
+        // try_join_all keeps the chunk order, so pairs stay in request order
+        let responses = future::try_join_all(
+            chunks
+                .into_iter()
+                .map(|chunk| self.check_bulk_chunk(chunk, consistency.clone())),
+        )
+        .await?;
+        let pairs: Vec<CheckBulkPermissionsPair> = responses.into_iter().flatten().collect();
+
+        info!(
+            pair_count = pairs.len(),
+            "Bulk permissions checked successfully"
+        );
+        Ok(pairs)
+    }
+
+    async fn check_bulk_chunk(
+        &self,
+        items: Vec<CheckBulkPermissionsRequestItem>,
+        consistency: Consistency,
+    ) -> Result<Vec<CheckBulkPermissionsPair>, AuthzedError> {
+        let request = CheckBulkPermissionsRequest {
+            consistency: Some(consistency),
+            items,
+            ..Default::default()
+        };
+
+        let response = self
+            .permissions()
+            .check_bulk_permissions(request)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to check permissions in bulk");
+                AuthzedError::from_status(e, |code, msg| AuthzedError::CheckPermissionError {
+                    code,
+                    msg,
+                })
+            })?
+            .into_inner();
+        Ok(response.pairs)
+    }
+
+    /// Stream the resources of `resource_type` on which `subject` holds `permission`.
+    /// Results arrive as SpiceDB computes them; `limit` of 0 means no limit.
+    #[instrument(skip_all, fields(resource_type = %resource_type, permission = %permission))]
+    pub async fn lookup_resources_stream(
+        &self,
+        resource_type: &str,
+        permission: &str,
+        subject: impl Into<SubjectReference>,
+        limit: u32,
+        cursor: Option<Cursor>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<BoxStream<'static, Result<LookupResourcesResponse, AuthzedError>>, AuthzedError>
+    {
+        let subject: SubjectReference = subject.into();
+        debug!(
+            subject_type = subject.object.as_ref().map(|o| o.object_type.as_str()),
+            subject_id = subject.object.as_ref().map(|o| o.object_id.as_str()),
+            limit,
+            has_cursor = cursor.is_some(),
+            "Looking up resources"
+        );
+
+        let request = LookupResourcesRequest {
+            consistency: Some(consistency.into()),
+            resource_object_type: resource_type.to_string(),
+            permission: permission.to_string(),
+            subject: Some(subject),
+            optional_limit: limit,
+            optional_cursor: cursor,
+            ..Default::default()
+        };
+
+        let stream = self
+            .permissions()
+            .lookup_resources(request)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to lookup resources");
+                AuthzedError::from_status(e, |code, msg| AuthzedError::LookupResourcesError {
+                    code,
+                    msg,
+                })
+            })?
+            .into_inner();
+
+        Ok(stream
+            .map(|response| {
+                response.map_err(|e| {
+                    error!(error = %e, "Error in lookup resources stream");
+                    AuthzedError::from_status(e, |code, msg| AuthzedError::LookupResourcesError {
+                        code,
+                        msg,
+                    })
+                })
+            })
+            .boxed())
+    }
+
+    /// Fetch one page of `lookup_resources_stream`, returning the cursor for the next page
+    #[instrument(skip_all, fields(resource_type = %resource_type, permission = %permission, limit))]
+    pub async fn lookup_resources_page(
+        &self,
+        resource_type: &str,
+        permission: &str,
+        subject: impl Into<SubjectReference>,
+        limit: u32,
+        cursor: Option<Cursor>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<Page<LookupResourcesResponse>, AuthzedError> {
+        let mut stream = self
+            .lookup_resources_stream(
+                resource_type,
+                permission,
+                subject,
+                limit,
+                cursor,
+                consistency,
+            )
+            .await?;
+
+        let mut items = Vec::new();
+        while let Some(response) = stream.next().await {
+            items.push(response?);
+        }
+
+        // A short page means SpiceDB has nothing left to return
+        let next_cursor = if limit > 0 && items.len() as u32 >= limit {
+            items.last().and_then(|r| r.after_result_cursor.clone())
+        } else {
+            None
+        };
+
+        debug!(
+            item_count = items.len(),
+            has_next = next_cursor.is_some(),
+            "Resource page looked up"
+        );
+        Ok(Page { items, next_cursor })
+    }
+
+    /// Collect the IDs of every resource of `resource_type` on which `subject`
+    /// unconditionally holds `permission`
+    #[instrument(skip_all, fields(resource_type = %resource_type, permission = %permission))]
+    pub async fn lookup_resources(
+        &self,
+        resource_type: &str,
+        permission: &str,
+        subject: impl Into<SubjectReference>,
+        consistency: impl Into<Consistency>,
+    ) -> Result<Vec<String>, AuthzedError> {
+        let mut stream = self
+            .lookup_resources_stream(resource_type, permission, subject, 0, None, consistency)
+            .await?;
+
+        let mut resource_ids = Vec::new();
+        while let Some(response) = stream.next().await {
+            let response = response?;
+            if response.permissionship() == LookupPermissionship::HasPermission {
+                resource_ids.push(response.resource_object_id);
+            } else {
+                debug!(
+                    resource_id = %response.resource_object_id,
+                    "Skipping conditionally accessible resource"
+                );
+            }
+        }
+
+        info!(
+            resource_count = resource_ids.len(),
+            "Successfully looked up resources"
+        );
+        Ok(resource_ids)
+    }
+
+    /// Collect every subject of `subject_type` holding `permission` on `resource`.
+    /// SpiceDB walks subject sets (e.g. `role#member`) down to concrete subjects;
+    /// wildcards come back as a `*` subject with their exclusions.
+    #[instrument(skip_all, fields(permission = %permission, subject_type = %subject_type))]
+    pub async fn lookup_subjects(
+        &self,
+        resource: impl Into<ObjectReference>,
+        permission: &str,
+        subject_type: &str,
+        consistency: impl Into<Consistency>,
+    ) -> Result<Vec<LookupSubjectsResponse>, AuthzedError> {
+        let resource: ObjectReference = resource.into();
+        debug!(
+            resource_type = %resource.object_type,
+            resource_id = %resource.object_id,
+            "Looking up subjects"
+        );
+
+        let request = LookupSubjectsRequest {
+            consistency: Some(consistency.into()),
+            resource: Some(resource),
+            permission: permission.to_string(),
+            subject_object_type: subject_type.to_string(),
+            wildcard_option: lookup_subjects_request::WildcardOption::IncludeWildcards.into(),
+            ..Default::default()
+        };
+
+        let mut stream = self
+            .permissions()
+            .lookup_subjects(request)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to lookup subjects");
+                AuthzedError::from_status(e, |code, msg| AuthzedError::LookupSubjectsError {
+                    code,
+                    msg,
+                })
+            })?
+            .into_inner();
+
+        let mut responses = Vec::new();
         while let Some(response) = stream.next().await {
             let response = response.map_err(|e| {
-                error!(error = %e, "Error in relationship stream");
-                AuthzedError::ConnectionError { msg: e.to_string() }
+                error!(error = %e, "Error in lookup subjects stream");
+                AuthzedError::from_status(e, |code, msg| AuthzedError::LookupSubjectsError {
+                    code,
+                    msg,
+                })
             })?;
-            relationships.push(response.relationship.unwrap());
+            responses.push(response);
         }
 
         info!(
-            relationship_count = relationships.len(),
-            "Successfully read relationships"
+            subject_count = responses.len(),
+            "Successfully looked up subjects"
         );
-        Ok(relationships)
+        Ok(responses)
+    }
+
+    /// Stream relationship changes committed after `after`, or from now when `None`.
+    /// Every response carries the revision it covers, to resume from after a disconnect.
+    #[instrument(skip_all, fields(has_cursor = after.is_some()))]
+    pub async fn watch(
+        &self,
+        object_types: Vec<String>,
+        after: Option<ZedToken>,
+    ) -> Result<BoxStream<'static, Result<WatchResponse, AuthzedError>>, AuthzedError> {
+        info!(?object_types, "Watching relationship changes");
+
+        let request = WatchRequest {
+            optional_object_types: object_types,
+            optional_start_cursor: after,
+            ..Default::default()
+        };
+
+        let stream = self
+            .watch
+            .clone()
+            .watch(request)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to watch relationship changes");
+                AuthzedError::from_status(e, |code, msg| AuthzedError::WatchError { code, msg })
+            })?
+            .into_inner();
+
+        Ok(stream
+            .map(|response| {
+                response.map_err(|e| {
+                    error!(error = %e, "Error in watch stream");
+                    AuthzedError::from_status(e, |code, msg| AuthzedError::WatchError { code, msg })
+                })
+            })
+            .boxed())
+    }
+
+    /// The schema currently applied to SpiceDB, `None` if none was ever written
+    #[instrument(skip_all)]
+    pub async fn read_schema(&self) -> Result<Option<String>, AuthzedError> {
+        debug!("Reading schema");
+
+        let response = self.schema.clone().read_schema(ReadSchemaRequest {}).await;
+        match response {
+            Ok(response) => {
+                let schema_text = response.into_inner().schema_text;
+                info!(schema_len = schema_text.len(), "Schema read successfully");
+                Ok(Some(schema_text))
+            }
+            Err(status) if status.code() == tonic::Code::NotFound => {
+                info!("No schema defined in SpiceDB");
+                Ok(None)
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to read schema");
+                Err(AuthzedError::from_status(e, |code, msg| {
+                    AuthzedError::ReadSchemaError { code, msg }
+                }))
+            }
+        }
+    }
+
+    /// Replace the SpiceDB schema. SpiceDB refuses the write if it would orphan
+    /// existing relationships.
+    #[instrument(skip_all)]
+    pub async fn write_schema(&self, schema: impl Into<String>) -> Result<ZedToken, AuthzedError> {
+        let request = WriteSchemaRequest {
+            schema: schema.into(),
+        };
+        info!(schema_len = request.schema.len(), "Writing schema");
+
+        let written_at = self
+            .schema
+            .clone()
+            .write_schema(request)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to write schema");
+                AuthzedError::from_status(e, |code, msg| AuthzedError::WriteSchemaError {
+                    code,
+                    msg,
+                })
+            })?
+            .into_inner()
+            .written_at
+            .ok_or_else(|| AuthzedError::WriteSchemaError {
+                code: Code::Internal,
+                msg: "SpiceDB returned no written_at token".to_string(),
+            })?;
+
+        info!(written_at = %written_at.token, "Schema written successfully");
+        Ok(written_at)
     }
 }
 
-// Interceptor for adding authentication token to requests
+fn read_relationships_request(
+    relationship_filter: RelationshipFilter,
+    limit: u32,
+    cursor: Option<Cursor>,
+    consistency: Consistency,
+) -> ReadRelationshipsRequest {
+    ReadRelationshipsRequest {
+        consistency: Some(consistency),
+        relationship_filter: Some(relationship_filter),
+        optional_limit: limit,
+        optional_cursor: cursor,
+    }
+}
+
+/// The relationship of a response, which SpiceDB always sets
+fn relationship_of(response: ReadRelationshipsResponse) -> Result<Relationship, AuthzedError> {
+    response
+        .relationship
+        .ok_or_else(|| AuthzedError::ReadRelationshipsError {
+            code: Code::Internal,
+            msg: "SpiceDB returned a response without relationship".to_string(),
+        })
+}
+
+// Interceptor for adding authentication token and call deadline to requests
 #[derive(Clone)]
 struct AuthInterceptor {
     token: String,
+    deadline: Option<Duration>,
 }
 
 impl Interceptor for AuthInterceptor {
@@ -303,6 +1207,10 @@ impl Interceptor for AuthInterceptor {
         &mut self,
         mut request: tonic::Request<()>,
     ) -> Result<tonic::Request<()>, tonic::Status> {
+        // Sent as grpc-timeout, enforced by the channel and by SpiceDB
+        if let Some(deadline) = self.deadline {
+            request.set_timeout(deadline);
+        }
         // Only add auth header if token is not empty
         if !self.token.is_empty() {
             let token = format!("Bearer {}", self.token);
@@ -315,3 +1223,511 @@ impl Interceptor for AuthInterceptor {
         Ok(request)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::authzed::fake::{FakeSpiceDb, fast_retries};
+    use std::sync::Arc;
+
+    fn relationship(user_id: &str) -> Relationship {
+        Relationship {
+            resource: Some(ObjectReference {
+                object_type: "server".to_string(),
+                object_id: "server_1".to_string(),
+            }),
+            relation: "owner".to_string(),
+            subject: Some(SubjectReference {
+                object: Some(ObjectReference {
+                    object_type: "user".to_string(),
+                    object_id: user_id.to_string(),
+                }),
+                optional_relation: String::new(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_retries_until_spicedb_is_back() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        spicedb.fail_next(2, Code::Unavailable);
+
+        // Act
+        let result = client.touch_relationship(relationship("user_1")).await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(spicedb.calls(), 3);
+        assert_eq!(spicedb.relationships(), vec![relationship("user_1")]);
+    }
+
+    #[tokio::test]
+    async fn test_write_gives_up_after_max_retries() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        spicedb.fail_next(10, Code::ResourceExhausted);
+
+        // Act
+        let error = client
+            .write_relationships(vec![relationship("user_1").touch()])
+            .await
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(error.code(), Some(Code::ResourceExhausted));
+        assert!(error.is_retryable());
+        assert_eq!(spicedb.calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_create_is_not_retried() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        spicedb.fail_next(1, Code::Unavailable);
+
+        // Act
+        let error = client
+            .write_relationships(vec![
+                relationship("user_1").touch(),
+                relationship("user_2").create(),
+            ])
+            .await
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(error.code(), Some(Code::Unavailable));
+        assert_eq!(spicedb.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_error_is_not_retried() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        spicedb.fail_next(1, Code::FailedPrecondition);
+
+        // Act
+        let error = client
+            .create_relationship(relationship("user_1"))
+            .await
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(error.code(), Some(Code::FailedPrecondition));
+        assert!(!error.is_retryable());
+        assert_eq!(spicedb.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_filtered_delete_retries_deadline_exceeded() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        client
+            .touch_relationship(relationship("user_1"))
+            .await
+            .unwrap();
+        spicedb.fail_next(1, Code::DeadlineExceeded);
+
+        // Act
+        let summary = client
+            .filtered_delete(RelationshipFilter {
+                resource_type: "server".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(summary.deleted_count, 1);
+        assert_eq!(spicedb.calls(), 3);
+        assert!(spicedb.relationships().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_delete_in_batches_until_empty() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(0)).await;
+        let updates = (0..25)
+            .map(|i| relationship(&format!("user_{}", i)).touch())
+            .collect();
+        client.write_relationships(updates).await.unwrap();
+        let mut progress = Vec::new();
+
+        // Act
+        let summary = client
+            .filtered_delete_in_batches(
+                RelationshipFilter {
+                    resource_type: "server".to_string(),
+                    ..Default::default()
+                },
+                10,
+                |summary| progress.push(summary.deleted_count),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(progress, vec![10, 20, 25]);
+        assert_eq!(summary.deleted_count, 25);
+        assert_eq!(summary.batches, 3);
+        assert!(spicedb.relationships().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_delete_in_batches_retries_a_failed_batch() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        let updates = (0..15)
+            .map(|i| relationship(&format!("user_{}", i)).touch())
+            .collect();
+        client.write_relationships(updates).await.unwrap();
+        // The first batch goes through, the second one fails once
+        spicedb.fail_after(1, 1, Code::Unavailable);
+
+        // Act
+        let summary = client
+            .filtered_delete_in_batches(
+                RelationshipFilter {
+                    resource_type: "server".to_string(),
+                    ..Default::default()
+                },
+                10,
+                |_| {},
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(summary.deleted_count, 15);
+        assert_eq!(summary.batches, 2);
+        assert!(spicedb.relationships().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_relationships_in_bulk() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(0)).await;
+        let relationships = (0..2_500)
+            .map(|i| relationship(&format!("user_{}", i)))
+            .collect();
+
+        // Act
+        let summary = client.import_relationships(relationships).await.unwrap();
+
+        // Assert
+        assert_eq!(summary.path, ImportPath::BulkImport);
+        assert_eq!(summary.loaded, 2_500);
+        assert_eq!(spicedb.relationships().len(), 2_500);
+        assert_eq!(spicedb.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_relationships_falls_back_without_bulk_import() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(0)).await;
+        spicedb.fail_next(1, Code::Unimplemented);
+        let relationships = (0..2_500)
+            .map(|i| relationship(&format!("user_{}", i)))
+            .collect();
+
+        // Act
+        let summary = client.import_relationships(relationships).await.unwrap();
+
+        // Assert
+        assert_eq!(summary.path, ImportPath::ChunkedWrites);
+        assert_eq!(summary.loaded, 2_500);
+        assert_eq!(spicedb.relationships().len(), 2_500);
+        // The rejected import, then three chunks
+        assert_eq!(spicedb.calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_import_relationships_falls_back_on_existing_relationships() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(0)).await;
+        client
+            .touch_relationship(relationship("user_1"))
+            .await
+            .unwrap();
+
+        // Act
+        let summary = client
+            .import_relationships(vec![relationship("user_1"), relationship("user_2")])
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(summary.path, ImportPath::ChunkedWrites);
+        assert_eq!(spicedb.relationships().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_retries_unavailable() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        client
+            .touch_relationship(relationship("user_1"))
+            .await
+            .unwrap();
+        client
+            .touch_relationship(relationship("user_2"))
+            .await
+            .unwrap();
+        spicedb.fail_next(2, Code::Unavailable);
+
+        // Act
+        let relationships = client
+            .read_relationships(RelationshipFilter::default(), Consistency::default())
+            .await;
+
+        // Assert
+        assert_eq!(
+            relationships.unwrap(),
+            vec![relationship("user_1"), relationship("user_2")]
+        );
+    }
+
+    async fn spicedb_with_owners(count: usize) -> (Arc<FakeSpiceDb>, AuthZedClient) {
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(fast_retries(3)).await;
+        let updates = (0..count)
+            .map(|i| relationship(&format!("user_{}", i)).touch())
+            .collect();
+        client.write_relationships(updates).await.unwrap();
+        (spicedb, client)
+    }
+
+    #[tokio::test]
+    async fn test_read_relationships_stream() {
+        // Arrange
+        let (_spicedb, client) = spicedb_with_owners(3).await;
+
+        // Act
+        let relationships: Vec<_> = client
+            .read_relationships_stream(
+                RelationshipFilter {
+                    resource_type: "server".to_string(),
+                    ..Default::default()
+                },
+                0,
+                None,
+                Consistency::default(),
+            )
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            relationships,
+            vec![
+                relationship("user_0"),
+                relationship("user_1"),
+                relationship("user_2")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_relationships_page_walks_every_page() {
+        // Arrange
+        let (_spicedb, client) = spicedb_with_owners(5).await;
+
+        // Act
+        let mut page_sizes = Vec::new();
+        let mut relationships = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = client
+                .read_relationships_page(
+                    RelationshipFilter::default(),
+                    2,
+                    cursor,
+                    Consistency::default(),
+                )
+                .await
+                .unwrap();
+            page_sizes.push(page.items.len());
+            relationships.extend(page.items);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        // Assert
+        assert_eq!(page_sizes, vec![2, 2, 1]);
+        assert_eq!(relationships.len(), 5);
+        assert_eq!(relationships[4], relationship("user_4"));
+    }
+
+    #[tokio::test]
+    async fn test_for_each_relationship_page() {
+        // Arrange
+        let (_spicedb, client) = spicedb_with_owners(5).await;
+
+        // Act
+        let mut page_sizes = Vec::new();
+        let total = client
+            .for_each_relationship_page(
+                RelationshipFilter::default(),
+                2,
+                Consistency::default(),
+                |page| page_sizes.push(page.len()),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(total, 5);
+        assert_eq!(page_sizes, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn test_response_without_relationship_is_an_error() {
+        let result = relationship_of(ReadRelationshipsResponse::default());
+
+        assert!(matches!(
+            result,
+            Err(AuthzedError::ReadRelationshipsError {
+                code: Code::Internal,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retries_disabled() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb.serve(RetryConfig::disabled()).await;
+        spicedb.fail_next(1, Code::Unavailable);
+
+        // Act
+        let result = client.touch_relationship(relationship("user_1")).await;
+
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(spicedb.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_slow_call_times_out() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb
+            .serve_with(|config| config.connection.request_timeout_ms = 50)
+            .await;
+        spicedb.set_latency(Duration::from_secs(5));
+
+        // Act
+        let result = client
+            .check_permission(
+                relationship("user_1").resource.unwrap(),
+                "view",
+                relationship("user_1").subject.unwrap(),
+                Consistency::default(),
+            )
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(AuthzedError::TimeoutError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_check_is_split_into_chunks_in_order() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let client = spicedb
+            .serve_with(|config| config.bulk_check_chunk_size = NonZeroUsize::new(2).unwrap())
+            .await;
+        let items: Vec<CheckBulkPermissionsRequestItem> = (0..5)
+            .map(|i| CheckBulkPermissionsRequestItem {
+                resource: Some(ObjectReference {
+                    object_type: "server".to_string(),
+                    object_id: format!("server_{}", i),
+                }),
+                permission: "view".to_string(),
+                subject: relationship("user_1").subject,
+                context: None,
+            })
+            .collect();
+
+        // Act
+        let pairs = client
+            .check_bulk_permissions(items, Consistency::default())
+            .await
+            .unwrap();
+
+        // Assert
+        let ids: Vec<String> = pairs
+            .into_iter()
+            .map(|pair| pair.request.unwrap().resource.unwrap().object_id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["server_0", "server_1", "server_2", "server_3", "server_4"]
+        );
+        let mut sizes = spicedb.bulk_check_sizes();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 2, 2]);
+    }
+
+    async fn unused_endpoint() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().to_string()
+    }
+
+    fn config(endpoint: String, lazy_connect: bool) -> AuthZedConfig {
+        AuthZedConfig {
+            endpoint,
+            token: None,
+            tls: AuthZedTlsConfig::default(),
+            channel_pool_size: NonZeroUsize::MIN,
+            bulk_check_chunk_size: DEFAULT_BULK_CHECK_CHUNK_SIZE,
+            retry: RetryConfig::disabled(),
+            connection: AuthZedConnectionConfig {
+                lazy_connect,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_eager_connect_fails_without_spicedb() {
+        let result = AuthZedClient::new(config(unused_endpoint().await, false)).await;
+
+        assert!(matches!(result, Err(AuthzedError::ConnectionError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_lazy_connect_starts_without_spicedb() {
+        // Arrange
+        let client = AuthZedClient::new(config(unused_endpoint().await, true))
+            .await
+            .unwrap();
+
+        // Act
+        let error = client
+            .touch_relationship(relationship("user_1"))
+            .await
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(error.code(), Some(Code::Unavailable));
+    }
+}
@@ -0,0 +1,190 @@
+use std::{future::Future, time::Duration};
+
+use clap::Parser;
+use rand::Rng;
+use tracing::warn;
+
+use crate::infrastructure::authzed::error::AuthzedError;
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_INITIAL_BACKOFF_MS: u64 = 100;
+const DEFAULT_MAX_BACKOFF_MS: u64 = 5_000;
+
+/// Retries of SpiceDB calls failing with a transient error
+#[derive(Debug, Clone, Parser)]
+pub struct RetryConfig {
+    /// Retries after the first attempt; 0 disables retrying
+    #[arg(
+        long = "authzed-max-retries",
+        env = "AUTHZED_MAX_RETRIES",
+        default_value_t = DEFAULT_MAX_RETRIES
+    )]
+    pub max_retries: u32,
+
+    /// Upper bound of the delay before the first retry, doubled on each retry
+    #[arg(
+        long = "authzed-retry-initial-backoff-ms",
+        env = "AUTHZED_RETRY_INITIAL_BACKOFF_MS",
+        default_value_t = DEFAULT_INITIAL_BACKOFF_MS
+    )]
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound of any single delay between retries
+    #[arg(
+        long = "authzed-retry-max-backoff-ms",
+        env = "AUTHZED_RETRY_MAX_BACKOFF_MS",
+        default_value_t = DEFAULT_MAX_BACKOFF_MS
+    )]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff_ms: DEFAULT_INITIAL_BACKOFF_MS,
+            max_backoff_ms: DEFAULT_MAX_BACKOFF_MS,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Never retry, e.g. for callers running their own retry loop
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (0-based): exponential, capped, with
+    /// equal jitter so clients failing together do not retry in lockstep
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .initial_backoff_ms
+            .saturating_mul(1u64.checked_shl(retry).unwrap_or(u64::MAX))
+            .min(self.max_backoff_ms);
+        let half = ceiling / 2;
+        Duration::from_millis(half + rand::thread_rng().gen_range(0..=ceiling - half))
+    }
+}
+
+/// Run `call` until it succeeds, fails with a permanent error or runs out of retries.
+/// Only idempotent requests should be retried: a write whose response was lost may
+/// have been applied, so a retried create can fail with AlreadyExists.
+pub async fn with_retries<T, F, Fut>(
+    config: &RetryConfig,
+    operation: &str,
+    mut call: F,
+) -> Result<T, AuthzedError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AuthzedError>>,
+{
+    let mut retry = 0;
+    loop {
+        match call().await {
+            Err(e) if e.is_retryable() && retry < config.max_retries => {
+                let delay = config.backoff(retry);
+                retry += 1;
+                warn!(
+                    operation,
+                    retry,
+                    max_retries = config.max_retries,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "Transient SpiceDB error, retrying"
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tonic::Code;
+
+    fn config(max_retries: u32) -> RetryConfig {
+        RetryConfig {
+            max_retries,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 1,
+        }
+    }
+
+    async fn failing_call(
+        calls: &AtomicU32,
+        failures: u32,
+        code: Code,
+    ) -> Result<u32, AuthzedError> {
+        let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if call <= failures {
+            Err(AuthzedError::WriteRelationshipError {
+                code,
+                msg: format!("attempt {}", call),
+            })
+        } else {
+            Ok(call)
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_within_jitter_and_is_capped() {
+        let config = RetryConfig {
+            max_retries: 10,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+
+        for _ in 0..100 {
+            let first = config.backoff(0).as_millis();
+            let third = config.backoff(2).as_millis();
+            let capped = config.backoff(63).as_millis();
+            assert!((50..=100).contains(&first), "{}", first);
+            assert!((200..=400).contains(&third), "{}", third);
+            assert!((500..=1_000).contains(&capped), "{}", capped);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors_until_success() {
+        let calls = AtomicU32::new(0);
+
+        let result = with_retries(&config(3), "test", || {
+            failing_call(&calls, 2, Code::Unavailable)
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let calls = AtomicU32::new(0);
+
+        let result = with_retries(&config(2), "test", || {
+            failing_call(&calls, 5, Code::Unavailable)
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_permanent_errors() {
+        let calls = AtomicU32::new(0);
+
+        let result = with_retries(&config(3), "test", || {
+            failing_call(&calls, 1, Code::InvalidArgument)
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+
+use clap::{Parser, ValueEnum};
+
+mod parser;
+
+pub use parser::{Schema, SchemaDefinition, SchemaParseError, SchemaPermission, SchemaRelation};
+
+/// The schema the repositories are written against
+pub const BEEP_SCHEMA: &str = include_str!("../../../../authzed/beep.zed");
+
+/// What to do at startup when the live SpiceDB schema differs from beep.zed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SchemaMode {
+    /// Do not read the live schema nor validate the relation mappings
+    Skip,
+    /// Report differences and refuse to start
+    #[default]
+    Verify,
+    /// Report differences and write beep.zed to SpiceDB
+    Apply,
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct SchemaConfig {
+    /// Check (or apply) the embedded beep.zed against SpiceDB at startup
+    #[arg(
+        long = "schema-mode",
+        env = "SCHEMA_MODE",
+        value_enum,
+        default_value_t = SchemaMode::Verify
+    )]
+    pub mode: SchemaMode,
+}
+
+/// A way the live schema differs from the expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaDifference {
+    MissingDefinition {
+        definition: String,
+    },
+    UnexpectedDefinition {
+        definition: String,
+    },
+    MissingRelation {
+        definition: String,
+        relation: String,
+    },
+    UnexpectedRelation {
+        definition: String,
+        relation: String,
+    },
+    RelationChanged {
+        definition: String,
+        relation: String,
+        expected: Vec<String>,
+        actual: Vec<String>,
+    },
+    MissingPermission {
+        definition: String,
+        permission: String,
+    },
+    UnexpectedPermission {
+        definition: String,
+        permission: String,
+    },
+    PermissionChanged {
+        definition: String,
+        permission: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for SchemaDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaDifference::MissingDefinition { definition } => {
+                write!(f, "- definition {}", definition)
+            }
+            SchemaDifference::UnexpectedDefinition { definition } => {
+                write!(f, "+ definition {}", definition)
+            }
+            SchemaDifference::MissingRelation {
+                definition,
+                relation,
+            } => write!(f, "- relation {}#{}", definition, relation),
+            SchemaDifference::UnexpectedRelation {
+                definition,
+                relation,
+            } => write!(f, "+ relation {}#{}", definition, relation),
+            SchemaDifference::RelationChanged {
+                definition,
+                relation,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "~ relation {}#{}: expected {}, found {}",
+                definition,
+                relation,
+                expected.join(" | "),
+                actual.join(" | ")
+            ),
+            SchemaDifference::MissingPermission {
+                definition,
+                permission,
+            } => write!(f, "- permission {}#{}", definition, permission),
+            SchemaDifference::UnexpectedPermission {
+                definition,
+                permission,
+            } => write!(f, "+ permission {}#{}", definition, permission),
+            SchemaDifference::PermissionChanged {
+                definition,
+                permission,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "~ permission {}#{}: expected {}, found {}",
+                definition, permission, expected, actual
+            ),
+        }
+    }
+}
+
+/// Differences of `actual` (the live schema) relative to `expected`. Lines
+/// starting with `-` are missing from the live schema, `+` only exist there and
+/// `~` differ.
+pub fn diff(expected: &Schema, actual: &Schema) -> Vec<SchemaDifference> {
+    let mut differences = Vec::new();
+
+    for expected_definition in &expected.definitions {
+        let definition = expected_definition.name.clone();
+        let Some(actual_definition) = actual.definition(&definition) else {
+            differences.push(SchemaDifference::MissingDefinition { definition });
+            continue;
+        };
+
+        for expected_relation in &expected_definition.relations {
+            let relation = expected_relation.name.clone();
+            match actual_definition.relation(&relation) {
+                None => differences.push(SchemaDifference::MissingRelation {
+                    definition: definition.clone(),
+                    relation,
+                }),
+                Some(actual_relation)
+                    if actual_relation.subject_types != expected_relation.subject_types =>
+                {
+                    differences.push(SchemaDifference::RelationChanged {
+                        definition: definition.clone(),
+                        relation,
+                        expected: expected_relation.subject_types.clone(),
+                        actual: actual_relation.subject_types.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for actual_relation in &actual_definition.relations {
+            if expected_definition
+                .relation(&actual_relation.name)
+                .is_none()
+            {
+                differences.push(SchemaDifference::UnexpectedRelation {
+                    definition: definition.clone(),
+                    relation: actual_relation.name.clone(),
+                });
+            }
+        }
+
+        for expected_permission in &expected_definition.permissions {
+            let permission = expected_permission.name.clone();
+            match actual_definition.permission(&permission) {
+                None => differences.push(SchemaDifference::MissingPermission {
+                    definition: definition.clone(),
+                    permission,
+                }),
+                Some(actual_permission)
+                    if actual_permission.expression != expected_permission.expression =>
+                {
+                    differences.push(SchemaDifference::PermissionChanged {
+                        definition: definition.clone(),
+                        permission,
+                        expected: expected_permission.expression.clone(),
+                        actual: actual_permission.expression.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for actual_permission in &actual_definition.permissions {
+            if expected_definition
+                .permission(&actual_permission.name)
+                .is_none()
+            {
+                differences.push(SchemaDifference::UnexpectedPermission {
+                    definition: definition.clone(),
+                    permission: actual_permission.name.clone(),
+                });
+            }
+        }
+    }
+
+    for actual_definition in &actual.definitions {
+        if expected.definition(&actual_definition.name).is_none() {
+            differences.push(SchemaDifference::UnexpectedDefinition {
+                definition: actual_definition.name.clone(),
+            });
+        }
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_beep_schema() {
+        let schema = Schema::parse(BEEP_SCHEMA).unwrap();
+
+        let names: Vec<&str> = schema
+            .definitions
+            .iter()
+            .map(|definition| definition.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["user", "server", "role", "channel", "permission_override"]
+        );
+
+        let channel = schema.definition("channel").unwrap();
+        assert_eq!(
+            channel
+                .relation("send_message_grant")
+                .unwrap()
+                .subject_types,
+            vec!["permission_override#granted_to", "role#member", "user"]
+        );
+        assert_eq!(
+            channel.permission("send_message").unwrap().expression,
+            "(server->send_message+send_message_grant)-send_message_deny"
+        );
+    }
+
+    #[test]
+    fn test_parse_ignores_comments_caveats_and_layout() {
+        let text = r#"
+            use expiration
+
+            caveat only_on_weekdays(day string) {
+                day != "saturday" && day != "sunday"
+            }
+
+            // a line comment
+            definition user {}
+
+            definition document {
+                /** doc comment */
+                relation viewer: user | user:* with only_on_weekdays
+                permission view = viewer +
+                    viewer
+            }
+        "#;
+
+        let schema = Schema::parse(text).unwrap();
+
+        let document = schema.definition("document").unwrap();
+        assert_eq!(
+            document.relation("viewer").unwrap().subject_types,
+            vec!["user", "user:* with only_on_weekdays"]
+        );
+        assert_eq!(
+            document.permission("view").unwrap().expression,
+            "viewer+viewer"
+        );
+        assert!(schema.definition("user").unwrap().relations.is_empty());
+    }
+
+    #[test]
+    fn test_parse_unclosed_definition() {
+        let result = Schema::parse("definition user {\n  relation owner: user\n");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_diff_identical_schemas_with_different_formatting() {
+        let expected = Schema::parse(BEEP_SCHEMA).unwrap();
+        let reformatted = BEEP_SCHEMA.replace("    ", "\t").replace(" + ", "+");
+        let actual = Schema::parse(&reformatted).unwrap();
+
+        assert!(diff(&expected, &actual).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_each_difference() {
+        let expected = Schema::parse(
+            "definition user {}\n\
+             definition doc {\n\
+             relation owner: user\n\
+             relation viewer: user\n\
+             permission view = owner + viewer\n\
+             }",
+        )
+        .unwrap();
+        let actual = Schema::parse(
+            "definition doc {\n\
+             relation owner: user | doc#owner\n\
+             relation editor: user\n\
+             permission view = owner\n\
+             }\n\
+             definition folder {}",
+        )
+        .unwrap();
+
+        let differences: Vec<String> = diff(&expected, &actual)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            differences,
+            vec![
+                "- definition user",
+                "~ relation doc#owner: expected user, found doc#owner | user",
+                "- relation doc#viewer",
+                "+ relation doc#editor",
+                "~ permission doc#view: expected owner+viewer, found owner",
+                "+ definition folder",
+            ]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Parser of the relations and permissions of a zed schema. Also compiled into
+//! core/build.rs, which generates typed names from beep.zed, so it only uses std and thiserror.
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Invalid schema at line {line}: {msg}")]
+pub struct SchemaParseError {
+    pub line: usize,
+    pub msg: String,
+}
+
+/// Relations and permissions of a schema; caveats and comments are ignored
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schema {
+    pub definitions: Vec<SchemaDefinition>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDefinition {
+    pub name: String,
+    pub relations: Vec<SchemaRelation>,
+    pub permissions: Vec<SchemaPermission>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaRelation {
+    pub name: String,
+    /// Allowed subjects as written, e.g. "user" or "role#member", sorted
+    pub subject_types: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaPermission {
+    pub name: String,
+    /// Expression with whitespace removed
+    pub expression: String,
+}
+
+impl Schema {
+    pub fn parse(text: &str) -> Result<Self, SchemaParseError> {
+        let text = strip_comments(text);
+        let mut definitions = Vec::new();
+        let mut current: Option<SchemaDefinition> = None;
+        let mut statement: Option<(usize, String)> = None;
+        let mut caveat_depth = 0usize;
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if caveat_depth > 0 {
+                caveat_depth += line.matches('{').count();
+                caveat_depth -= line.matches('}').count().min(caveat_depth);
+                continue;
+            }
+
+            let Some(definition) = current.as_mut() else {
+                if let Some(rest) = line.strip_prefix("definition ") {
+                    let (name, body) = rest.split_once('{').ok_or_else(|| SchemaParseError {
+                        line: line_number,
+                        msg: "expected '{' after definition name".to_string(),
+                    })?;
+                    let definition = SchemaDefinition {
+                        name: name.trim().to_string(),
+                        ..Default::default()
+                    };
+                    match body.trim() {
+                        "}" => definitions.push(definition),
+                        "" => current = Some(definition),
+                        _ => {
+                            return Err(SchemaParseError {
+                                line: line_number,
+                                msg: "definition body must start on its own line".to_string(),
+                            });
+                        }
+                    }
+                } else if line.starts_with("caveat ") {
+                    caveat_depth = line
+                        .matches('{')
+                        .count()
+                        .saturating_sub(line.matches('}').count());
+                }
+                // Anything else at the top level (e.g. `use` directives) is ignored
+                continue;
+            };
+
+            let starts_statement =
+                line.starts_with("relation ") || line.starts_with("permission ") || line == "}";
+            if starts_statement && let Some((line, statement)) = statement.take() {
+                parse_statement(definition, line, &statement)?;
+            }
+
+            if line == "}" {
+                definitions.extend(current.take());
+            } else if starts_statement {
+                statement = Some((line_number, line.to_string()));
+            } else if let Some((_, statement)) = statement.as_mut() {
+                statement.push(' ');
+                statement.push_str(line);
+            } else {
+                return Err(SchemaParseError {
+                    line: line_number,
+                    msg: format!("unexpected '{}'", line),
+                });
+            }
+        }
+
+        if let Some(definition) = current {
+            return Err(SchemaParseError {
+                line: text.lines().count(),
+                msg: format!("definition {} is not closed", definition.name),
+            });
+        }
+
+        Ok(Self { definitions })
+    }
+
+    pub fn definition(&self, name: &str) -> Option<&SchemaDefinition> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.name == name)
+    }
+}
+
+impl SchemaRelation {
+    /// Whether a subject of `subject_type` (e.g. "role#member") may be written,
+    /// with or without a caveat
+    pub fn allows(&self, subject_type: &str) -> bool {
+        self.subject_types.iter().any(|allowed| {
+            allowed
+                .split_whitespace()
+                .next()
+                .is_some_and(|allowed| allowed == subject_type)
+        })
+    }
+}
+
+impl SchemaDefinition {
+    pub fn relation(&self, name: &str) -> Option<&SchemaRelation> {
+        self.relations.iter().find(|relation| relation.name == name)
+    }
+
+    pub fn permission(&self, name: &str) -> Option<&SchemaPermission> {
+        self.permissions
+            .iter()
+            .find(|permission| permission.name == name)
+    }
+}
+
+fn parse_statement(
+    definition: &mut SchemaDefinition,
+    line: usize,
+    statement: &str,
+) -> Result<(), SchemaParseError> {
+    let invalid = |msg: &str| SchemaParseError {
+        line,
+        msg: msg.to_string(),
+    };
+
+    if let Some(rest) = statement.strip_prefix("relation ") {
+        let (name, subject_types) = rest
+            .split_once(':')
+            .ok_or_else(|| invalid("expected ':' after relation name"))?;
+        let mut subject_types: Vec<String> = subject_types
+            .split('|')
+            .map(|subject_type| {
+                subject_type
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        subject_types.sort();
+        definition.relations.push(SchemaRelation {
+            name: name.trim().to_string(),
+            subject_types,
+        });
+    } else if let Some(rest) = statement.strip_prefix("permission ") {
+        let (name, expression) = rest
+            .split_once('=')
+            .ok_or_else(|| invalid("expected '=' after permission name"))?;
+        definition.permissions.push(SchemaPermission {
+            name: name.trim().to_string(),
+            expression: expression.split_whitespace().collect(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Blank out `//` and `/* */` comments, keeping line breaks so that line
+/// numbers in errors still match the source
+fn strip_comments(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        stripped.push('\n');
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    stripped
+}
@@ -0,0 +1,240 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, ValueEnum};
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+use tracing::debug;
+
+use crate::infrastructure::authzed::error::AuthzedError;
+
+/// Which certificates the SpiceDB server certificate is verified against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TlsMode {
+    /// No TLS, for a local SpiceDB
+    #[default]
+    Plaintext,
+    /// The operating system trust store, plus `AUTHZED_CA_CERT` when set
+    System,
+    /// Only the CA in `AUTHZED_CA_CERT`, e.g. for a self-signed certificate
+    CustomCa,
+}
+
+/// TLS settings of the SpiceDB connection
+#[derive(Debug, Clone, Default, Parser)]
+pub struct AuthZedTlsConfig {
+    #[arg(
+        long = "authzed-tls-mode",
+        env = "AUTHZED_TLS_MODE",
+        value_enum,
+        default_value_t = TlsMode::Plaintext
+    )]
+    pub mode: TlsMode,
+
+    /// PEM file of the CA that signed the SpiceDB certificate
+    #[arg(long = "authzed-ca-cert", env = "AUTHZED_CA_CERT")]
+    pub ca_cert_path: Option<PathBuf>,
+
+    /// PEM client certificate, for mutual TLS
+    #[arg(long = "authzed-client-cert", env = "AUTHZED_CLIENT_CERT")]
+    pub client_cert_path: Option<PathBuf>,
+
+    /// PEM private key of the client certificate, for mutual TLS
+    #[arg(long = "authzed-client-key", env = "AUTHZED_CLIENT_KEY")]
+    pub client_key_path: Option<PathBuf>,
+
+    /// Name to verify the server certificate against, when it differs from the
+    /// endpoint host (e.g. connecting through an IP or a tunnel)
+    #[arg(long = "authzed-tls-domain", env = "AUTHZED_TLS_DOMAIN")]
+    pub domain: Option<String>,
+}
+
+impl AuthZedTlsConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.mode != TlsMode::Plaintext
+    }
+
+    /// The tonic TLS settings, `None` in plaintext mode
+    pub async fn client_tls_config(&self) -> Result<Option<ClientTlsConfig>, AuthzedError> {
+        let mut tls = match self.mode {
+            TlsMode::Plaintext => {
+                let tls_option_set = self.ca_cert_path.is_some()
+                    || self.client_cert_path.is_some()
+                    || self.client_key_path.is_some()
+                    || self.domain.is_some();
+                if tls_option_set {
+                    return Err(tls_error(
+                        "TLS options are set but AUTHZED_TLS_MODE is plaintext",
+                    ));
+                }
+                return Ok(None);
+            }
+            TlsMode::System => ClientTlsConfig::new().with_native_roots(),
+            TlsMode::CustomCa => ClientTlsConfig::new(),
+        };
+
+        match &self.ca_cert_path {
+            Some(path) => {
+                debug!(path = %path.display(), "Trusting custom CA certificate");
+                tls = tls.ca_certificate(Certificate::from_pem(read_pem(path).await?));
+            }
+            None if self.mode == TlsMode::CustomCa => {
+                return Err(tls_error("custom-ca TLS mode requires AUTHZED_CA_CERT"));
+            }
+            None => {}
+        }
+
+        match (&self.client_cert_path, &self.client_key_path) {
+            (Some(cert_path), Some(key_path)) => {
+                debug!(path = %cert_path.display(), "Using client certificate");
+                let identity =
+                    Identity::from_pem(read_pem(cert_path).await?, read_pem(key_path).await?);
+                tls = tls.identity(identity);
+            }
+            (None, None) => {}
+            _ => {
+                return Err(tls_error(
+                    "AUTHZED_CLIENT_CERT and AUTHZED_CLIENT_KEY must be set together",
+                ));
+            }
+        }
+
+        if let Some(domain) = &self.domain {
+            tls = tls.domain_name(domain.clone());
+        }
+
+        Ok(Some(tls))
+    }
+}
+
+/// The endpoint as a URL, with a scheme matching the TLS setting
+pub fn endpoint_url(endpoint: &str, tls_enabled: bool) -> Result<String, AuthzedError> {
+    match (endpoint.split_once("://"), tls_enabled) {
+        (None, false) => Ok(format!("http://{}", endpoint)),
+        (None, true) => Ok(format!("https://{}", endpoint)),
+        (Some(("http", _)), false) | (Some(("https", _)), true) => Ok(endpoint.to_string()),
+        (Some(("https", _)), false) => Err(tls_error(
+            "https endpoint requires AUTHZED_TLS_MODE system or custom-ca",
+        )),
+        (Some((scheme, _)), _) => Err(tls_error(&format!(
+            "{} endpoint does not match AUTHZED_TLS_MODE",
+            scheme
+        ))),
+    }
+}
+
+async fn read_pem(path: &Path) -> Result<Vec<u8>, AuthzedError> {
+    tokio::fs::read(path)
+        .await
+        .map_err(|e| tls_error(&format!("Could not read {}: {}", path.display(), e)))
+}
+
+fn tls_error(msg: &str) -> AuthzedError {
+    AuthzedError::TlsConfigError {
+        msg: msg.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The content is not parsed until the connection is established
+    const PEM: &str = "-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----\n";
+
+    fn pem_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("authz_tls_{}_{}", std::process::id(), name));
+        std::fs::write(&path, PEM).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_has_no_tls() {
+        let config = AuthZedTlsConfig::default();
+
+        let tls = config.client_tls_config().await.unwrap();
+
+        assert!(tls.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_rejects_tls_options() {
+        let config = AuthZedTlsConfig {
+            domain: Some("spicedb.local".to_string()),
+            ..Default::default()
+        };
+
+        let result = config.client_tls_config().await;
+
+        assert!(matches!(result, Err(AuthzedError::TlsConfigError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_custom_ca_requires_ca_cert() {
+        let config = AuthZedTlsConfig {
+            mode: TlsMode::CustomCa,
+            ..Default::default()
+        };
+
+        let result = config.client_tls_config().await;
+
+        assert!(matches!(result, Err(AuthzedError::TlsConfigError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_custom_ca_with_mutual_tls() {
+        let config = AuthZedTlsConfig {
+            mode: TlsMode::CustomCa,
+            ca_cert_path: Some(pem_file("ca.pem")),
+            client_cert_path: Some(pem_file("client.pem")),
+            client_key_path: Some(pem_file("client.key")),
+            domain: Some("spicedb.local".to_string()),
+        };
+
+        let tls = config.client_tls_config().await.unwrap();
+
+        assert!(tls.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_client_cert_requires_key() {
+        let config = AuthZedTlsConfig {
+            mode: TlsMode::System,
+            client_cert_path: Some(pem_file("lonely.pem")),
+            ..Default::default()
+        };
+
+        let result = config.client_tls_config().await;
+
+        assert!(matches!(result, Err(AuthzedError::TlsConfigError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_missing_ca_file() {
+        let config = AuthZedTlsConfig {
+            mode: TlsMode::CustomCa,
+            ca_cert_path: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..Default::default()
+        };
+
+        let result = config.client_tls_config().await;
+
+        assert!(matches!(result, Err(AuthzedError::TlsConfigError { .. })));
+    }
+
+    #[test]
+    fn test_endpoint_url_scheme() {
+        assert_eq!(
+            endpoint_url("localhost:50051", false).unwrap(),
+            "http://localhost:50051"
+        );
+        assert_eq!(
+            endpoint_url("grpc.authzed.com:443", true).unwrap(),
+            "https://grpc.authzed.com:443"
+        );
+        assert_eq!(
+            endpoint_url("https://grpc.authzed.com:443", true).unwrap(),
+            "https://grpc.authzed.com:443"
+        );
+        assert!(endpoint_url("https://grpc.authzed.com:443", false).is_err());
+        assert!(endpoint_url("http://localhost:50051", true).is_err());
+    }
+}
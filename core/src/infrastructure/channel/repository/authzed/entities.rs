@@ -1,7 +1,10 @@
 use crate::{
     authzed::api::v1::{Relationship, RelationshipFilter},
-    domain::channel::entities::{CreateChannelInput, DeleteChannelInput},
-    infrastructure::common::authzed::entities::{Relation, channel::Channel, server::Server},
+    domain::channel::entities::{CreateChannelInput, DeleteChannelInput, ListServerChannelsInput},
+    infrastructure::common::authzed::{
+        beep::channel,
+        entities::{channel::Channel, relationship::Object, server::Server},
+    },
 };
 
 impl Into<(Channel, Server)> for CreateChannelInput {
@@ -15,23 +18,23 @@ impl Into<(Channel, Server)> for CreateChannelInput {
 impl From<CreateChannelInput> for Relationship {
     fn from(input: CreateChannelInput) -> Self {
         let (channel, server): (Channel, Server) = input.into();
-        Relationship {
-            resource: Some(channel.into()),
-            relation: Relation::Server.into(),
-            subject: Some(server.into()),
-            ..Default::default()
-        }
+        channel
+            .relationship(channel::Relation::Server, server)
+            .build()
     }
 }
 
 impl From<DeleteChannelInput> for RelationshipFilter {
     fn from(input: DeleteChannelInput) -> Self {
-        RelationshipFilter {
-            resource_type: "channel".to_string(),
-            optional_resource_id: input.channel_id,
-            optional_resource_id_prefix: String::new(),
-            optional_relation: String::new(),
-            optional_subject_filter: None,
-        }
+        Channel::from(input.channel_id).filter().build()
+    }
+}
+
+impl From<ListServerChannelsInput> for RelationshipFilter {
+    fn from(input: ListServerChannelsInput) -> Self {
+        Channel::filter_all()
+            .relation(channel::Relation::Server)
+            .subject(Server::from(input.server_id).subject_filter())
+            .build()
     }
 }
@@ -1,12 +1,15 @@
 use crate::{
-    domain::channel::{
-        ChannelError,
-        entities::{CreateChannelInput, DeleteChannelInput},
-        port::ChannelRepository,
+    domain::{
+        channel::{
+            ChannelError,
+            entities::{CreateChannelInput, DeleteChannelInput, ListServerChannelsInput},
+            port::ChannelRepository,
+        },
+        common::entities::{DeleteOutput, ZedToken},
     },
-    infrastructure::authzed::AuthZedClient,
+    infrastructure::authzed::{AuthZedClient, READ_PAGE_SIZE, error::AuthzedError},
 };
-use tracing::{info, instrument};
+use tracing::{error, info, instrument};
 pub mod entities;
 
 #[derive(Clone)]
@@ -20,9 +23,18 @@ impl AuthzedChannelRepository {
     }
 }
 
+/// `Unavailable` when SpiceDB could not answer for now, the error built by `other` otherwise
+fn channel_error(e: AuthzedError, other: impl FnOnce(String) -> ChannelError) -> ChannelError {
+    if e.is_unavailable() {
+        ChannelError::Unavailable { msg: e.to_string() }
+    } else {
+        other(e.to_string())
+    }
+}
+
 impl ChannelRepository for AuthzedChannelRepository {
     #[instrument(skip(self), fields(channel_id = %input.channel_id, server_id = %input.server_id))]
-    async fn create(&self, input: CreateChannelInput) -> Result<(), ChannelError> {
+    async fn create(&self, input: CreateChannelInput) -> Result<ZedToken, ChannelError> {
         info!(
             channel_id = %input.channel_id,
             server_id = %input.server_id,
@@ -33,7 +45,8 @@ impl ChannelRepository for AuthzedChannelRepository {
             .authzed_client
             .create_relationship(input)
             .await
-            .map_err(|e| ChannelError::CreateChannelError { msg: e.to_string() });
+            .map(ZedToken::from)
+            .map_err(|e| channel_error(e, |msg| ChannelError::CreateChannelError { msg }));
 
         match &result {
             Ok(_) => info!("Channel relationship created successfully in AuthZed"),
@@ -44,7 +57,7 @@ impl ChannelRepository for AuthzedChannelRepository {
     }
 
     #[instrument(skip(self), fields(channel_id = %input.channel_id))]
-    async fn delete(&self, input: DeleteChannelInput) -> Result<(), ChannelError> {
+    async fn delete(&self, input: DeleteChannelInput) -> Result<DeleteOutput, ChannelError> {
         info!(
             channel_id = %input.channel_id,
             "Deleting channel relationships in AuthZed"
@@ -54,11 +67,51 @@ impl ChannelRepository for AuthzedChannelRepository {
             .authzed_client
             .filtered_delete(input)
             .await
-            .map_err(|e| ChannelError::DeleteChannelError { msg: e.to_string() });
+            .map_err(|e| channel_error(e, |msg| ChannelError::DeleteChannelError { msg }));
+
+        match &result {
+            Ok(summary) => info!(
+                deleted_count = summary.deleted_count,
+                batches = summary.batches,
+                "Channel relationships deleted successfully in AuthZed"
+            ),
+            Err(e) => error!(error = ?e, "Failed to delete channel relationships in AuthZed"),
+        }
+
+        result.map(DeleteOutput::from)
+    }
+
+    #[instrument(skip(self), fields(server_id = %input.server_id))]
+    async fn list_by_server(
+        &self,
+        input: ListServerChannelsInput,
+    ) -> Result<Vec<String>, ChannelError> {
+        info!(
+            server_id = %input.server_id,
+            "Listing server channels in AuthZed"
+        );
+
+        let consistency = input.consistency.clone();
+        let mut channels: Vec<String> = Vec::new();
+        let result = self
+            .authzed_client
+            .for_each_relationship_page(input, READ_PAGE_SIZE, consistency, |page| {
+                channels.extend(
+                    page.into_iter()
+                        .filter_map(|relationship| relationship.resource)
+                        .map(|resource| resource.object_id),
+                )
+            })
+            .await
+            .map(|_| channels)
+            .map_err(|e| channel_error(e, |msg| ChannelError::ListChannelsError { msg }));
 
         match &result {
-            Ok(_) => info!("Channel relationships deleted successfully in AuthZed"),
-            Err(e) => info!(error = ?e, "Failed to delete channel relationships in AuthZed"),
+            Ok(channels) => info!(
+                channel_count = channels.len(),
+                "Server channels listed in AuthZed"
+            ),
+            Err(e) => info!(error = ?e, "Failed to list server channels in AuthZed"),
         }
 
         result
@@ -0,0 +1,68 @@
+//! Typed names of beep.zed, generated by core/build.rs: a `Definition` enum and,
+//! per definition, a module with its `NAME` and `Relation`/`Permission` enums.
+//! Renaming or removing something in beep.zed breaks the code that writes it.
+
+include!(concat!(env!("OUT_DIR"), "/beep_schema.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::authzed::schema::{BEEP_SCHEMA, Schema};
+
+    #[test]
+    fn test_generated_names_match_beep_schema() {
+        let schema = Schema::parse(BEEP_SCHEMA).unwrap();
+
+        let definitions: Vec<_> = Definition::ALL.iter().map(|d| d.as_str()).collect();
+        let expected: Vec<_> = schema.definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(definitions, expected);
+
+        let channel = schema.definition(channel::NAME).unwrap();
+        let relations: Vec<_> = channel::Relation::ALL.iter().map(|r| r.as_str()).collect();
+        let expected: Vec<_> = channel.relations.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(relations, expected);
+        let permissions: Vec<_> = channel::Permission::ALL
+            .iter()
+            .map(|p| p.as_str())
+            .collect();
+        let expected: Vec<_> = channel
+            .permissions
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(permissions, expected);
+    }
+
+    #[test]
+    fn test_generated_subject_types_match_beep_schema() {
+        let schema = Schema::parse(BEEP_SCHEMA).unwrap();
+        let channel = schema.definition(channel::NAME).unwrap();
+
+        for relation in channel::Relation::ALL {
+            assert_eq!(
+                relation.subject_types(),
+                channel.relation(relation.as_str()).unwrap().subject_types,
+                "{}",
+                relation
+            );
+        }
+        assert_eq!(
+            permission_override::Relation::GrantedTo.subject_types(),
+            ["role#member", "user"]
+        );
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        assert_eq!(
+            Definition::from_name("permission_override"),
+            Some(Definition::PermissionOverride)
+        );
+        assert_eq!(
+            server::Relation::from_name("invitation_creator"),
+            Some(server::Relation::InvitationCreator)
+        );
+        assert_eq!(String::from(role::Permission::Manage), "manage");
+        assert_eq!(user::Relation::from_name("owner"), None);
+    }
+}
@@ -1,13 +1,31 @@
-use crate::{authzed::api::v1::ObjectReference, infrastructure::common::authzed::entities::Id};
+use crate::{
+    authzed::api::v1::{ObjectReference, SubjectReference},
+    infrastructure::common::authzed::{
+        beep::channel,
+        entities::{Entity, Id, relationship::Object},
+    },
+};
 
 pub struct Channel(Id);
 
-impl Into<ObjectReference> for Channel {
-    fn into(self) -> ObjectReference {
-        ObjectReference {
-            object_type: "channel".to_string(),
-            object_id: self.0,
-        }
+impl Object for Channel {
+    const DEFINITION: Entity = Entity::Channel;
+    type Relation = channel::Relation;
+
+    fn id(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Channel> for ObjectReference {
+    fn from(channel: Channel) -> Self {
+        channel.reference()
+    }
+}
+
+impl From<Channel> for SubjectReference {
+    fn from(channel: Channel) -> Self {
+        channel.subject()
     }
 }
 
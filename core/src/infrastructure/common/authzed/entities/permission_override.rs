@@ -0,0 +1,36 @@
+use crate::{
+    authzed::api::v1::{ObjectReference, SubjectReference},
+    infrastructure::common::authzed::{
+        beep::permission_override,
+        entities::{Entity, Id, relationship::Object},
+    },
+};
+
+pub struct PermissionOverride(Id);
+
+impl Object for PermissionOverride {
+    const DEFINITION: Entity = Entity::PermissionOverride;
+    type Relation = permission_override::Relation;
+
+    fn id(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<PermissionOverride> for ObjectReference {
+    fn from(permission_override: PermissionOverride) -> Self {
+        permission_override.reference()
+    }
+}
+
+impl From<PermissionOverride> for SubjectReference {
+    fn from(permission_override: PermissionOverride) -> Self {
+        permission_override.subject()
+    }
+}
+
+impl From<String> for PermissionOverride {
+    fn from(id: String) -> Self {
+        PermissionOverride(Id::from(id))
+    }
+}
@@ -0,0 +1,245 @@
+use std::marker::PhantomData;
+
+use prost_types::{Struct, Timestamp};
+
+use crate::{
+    authzed::api::v1::{
+        ContextualizedCaveat, ObjectReference, Relationship, RelationshipFilter, SubjectFilter,
+        SubjectReference, subject_filter::RelationFilter,
+    },
+    infrastructure::common::authzed::entities::Entity,
+};
+
+/// An object of a beep.zed definition, e.g. `Role::from(id)`, from which the
+/// relationships and filters the repositories send are built
+pub trait Object: Sized {
+    const DEFINITION: Entity;
+    /// Relations declared on the definition
+    type Relation: Into<String>;
+
+    fn id(&self) -> &str;
+
+    fn reference(&self) -> ObjectReference {
+        ObjectReference {
+            object_type: Self::DEFINITION.into(),
+            object_id: self.id().to_string(),
+        }
+    }
+
+    /// The object itself as a subject, e.g. `user:user_1`
+    fn subject(&self) -> SubjectReference {
+        SubjectReference {
+            object: Some(self.reference()),
+            optional_relation: String::new(),
+        }
+    }
+
+    /// Every subject of one of its relations, e.g. `role:role_1#member`
+    fn subject_set(&self, relation: Self::Relation) -> SubjectReference {
+        SubjectReference {
+            object: Some(self.reference()),
+            optional_relation: relation.into(),
+        }
+    }
+
+    /// `self#relation@subject`
+    fn relationship(
+        &self,
+        relation: Self::Relation,
+        subject: impl Into<SubjectReference>,
+    ) -> RelationshipBuilder {
+        RelationshipBuilder {
+            relationship: Relationship {
+                resource: Some(self.reference()),
+                relation: relation.into(),
+                subject: Some(subject.into()),
+                optional_caveat: None,
+                optional_expires_at: None,
+            },
+        }
+    }
+
+    /// Relationships where this object is the resource
+    fn filter(&self) -> RelationshipFilterBuilder<Self> {
+        let mut builder = Self::filter_all();
+        builder.filter.optional_resource_id = self.id().to_string();
+        builder
+    }
+
+    /// Relationships where any object of the definition is the resource
+    fn filter_all() -> RelationshipFilterBuilder<Self> {
+        RelationshipFilterBuilder {
+            filter: RelationshipFilter {
+                resource_type: Self::DEFINITION.into(),
+                optional_resource_id: String::new(),
+                optional_resource_id_prefix: String::new(),
+                optional_relation: String::new(),
+                optional_subject_filter: None,
+            },
+            object: PhantomData,
+        }
+    }
+
+    /// Subjects that are this object, with or without a subject relation
+    fn subject_filter(&self) -> SubjectFilter {
+        SubjectFilter {
+            subject_type: Self::DEFINITION.into(),
+            optional_subject_id: self.id().to_string(),
+            optional_relation: None,
+        }
+    }
+
+    /// Subjects that are exactly `self#relation`
+    fn subject_set_filter(&self, relation: Self::Relation) -> SubjectFilter {
+        SubjectFilter {
+            optional_relation: Some(RelationFilter {
+                relation: relation.into(),
+            }),
+            ..self.subject_filter()
+        }
+    }
+}
+
+/// A relationship with optional caveat and expiration, from `Object::relationship`
+#[derive(Debug, Clone)]
+pub struct RelationshipBuilder {
+    relationship: Relationship,
+}
+
+impl RelationshipBuilder {
+    /// Only holds while the named caveat of the schema is satisfied
+    pub fn caveat(self, name: impl Into<String>) -> Self {
+        self.caveat_with_context(name, None)
+    }
+
+    /// Caveat with part of its parameters bound at write time
+    pub fn caveat_with_context(mut self, name: impl Into<String>, context: Option<Struct>) -> Self {
+        self.relationship.optional_caveat = Some(ContextualizedCaveat {
+            caveat_name: name.into(),
+            context,
+        });
+        self
+    }
+
+    /// SpiceDB drops the relationship once `expires_at` has passed
+    pub fn expires_at(mut self, expires_at: impl Into<Timestamp>) -> Self {
+        self.relationship.optional_expires_at = Some(expires_at.into());
+        self
+    }
+
+    pub fn build(self) -> Relationship {
+        self.relationship
+    }
+}
+
+impl From<RelationshipBuilder> for Relationship {
+    fn from(builder: RelationshipBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// A filter on the relationships of a definition, from `Object::filter` or `Object::filter_all`
+pub struct RelationshipFilterBuilder<O> {
+    filter: RelationshipFilter,
+    object: PhantomData<O>,
+}
+
+impl<O: Object> RelationshipFilterBuilder<O> {
+    pub fn relation(mut self, relation: O::Relation) -> Self {
+        self.filter.optional_relation = relation.into();
+        self
+    }
+
+    pub fn subject(mut self, subject: SubjectFilter) -> Self {
+        self.filter.optional_subject_filter = Some(subject);
+        self
+    }
+
+    pub fn build(self) -> RelationshipFilter {
+        self.filter
+    }
+}
+
+impl<O: Object> From<RelationshipFilterBuilder<O>> for RelationshipFilter {
+    fn from(builder: RelationshipFilterBuilder<O>) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{
+        authzed::backup::format_relationship,
+        common::authzed::{
+            beep::{permission_override, role, server},
+            entities::{
+                channel::Channel, permission_override::PermissionOverride, role::Role,
+                server::Server, user::User,
+            },
+        },
+    };
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_relationship_to_subject_set() {
+        let relationship = Server::from("server_1".to_string())
+            .relationship(
+                server::Relation::MessageSender,
+                Role::from("role_1".to_string()).members(),
+            )
+            .build();
+
+        assert_eq!(
+            format_relationship(&relationship).unwrap(),
+            "server:server_1#message_sender@role:role_1#member"
+        );
+    }
+
+    #[test]
+    fn test_relationship_with_caveat_and_expiration() {
+        let expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_893_456_000);
+
+        let relationship = Role::from("role_1".to_string())
+            .relationship(role::Relation::Member, User::from("user_1".to_string()))
+            .caveat("during_event")
+            .expires_at(expires_at)
+            .build();
+
+        assert_eq!(
+            format_relationship(&relationship).unwrap(),
+            "role:role_1#member@user:user_1[during_event][expiration:2030-01-01T00:00:00Z]"
+        );
+    }
+
+    #[test]
+    fn test_filter_on_subject_of_a_definition() {
+        let filter = Channel::filter_all()
+            .subject(PermissionOverride::from("override_1".to_string()).subject_filter())
+            .build();
+
+        assert_eq!(filter.resource_type, "channel");
+        assert_eq!(filter.optional_resource_id, "");
+        assert_eq!(filter.optional_relation, "");
+        let subject_filter = filter.optional_subject_filter.unwrap();
+        assert_eq!(subject_filter.subject_type, "permission_override");
+        assert_eq!(subject_filter.optional_subject_id, "override_1");
+        assert!(subject_filter.optional_relation.is_none());
+    }
+
+    #[test]
+    fn test_filter_on_relation_and_subject_set() {
+        let filter = PermissionOverride::from("override_1".to_string())
+            .filter()
+            .relation(permission_override::Relation::GrantedTo)
+            .subject(Role::from("role_1".to_string()).members_filter())
+            .build();
+
+        assert_eq!(filter.resource_type, "permission_override");
+        assert_eq!(filter.optional_resource_id, "override_1");
+        assert_eq!(filter.optional_relation, "granted_to");
+        let subject_filter = filter.optional_subject_filter.unwrap();
+        assert_eq!(subject_filter.subject_type, "role");
+        assert_eq!(subject_filter.optional_relation.unwrap().relation, "member");
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    authzed::api::v1::{ObjectReference, SubjectFilter, SubjectReference},
+    infrastructure::common::authzed::{
+        beep::role,
+        entities::{Entity, Id, relationship::Object},
+    },
+};
+
+pub struct Role(Id);
+
+impl Object for Role {
+    const DEFINITION: Entity = Entity::Role;
+    type Relation = role::Relation;
+
+    fn id(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Role {
+    /// `role:id#member`, the users holding the role
+    pub fn members(&self) -> SubjectReference {
+        self.subject_set(role::Relation::Member)
+    }
+
+    pub fn members_filter(&self) -> SubjectFilter {
+        self.subject_set_filter(role::Relation::Member)
+    }
+}
+
+impl From<Role> for ObjectReference {
+    fn from(role: Role) -> Self {
+        role.reference()
+    }
+}
+
+impl From<Role> for SubjectReference {
+    fn from(role: Role) -> Self {
+        role.subject()
+    }
+}
+
+impl From<String> for Role {
+    fn from(id: String) -> Self {
+        Role(Id::from(id))
+    }
+}
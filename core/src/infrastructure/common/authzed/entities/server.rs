@@ -1,25 +1,31 @@
 use crate::{
     authzed::api::v1::{ObjectReference, SubjectReference},
-    infrastructure::common::authzed::entities::Id,
+    infrastructure::common::authzed::{
+        beep::server,
+        entities::{Entity, Id, relationship::Object},
+    },
 };
 
 pub struct Server(Id);
 
-impl Into<ObjectReference> for Server {
-    fn into(self) -> ObjectReference {
-        ObjectReference {
-            object_type: "server".to_string(),
-            object_id: self.0,
-        }
+impl Object for Server {
+    const DEFINITION: Entity = Entity::Server;
+    type Relation = server::Relation;
+
+    fn id(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Server> for ObjectReference {
+    fn from(server: Server) -> Self {
+        server.reference()
     }
 }
 
-impl Into<SubjectReference> for Server {
-    fn into(self) -> SubjectReference {
-        SubjectReference {
-            object: Some(self.into()),
-            ..Default::default()
-        }
+impl From<Server> for SubjectReference {
+    fn from(server: Server) -> Self {
+        server.subject()
     }
 }
 
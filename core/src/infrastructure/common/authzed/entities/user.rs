@@ -1,30 +1,36 @@
 use crate::{
     authzed::api::v1::{ObjectReference, SubjectReference},
-    infrastructure::common::authzed::entities::{Entity, Id},
+    infrastructure::common::authzed::{
+        beep::user,
+        entities::{Entity, Id, relationship::Object},
+    },
 };
 
 pub struct User(Id);
 
-impl Into<ObjectReference> for User {
-    fn into(self) -> ObjectReference {
-        ObjectReference {
-            object_type: Entity::User.into(),
-            object_id: self.0,
-        }
+impl Object for User {
+    const DEFINITION: Entity = Entity::User;
+    type Relation = user::Relation;
+
+    fn id(&self) -> &str {
+        &self.0
     }
 }
 
-impl Into<SubjectReference> for User {
-    fn into(self) -> SubjectReference {
-        SubjectReference {
-            object: Some(self.into()),
-            ..Default::default()
-        }
+impl From<User> for ObjectReference {
+    fn from(user: User) -> Self {
+        user.reference()
+    }
+}
+
+impl From<User> for SubjectReference {
+    fn from(user: User) -> Self {
+        user.subject()
     }
 }
 
 impl From<String> for User {
-    fn from(value: String) -> Self {
-        User(Id::from(value))
+    fn from(id: String) -> Self {
+        User(Id::from(id))
     }
-}
\ No newline at end of file
+}
@@ -1 +1,2 @@
-pub mod entities;
\ No newline at end of file
+pub mod beep;
+pub mod entities;
@@ -1,2 +1,4 @@
 pub mod authzed;
 pub mod permissions;
+pub mod permissions_translations;
+pub mod schema_requirements;
@@ -1,5 +1,12 @@
 use permission_translation::models::CapabilityDescriptor;
 
+use crate::{
+    domain::permission::entities::{
+        ChannelPermission, Permission, RolePermission, ServerPermission,
+    },
+    infrastructure::common::authzed::beep::{channel, role, server},
+};
+
 /// Parse a permission bitmask and return the list of permission Display names
 pub fn parse_permission_bitmask(bitmask: u64, descriptor: &CapabilityDescriptor) -> Vec<String> {
     let mut permissions = Vec::new();
@@ -15,52 +22,230 @@ pub fn parse_permission_bitmask(bitmask: u64, descriptor: &CapabilityDescriptor)
     permissions
 }
 
-/// Convert a permission Display name to a server relation name
+/// Convert a permission Display name to the server relation storing it
 /// Returns None if the permission is not recognized
-pub fn permission_display_to_server_relation(display_name: &str) -> Option<&'static str> {
+pub fn permission_display_to_server_relation(display_name: &str) -> Option<server::Relation> {
     match display_name {
-        "admin" => Some("administrator"),
-        "manage" => Some("server_manager"),
-        "manage_role" => Some("role_manager"),
-        "create_invitation" => Some("invitation_creator"),
-        "manage_channels" => Some("channel_manager"),
-        "manage_webhooks" => Some("webhook_manager"),
-        "view_channel" => Some("channel_viewer"),
-        "send_message" => Some("message_sender"),
-        "manage_nicknames" => Some("nickname_manager"),
-        "change_nickname" => Some("nickname_changer"),
-        "manage_message" => Some("message_manager"),
-        "attach_files" => Some("file_attacher"),
+        "admin" => Some(server::Relation::Administrator),
+        "manage" => Some(server::Relation::ServerManager),
+        "manage_role" => Some(server::Relation::RoleManager),
+        "create_invitation" => Some(server::Relation::InvitationCreator),
+        "manage_channels" => Some(server::Relation::ChannelManager),
+        "manage_webhooks" => Some(server::Relation::WebhookManager),
+        "view_channel" => Some(server::Relation::ChannelViewer),
+        "send_message" => Some(server::Relation::MessageSender),
+        "manage_nicknames" => Some(server::Relation::NicknameManager),
+        "change_nickname" => Some(server::Relation::NicknameChanger),
+        "manage_message" => Some(server::Relation::MessageManager),
+        "attach_files" => Some(server::Relation::FileAttacher),
         _ => None,
     }
 }
 
 /// Check if a permission is a channel-level permission
 pub fn is_channel_permission(display_name: &str) -> bool {
-    matches!(
-        display_name,
-        "send_message" | "view_channel" | "manage_message" | "attach_files" | "manage_webhooks"
-    )
+    channel_grant_and_deny_relations(display_name).is_some()
 }
 
-/// Convert a permission Display name to a channel relation name with grant/deny suffix
+/// Convert a permission Display name to the channel grant or deny relation storing it
 /// Returns None if the permission is not a valid channel permission
 pub fn permission_display_to_channel_relation(
     display_name: &str,
     is_grant: bool,
-) -> Option<String> {
-    let suffix = if is_grant { "_grant" } else { "_deny" };
-
-    let base_relation = match display_name {
-        "send_message" => Some("send_message"),
-        "view_channel" => Some("view_channel"),
-        "manage_message" => Some("manage_message"),
-        "attach_files" => Some("attach_files"),
-        "manage_webhooks" => Some("manage_webhooks"),
+) -> Option<channel::Relation> {
+    channel_grant_and_deny_relations(display_name)
+        .map(|(grant, deny)| if is_grant { grant } else { deny })
+}
+
+fn channel_grant_and_deny_relations(
+    display_name: &str,
+) -> Option<(channel::Relation, channel::Relation)> {
+    use channel::Relation::*;
+
+    match display_name {
+        "send_message" => Some((SendMessageGrant, SendMessageDeny)),
+        "view_channel" => Some((ViewChannelGrant, ViewChannelDeny)),
+        "manage_message" => Some((ManageMessageGrant, ManageMessageDeny)),
+        "attach_files" => Some((AttachFilesGrant, AttachFilesDeny)),
+        "manage_webhooks" => Some((ManageWebhooksGrant, ManageWebhooksDeny)),
+        _ => None,
+    }
+}
+
+/// Convert a permission Display name to the server permission that checks it
+/// Returns None if the permission is not recognized
+pub fn permission_display_to_server_permission(display_name: &str) -> Option<ServerPermission> {
+    match display_name {
+        "admin" => Some(ServerPermission::Admin),
+        "manage" => Some(ServerPermission::Manage),
+        "manage_role" => Some(ServerPermission::ManageRole),
+        "create_invitation" => Some(ServerPermission::CreateInvitation),
+        "manage_channels" => Some(ServerPermission::ManageChannels),
+        "manage_webhooks" => Some(ServerPermission::ManageWebhooks),
+        "view_channel" => Some(ServerPermission::ViewChannel),
+        "send_message" => Some(ServerPermission::SendMessage),
+        "manage_nicknames" => Some(ServerPermission::ManageNicknames),
+        "change_nickname" => Some(ServerPermission::ChangeNickname),
+        "manage_message" => Some(ServerPermission::ManageMessage),
+        "attach_files" => Some(ServerPermission::AttachFiles),
         _ => None,
-    };
+    }
+}
+
+/// Convert a permission Display name to the channel permission that checks it,
+/// with server grants and channel overrides applied
+/// Returns None if the permission is not a valid channel permission
+pub fn permission_display_to_channel_permission(display_name: &str) -> Option<ChannelPermission> {
+    match display_name {
+        "send_message" => Some(ChannelPermission::SendMessage),
+        "view_channel" => Some(ChannelPermission::View),
+        "manage_message" => Some(ChannelPermission::ManageMessage),
+        "attach_files" => Some(ChannelPermission::AttachFiles),
+        "manage_webhooks" => Some(ChannelPermission::ManageWebhooks),
+        _ => None,
+    }
+}
+
+/// Convert a permission Display name to the role permission that checks it,
+/// with role-level grants and denies applied
+/// Returns None if the permission is not a valid role permission
+pub fn permission_display_to_role_permission(display_name: &str) -> Option<RolePermission> {
+    match display_name {
+        "manage_role" => Some(RolePermission::Manage),
+        _ => None,
+    }
+}
+
+impl From<ServerPermission> for server::Permission {
+    fn from(permission: ServerPermission) -> Self {
+        match permission {
+            ServerPermission::Admin => server::Permission::Admin,
+            ServerPermission::Manage => server::Permission::Manage,
+            ServerPermission::View => server::Permission::View,
+            ServerPermission::ManageRole => server::Permission::ManageRole,
+            ServerPermission::ViewRole => server::Permission::ViewRole,
+            ServerPermission::CreateInvitation => server::Permission::CreateInvitation,
+            ServerPermission::ManageChannels => server::Permission::ManageChannels,
+            ServerPermission::ManageWebhooks => server::Permission::ManageWebhooks,
+            ServerPermission::ViewChannel => server::Permission::ViewChannel,
+            ServerPermission::SendMessage => server::Permission::SendMessage,
+            ServerPermission::ManageNicknames => server::Permission::ManageNicknames,
+            ServerPermission::ChangeNickname => server::Permission::ChangeNickname,
+            ServerPermission::ManageMessage => server::Permission::ManageMessage,
+            ServerPermission::AttachFiles => server::Permission::AttachFiles,
+        }
+    }
+}
+
+impl From<ChannelPermission> for channel::Permission {
+    fn from(permission: ChannelPermission) -> Self {
+        match permission {
+            ChannelPermission::View => channel::Permission::View,
+            ChannelPermission::SendMessage => channel::Permission::SendMessage,
+            ChannelPermission::ManageMessage => channel::Permission::ManageMessage,
+            ChannelPermission::AttachFiles => channel::Permission::AttachFiles,
+            ChannelPermission::ManageWebhooks => channel::Permission::ManageWebhooks,
+        }
+    }
+}
+
+impl From<RolePermission> for role::Permission {
+    fn from(permission: RolePermission) -> Self {
+        match permission {
+            RolePermission::Manage => role::Permission::Manage,
+            RolePermission::View => role::Permission::View,
+        }
+    }
+}
 
-    base_relation.map(|base| format!("{}{}", base, suffix))
+/// The definition declaring a permission and its name there, as written in beep.zed
+pub fn schema_permission(permission: Permission) -> (&'static str, &'static str) {
+    match permission {
+        Permission::Server(permission) => {
+            (server::NAME, server::Permission::from(permission).as_str())
+        }
+        Permission::Channel(permission) => (
+            channel::NAME,
+            channel::Permission::from(permission).as_str(),
+        ),
+        Permission::Role(permission) => (role::NAME, role::Permission::from(permission).as_str()),
+    }
+}
+
+/// Convert a server relation name back to its permission Display name
+/// Returns None if the relation does not carry a permission (e.g. "owner")
+pub fn server_relation_to_permission_display(relation: &str) -> Option<&'static str> {
+    use server::Relation::*;
+
+    match server::Relation::from_name(relation)? {
+        Administrator => Some("admin"),
+        ServerManager => Some("manage"),
+        RoleManager => Some("manage_role"),
+        InvitationCreator => Some("create_invitation"),
+        ChannelManager => Some("manage_channels"),
+        WebhookManager => Some("manage_webhooks"),
+        ChannelViewer => Some("view_channel"),
+        MessageSender => Some("send_message"),
+        NicknameManager => Some("manage_nicknames"),
+        NicknameChanger => Some("change_nickname"),
+        MessageManager => Some("manage_message"),
+        FileAttacher => Some("attach_files"),
+        // No descriptor permission is stored in these yet
+        Owner | RoleViewer | ServerViewer => None,
+    }
+}
+
+/// Convert a channel grant/deny relation name back to its permission Display name
+/// Returns None if the relation is not a channel grant or deny
+pub fn channel_relation_to_permission_display(relation: &str) -> Option<&'static str> {
+    use channel::Relation::*;
+
+    match channel::Relation::from_name(relation)? {
+        SendMessageGrant | SendMessageDeny => Some("send_message"),
+        ViewChannelGrant | ViewChannelDeny => Some("view_channel"),
+        ManageMessageGrant | ManageMessageDeny => Some("manage_message"),
+        AttachFilesGrant | AttachFilesDeny => Some("attach_files"),
+        ManageWebhooksGrant | ManageWebhooksDeny => Some("manage_webhooks"),
+        Server => None,
+    }
+}
+
+/// Whether a channel relation grants (`true`) or denies (`false`) its permission
+/// Returns None if the relation is not a channel grant or deny
+pub fn channel_relation_is_allow(relation: channel::Relation) -> Option<bool> {
+    use channel::Relation::*;
+
+    match relation {
+        SendMessageGrant | ViewChannelGrant | ManageMessageGrant | AttachFilesGrant
+        | ManageWebhooksGrant => Some(true),
+        SendMessageDeny | ViewChannelDeny | ManageMessageDeny | AttachFilesDeny
+        | ManageWebhooksDeny => Some(false),
+        Server => None,
+    }
+}
+
+/// Convert a role grant/deny relation back to its permission Display name
+/// Returns None if the relation is not a role grant or deny
+pub fn role_relation_to_permission_display(relation: role::Relation) -> Option<&'static str> {
+    use role::Relation::*;
+
+    match relation {
+        ManageRoleGrant | ManageRoleDeny => Some("manage_role"),
+        ViewRoleGrant | ViewRoleDeny => Some("view_role"),
+        Server | Member => None,
+    }
+}
+
+/// Whether a role relation grants (`true`) or denies (`false`) its permission
+/// Returns None if the relation is not a role grant or deny
+pub fn role_relation_is_allow(relation: role::Relation) -> Option<bool> {
+    use role::Relation::*;
+
+    match relation {
+        ManageRoleGrant | ViewRoleGrant => Some(true),
+        ManageRoleDeny | ViewRoleDeny => Some(false),
+        Server | Member => None,
+    }
 }
 
 #[cfg(test)]
@@ -113,51 +298,51 @@ mod tests {
     fn test_permission_to_server_relation_mapping() {
         assert_eq!(
             permission_display_to_server_relation("admin"),
-            Some("administrator")
+            Some(server::Relation::Administrator)
         );
         assert_eq!(
             permission_display_to_server_relation("manage"),
-            Some("server_manager")
+            Some(server::Relation::ServerManager)
         );
         assert_eq!(
             permission_display_to_server_relation("manage_role"),
-            Some("role_manager")
+            Some(server::Relation::RoleManager)
         );
         assert_eq!(
             permission_display_to_server_relation("create_invitation"),
-            Some("invitation_creator")
+            Some(server::Relation::InvitationCreator)
         );
         assert_eq!(
             permission_display_to_server_relation("manage_channels"),
-            Some("channel_manager")
+            Some(server::Relation::ChannelManager)
         );
         assert_eq!(
             permission_display_to_server_relation("manage_webhooks"),
-            Some("webhook_manager")
+            Some(server::Relation::WebhookManager)
         );
         assert_eq!(
             permission_display_to_server_relation("view_channel"),
-            Some("channel_viewer")
+            Some(server::Relation::ChannelViewer)
         );
         assert_eq!(
             permission_display_to_server_relation("send_message"),
-            Some("message_sender")
+            Some(server::Relation::MessageSender)
         );
         assert_eq!(
             permission_display_to_server_relation("manage_nicknames"),
-            Some("nickname_manager")
+            Some(server::Relation::NicknameManager)
         );
         assert_eq!(
             permission_display_to_server_relation("change_nickname"),
-            Some("nickname_changer")
+            Some(server::Relation::NicknameChanger)
         );
         assert_eq!(
             permission_display_to_server_relation("manage_message"),
-            Some("message_manager")
+            Some(server::Relation::MessageManager)
         );
         assert_eq!(
             permission_display_to_server_relation("attach_files"),
-            Some("file_attacher")
+            Some(server::Relation::FileAttacher)
         );
         assert_eq!(permission_display_to_server_relation("unknown"), None);
     }
@@ -180,23 +365,23 @@ mod tests {
     fn test_permission_to_channel_relation_grant() {
         assert_eq!(
             permission_display_to_channel_relation("send_message", true),
-            Some("send_message_grant".to_string())
+            Some(channel::Relation::SendMessageGrant)
         );
         assert_eq!(
             permission_display_to_channel_relation("view_channel", true),
-            Some("view_channel_grant".to_string())
+            Some(channel::Relation::ViewChannelGrant)
         );
         assert_eq!(
             permission_display_to_channel_relation("manage_message", true),
-            Some("manage_message_grant".to_string())
+            Some(channel::Relation::ManageMessageGrant)
         );
         assert_eq!(
             permission_display_to_channel_relation("attach_files", true),
-            Some("attach_files_grant".to_string())
+            Some(channel::Relation::AttachFilesGrant)
         );
         assert_eq!(
             permission_display_to_channel_relation("manage_webhooks", true),
-            Some("manage_webhooks_grant".to_string())
+            Some(channel::Relation::ManageWebhooksGrant)
         );
     }
 
@@ -204,11 +389,11 @@ mod tests {
     fn test_permission_to_channel_relation_deny() {
         assert_eq!(
             permission_display_to_channel_relation("send_message", false),
-            Some("send_message_deny".to_string())
+            Some(channel::Relation::SendMessageDeny)
         );
         assert_eq!(
             permission_display_to_channel_relation("view_channel", false),
-            Some("view_channel_deny".to_string())
+            Some(channel::Relation::ViewChannelDeny)
         );
     }
 
@@ -220,4 +405,111 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn test_permission_to_role_permission() {
+        assert_eq!(
+            permission_display_to_role_permission("manage_role"),
+            Some(RolePermission::Manage)
+        );
+        assert_eq!(permission_display_to_role_permission("send_message"), None);
+    }
+
+    #[test]
+    fn test_channel_relation_is_allow_matches_its_side() {
+        for &relation in channel::Relation::ALL {
+            let Some(display_name) = channel_relation_to_permission_display(relation.as_str())
+            else {
+                assert_eq!(channel_relation_is_allow(relation), None);
+                continue;
+            };
+            let is_allow = channel_relation_is_allow(relation).unwrap();
+            assert_eq!(
+                permission_display_to_channel_relation(display_name, is_allow),
+                Some(relation)
+            );
+        }
+    }
+
+    #[test]
+    fn test_role_relation_grant_and_deny() {
+        assert_eq!(
+            role_relation_to_permission_display(role::Relation::ManageRoleDeny),
+            Some("manage_role")
+        );
+        assert_eq!(
+            role_relation_is_allow(role::Relation::ViewRoleGrant),
+            Some(true)
+        );
+        assert_eq!(
+            role_relation_is_allow(role::Relation::ManageRoleDeny),
+            Some(false)
+        );
+        assert_eq!(
+            role_relation_to_permission_display(role::Relation::Member),
+            None
+        );
+        assert_eq!(role_relation_is_allow(role::Relation::Server), None);
+    }
+
+    #[test]
+    fn test_server_relation_to_permission_round_trip() {
+        let descriptor = create_test_descriptor();
+        for name in descriptor.keys() {
+            let relation = permission_display_to_server_relation(name).unwrap();
+            assert_eq!(
+                server_relation_to_permission_display(relation.as_str()),
+                Some(name.as_str())
+            );
+        }
+        assert_eq!(server_relation_to_permission_display("owner"), None);
+    }
+
+    #[test]
+    fn test_channel_relation_to_permission() {
+        assert_eq!(
+            channel_relation_to_permission_display("send_message_grant"),
+            Some("send_message")
+        );
+        assert_eq!(
+            channel_relation_to_permission_display("view_channel_deny"),
+            Some("view_channel")
+        );
+        assert_eq!(channel_relation_to_permission_display("server"), None);
+        assert_eq!(channel_relation_to_permission_display("admin_grant"), None);
+    }
+
+    #[test]
+    fn test_permission_to_server_permission_covers_descriptor() {
+        let descriptor = create_test_descriptor();
+        for name in descriptor.keys() {
+            let permission = permission_display_to_server_permission(name).unwrap();
+            assert_eq!(
+                schema_permission(permission.into()),
+                (server::NAME, name.as_str())
+            );
+        }
+        assert_eq!(permission_display_to_server_permission("unknown"), None);
+    }
+
+    #[test]
+    fn test_permission_to_channel_permission() {
+        assert_eq!(
+            permission_display_to_channel_permission("view_channel"),
+            Some(ChannelPermission::View)
+        );
+        assert_eq!(
+            permission_display_to_channel_permission("send_message"),
+            Some(ChannelPermission::SendMessage)
+        );
+        assert_eq!(permission_display_to_channel_permission("admin"), None);
+
+        let descriptor = create_test_descriptor();
+        for name in descriptor.keys() {
+            assert_eq!(
+                permission_display_to_channel_permission(name).is_some(),
+                is_channel_permission(name)
+            );
+        }
+    }
 }
@@ -18,9 +18,9 @@ pub enum Permissions {
     AttachFiles,      // Can upload images and files.
 }
 
-impl Into<CapilityHexValue> for Permissions {
-    fn into(self) -> CapilityHexValue {
-        match self {
+impl From<Permissions> for CapilityHexValue {
+    fn from(permission: Permissions) -> Self {
+        match permission {
             Permissions::Administrator => 0x1,
             Permissions::ManageServer => 0x2,
             Permissions::ManageRoles => 0x4,
@@ -57,6 +57,13 @@ impl Display for Permissions {
 }
 
 pub struct BeepPermissions(Arc<CapabilityDescriptor>);
+
+impl Default for BeepPermissions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BeepPermissions {
     pub fn new() -> Self {
         let mut descriptor = CapabilityDescriptor::new();
@@ -0,0 +1,347 @@
+use std::fmt;
+
+use permission_translation::models::CapabilityDescriptor;
+
+use crate::{
+    domain::permission::entities::Permission,
+    infrastructure::{
+        authzed::schema::Schema,
+        common::{
+            authzed::beep::{channel, permission_override, role, server},
+            permissions::{
+                is_channel_permission, permission_display_to_channel_permission,
+                permission_display_to_channel_relation, permission_display_to_role_permission,
+                permission_display_to_server_permission, permission_display_to_server_relation,
+                schema_permission,
+            },
+        },
+    },
+};
+
+/// A relationship shape the repositories write: `definition#relation@subject_type`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredRelation {
+    pub definition: String,
+    pub relation: String,
+    /// Subject as written in a schema, e.g. "user" or "role#member"
+    pub subject_type: String,
+}
+
+impl RequiredRelation {
+    fn new(definition: &str, relation: &str, subject_type: &str) -> Self {
+        Self {
+            definition: definition.to_string(),
+            relation: relation.to_string(),
+            subject_type: subject_type.to_string(),
+        }
+    }
+}
+
+/// A permission the repositories check or look up: `definition#permission`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredPermission {
+    pub definition: String,
+    pub permission: String,
+}
+
+/// A mapping the live schema cannot honour
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingViolation {
+    /// A descriptor permission with no server relation to store it in
+    UnmappedPermission {
+        permission: String,
+    },
+    MissingDefinition {
+        definition: String,
+    },
+    MissingRelation {
+        relation: RequiredRelation,
+    },
+    SubjectTypeNotAllowed {
+        relation: RequiredRelation,
+        allowed: Vec<String>,
+    },
+    MissingPermission {
+        permission: RequiredPermission,
+    },
+}
+
+impl fmt::Display for MappingViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingViolation::UnmappedPermission { permission } => {
+                write!(
+                    f,
+                    "permission {} has no server relation mapping",
+                    permission
+                )
+            }
+            MappingViolation::MissingDefinition { definition } => {
+                write!(f, "definition {} does not exist", definition)
+            }
+            MappingViolation::MissingRelation { relation } => write!(
+                f,
+                "relation {}#{} does not exist",
+                relation.definition, relation.relation
+            ),
+            MappingViolation::SubjectTypeNotAllowed { relation, allowed } => write!(
+                f,
+                "relation {}#{} does not allow {} (allows {})",
+                relation.definition,
+                relation.relation,
+                relation.subject_type,
+                allowed.join(" | ")
+            ),
+            MappingViolation::MissingPermission { permission } => write!(
+                f,
+                "permission {}#{} does not exist",
+                permission.definition, permission.permission
+            ),
+        }
+    }
+}
+
+/// Every relationship shape the repositories can write for the given descriptor
+pub fn required_relations(descriptor: &CapabilityDescriptor) -> Vec<RequiredRelation> {
+    // Written whatever the descriptor, with every subject type beep.zed allows them
+    let structural: Vec<(&str, &str, &[&str])> = [
+        (
+            server::NAME,
+            server::Relation::Owner.as_str(),
+            server::Relation::Owner.subject_types(),
+        ),
+        (
+            role::NAME,
+            role::Relation::Server.as_str(),
+            role::Relation::Server.subject_types(),
+        ),
+        (
+            role::NAME,
+            role::Relation::Member.as_str(),
+            role::Relation::Member.subject_types(),
+        ),
+        (
+            channel::NAME,
+            channel::Relation::Server.as_str(),
+            channel::Relation::Server.subject_types(),
+        ),
+    ]
+    .into_iter()
+    .chain(permission_override::Relation::ALL.iter().map(|relation| {
+        (
+            permission_override::NAME,
+            relation.as_str(),
+            relation.subject_types(),
+        )
+    }))
+    .collect();
+    let mut relations: Vec<RequiredRelation> = structural
+        .into_iter()
+        .flat_map(|(definition, relation, subject_types)| {
+            subject_types
+                .iter()
+                .map(move |subject_type| RequiredRelation::new(definition, relation, subject_type))
+        })
+        .collect();
+
+    let role_members = format!("{}#{}", role::NAME, role::Relation::Member);
+    for name in sorted_names(descriptor) {
+        if let Some(relation) = permission_display_to_server_relation(name) {
+            relations.push(RequiredRelation::new(
+                server::NAME,
+                relation.as_str(),
+                &role_members,
+            ));
+        }
+        if is_channel_permission(name) {
+            for (is_grant, override_relation) in [
+                (true, permission_override::Relation::GrantedTo),
+                (false, permission_override::Relation::DeniedTo),
+            ] {
+                if let Some(relation) = permission_display_to_channel_relation(name, is_grant) {
+                    relations.push(RequiredRelation::new(
+                        channel::NAME,
+                        relation.as_str(),
+                        &format!("{}#{}", permission_override::NAME, override_relation),
+                    ));
+                }
+            }
+        }
+    }
+
+    relations
+}
+
+/// Permission Display name to the permission checking it
+type PermissionMapping = fn(&str) -> Option<Permission>;
+
+/// Every permission the repositories can check for the given descriptor
+pub fn required_permissions(descriptor: &CapabilityDescriptor) -> Vec<RequiredPermission> {
+    let mappings: [PermissionMapping; 3] = [
+        |name| permission_display_to_server_permission(name).map(Permission::from),
+        |name| permission_display_to_channel_permission(name).map(Permission::from),
+        |name| permission_display_to_role_permission(name).map(Permission::from),
+    ];
+
+    let mut permissions = Vec::new();
+    for name in sorted_names(descriptor) {
+        for mapping in mappings {
+            if let Some(permission) = mapping(name) {
+                let (definition, permission) = schema_permission(permission);
+                permissions.push(RequiredPermission {
+                    definition: definition.to_string(),
+                    permission: permission.to_string(),
+                });
+            }
+        }
+    }
+
+    permissions
+}
+
+/// Check that `schema` accepts every relationship the repositories write and
+/// declares every permission they check
+pub fn validate(schema: &Schema, descriptor: &CapabilityDescriptor) -> Vec<MappingViolation> {
+    let mut violations: Vec<MappingViolation> = sorted_names(descriptor)
+        .into_iter()
+        .filter(|name| permission_display_to_server_relation(name).is_none())
+        .map(|name| MappingViolation::UnmappedPermission {
+            permission: name.to_string(),
+        })
+        .collect();
+
+    for required in required_relations(descriptor) {
+        let Some(definition) = schema.definition(&required.definition) else {
+            violations.push(MappingViolation::MissingDefinition {
+                definition: required.definition,
+            });
+            continue;
+        };
+        match definition.relation(&required.relation) {
+            None => violations.push(MappingViolation::MissingRelation { relation: required }),
+            Some(relation) if !relation.allows(&required.subject_type) => {
+                violations.push(MappingViolation::SubjectTypeNotAllowed {
+                    relation: required,
+                    allowed: relation.subject_types.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for required in required_permissions(descriptor) {
+        let Some(definition) = schema.definition(&required.definition) else {
+            violations.push(MappingViolation::MissingDefinition {
+                definition: required.definition,
+            });
+            continue;
+        };
+        if definition.permission(&required.permission).is_none() {
+            violations.push(MappingViolation::MissingPermission {
+                permission: required,
+            });
+        }
+    }
+
+    violations.dedup();
+    violations
+}
+
+fn sorted_names(descriptor: &CapabilityDescriptor) -> Vec<&str> {
+    let mut names: Vec<&str> = descriptor.keys().map(String::as_str).collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{
+        authzed::schema::BEEP_SCHEMA, common::permissions_translations::BeepPermissions,
+    };
+
+    #[test]
+    fn test_beep_schema_satisfies_every_mapping() {
+        let schema = Schema::parse(BEEP_SCHEMA).unwrap();
+        let descriptor = BeepPermissions::new().descriptor();
+
+        let violations = validate(&schema, &descriptor);
+
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    fn violations(schema_text: &str, descriptor: &CapabilityDescriptor) -> Vec<String> {
+        let schema = Schema::parse(schema_text).unwrap();
+        validate(&schema, descriptor)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_reports_missing_relation() {
+        let schema_text = BEEP_SCHEMA.replace(
+            "relation file_attacher: role#member",
+            "relation file_atacher: role#member",
+        );
+
+        let violations = violations(&schema_text, &BeepPermissions::new().descriptor());
+
+        assert_eq!(
+            violations,
+            vec!["relation server#file_attacher does not exist"]
+        );
+    }
+
+    #[test]
+    fn test_required_relations_cover_every_override_target() {
+        let relations = required_relations(&CapabilityDescriptor::new());
+
+        let granted_to: Vec<_> = relations
+            .iter()
+            .filter(|r| r.definition == "permission_override" && r.relation == "granted_to")
+            .map(|r| r.subject_type.as_str())
+            .collect();
+        assert_eq!(granted_to, vec!["role#member", "user"]);
+        assert!(relations.contains(&RequiredRelation::new("server", "owner", "user")));
+    }
+
+    #[test]
+    fn test_validate_reports_subject_type_not_allowed() {
+        let schema_text =
+            BEEP_SCHEMA.replace("relation member: user", "relation member: role#member");
+
+        let violations = violations(&schema_text, &BeepPermissions::new().descriptor());
+
+        assert_eq!(
+            violations,
+            vec!["relation role#member does not allow user (allows role#member)"]
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_missing_permission() {
+        let schema_text = BEEP_SCHEMA.replace(
+            "permission view = (server->view_channel",
+            "permission view_channel = (server->view_channel",
+        );
+
+        let violations = violations(&schema_text, &BeepPermissions::new().descriptor());
+
+        assert_eq!(violations, vec!["permission channel#view does not exist"]);
+    }
+
+    #[test]
+    fn test_validate_reports_unmapped_descriptor_permission() {
+        let mut descriptor = CapabilityDescriptor::new();
+        descriptor.insert("send_message".to_string(), 0x1);
+        descriptor.insert("view_role".to_string(), 0x2);
+
+        let violations = violations(BEEP_SCHEMA, &descriptor);
+
+        assert_eq!(
+            violations,
+            vec!["permission view_role has no server relation mapping"]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub mod authzed;
 pub mod channel;
 pub mod common;
+pub mod permission;
+pub mod permission_change;
 pub mod permission_override;
 pub mod role;
 pub mod server;
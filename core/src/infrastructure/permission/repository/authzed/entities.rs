@@ -0,0 +1,867 @@
+use crate::{
+    authzed::api::v1::{
+        CheckBulkPermissionsPair, CheckBulkPermissionsRequestItem, CheckDebugTrace,
+        CheckPermissionResponse, LookupPermissionship, LookupSubjectsResponse, ObjectReference,
+        PartialCaveatInfo, RelationshipFilter, check_bulk_permissions_pair, check_debug_trace,
+        check_permission_response::Permissionship,
+    },
+    domain::{
+        common::entities::Consistency,
+        permission::{
+            PermissionError,
+            entities::{
+                CheckPermissionInput, DecidingElement, ExplanationStep, GrantSubject,
+                PermissionCheckResult, PermissionExplanation, Resource, ResourceType,
+                SubjectsLookup, WildcardGrant,
+            },
+        },
+    },
+    infrastructure::common::{
+        authzed::{
+            beep::{Definition, channel, role, server},
+            entities::{
+                Entity, channel::Channel, relationship::Object, role::Role, server::Server,
+                user::User,
+            },
+        },
+        permissions::{
+            channel_relation_is_allow, channel_relation_to_permission_display,
+            is_channel_permission, permission_display_to_channel_permission,
+            permission_display_to_server_permission, role_relation_is_allow, schema_permission,
+            server_relation_to_permission_display,
+        },
+    },
+};
+use permission_translation::models::CapabilityDescriptor;
+use tracing::warn;
+
+impl From<Resource> for ObjectReference {
+    fn from(resource: Resource) -> Self {
+        match resource {
+            Resource::Server(id) => Server::from(id).into(),
+            Resource::Channel(id) => Channel::from(id).into(),
+            Resource::Role(id) => Role::from(id).into(),
+        }
+    }
+}
+
+impl From<ResourceType> for Entity {
+    fn from(resource_type: ResourceType) -> Self {
+        match resource_type {
+            ResourceType::Server => Entity::Server,
+            ResourceType::Channel => Entity::Channel,
+            ResourceType::Role => Entity::Role,
+        }
+    }
+}
+
+impl From<CheckPermissionInput> for CheckBulkPermissionsRequestItem {
+    fn from(input: CheckPermissionInput) -> Self {
+        CheckBulkPermissionsRequestItem {
+            resource: Some(input.resource.into()),
+            permission: schema_permission(input.permission).1.to_string(),
+            subject: Some(User::from(input.user_id).into()),
+            context: None,
+        }
+    }
+}
+
+/// Map a SpiceDB permissionship (and its caveat info) to the domain outcome
+fn permissionship_to_result(
+    permissionship: Permissionship,
+    partial_caveat_info: Option<PartialCaveatInfo>,
+) -> Result<PermissionCheckResult, PermissionError> {
+    match permissionship {
+        Permissionship::HasPermission => Ok(PermissionCheckResult::Allowed),
+        Permissionship::NoPermission => Ok(PermissionCheckResult::Denied),
+        Permissionship::ConditionalPermission => Ok(PermissionCheckResult::Conditional {
+            missing_context: partial_caveat_info
+                .map(|info| info.missing_required_context)
+                .unwrap_or_default(),
+        }),
+        Permissionship::Unspecified => Err(PermissionError::CheckPermissionError {
+            msg: "SpiceDB returned an unspecified permissionship".to_string(),
+        }),
+    }
+}
+
+impl TryFrom<CheckPermissionResponse> for PermissionCheckResult {
+    type Error = PermissionError;
+
+    fn try_from(response: CheckPermissionResponse) -> Result<Self, Self::Error> {
+        permissionship_to_result(response.permissionship(), response.partial_caveat_info)
+    }
+}
+
+impl TryFrom<CheckBulkPermissionsPair> for PermissionCheckResult {
+    type Error = PermissionError;
+
+    fn try_from(pair: CheckBulkPermissionsPair) -> Result<Self, Self::Error> {
+        match pair.response {
+            Some(check_bulk_permissions_pair::Response::Item(item)) => {
+                permissionship_to_result(item.permissionship(), item.partial_caveat_info)
+            }
+            Some(check_bulk_permissions_pair::Response::Error(status)) => {
+                Err(PermissionError::CheckPermissionError {
+                    msg: status.message,
+                })
+            }
+            None => Err(PermissionError::CheckPermissionError {
+                msg: "SpiceDB returned an empty bulk check pair".to_string(),
+            }),
+        }
+    }
+}
+
+/// Subject ID SpiceDB uses for a wildcard (`user:*`) grant
+const WILDCARD_SUBJECT_ID: &str = "*";
+
+impl From<Vec<LookupSubjectsResponse>> for SubjectsLookup {
+    fn from(responses: Vec<LookupSubjectsResponse>) -> Self {
+        let mut lookup = SubjectsLookup::default();
+        for response in responses {
+            let Some(subject) = response.subject else {
+                continue;
+            };
+            // Caveated subjects would need context we don't have, leave them out
+            if subject.permissionship() != LookupPermissionship::HasPermission {
+                continue;
+            }
+            if subject.subject_object_id == WILDCARD_SUBJECT_ID {
+                lookup.wildcard = Some(WildcardGrant {
+                    excluded_user_ids: response
+                        .excluded_subjects
+                        .into_iter()
+                        .map(|excluded| excluded.subject_object_id)
+                        .collect(),
+                });
+            } else {
+                lookup.user_ids.push(subject.subject_object_id);
+            }
+        }
+        lookup
+    }
+}
+
+fn is_granted(trace: &CheckDebugTrace) -> bool {
+    matches!(
+        trace.result(),
+        check_debug_trace::Permissionship::HasPermission
+            | check_debug_trace::Permissionship::ConditionalPermission
+    )
+}
+
+fn is_relation(trace: &CheckDebugTrace) -> bool {
+    trace.permission_type() == check_debug_trace::PermissionType::Relation
+}
+
+fn object_of(trace: &CheckDebugTrace) -> (&str, &str) {
+    trace
+        .resource
+        .as_ref()
+        .map(|r| (r.object_type.as_str(), r.object_id.as_str()))
+        .unwrap_or_default()
+}
+
+fn definition_of(trace: &CheckDebugTrace) -> Option<Definition> {
+    Definition::from_name(object_of(trace).0)
+}
+
+/// The `role#member` relation carrying a role's grants to its members
+fn is_role_member(trace: &CheckDebugTrace) -> bool {
+    definition_of(trace) == Some(Definition::Role)
+        && role::Relation::from_name(&trace.permission) == Some(role::Relation::Member)
+}
+
+/// A channel or role relation denying its permission
+fn is_deny_relation(trace: &CheckDebugTrace) -> bool {
+    let relation = trace.permission.as_str();
+    let is_allow = match definition_of(trace) {
+        Some(Definition::Channel) => {
+            channel::Relation::from_name(relation).and_then(channel_relation_is_allow)
+        }
+        Some(Definition::Role) => {
+            role::Relation::from_name(relation).and_then(role_relation_is_allow)
+        }
+        _ => None,
+    };
+    is_allow == Some(false)
+}
+
+fn sub_traces(trace: &CheckDebugTrace) -> &[CheckDebugTrace] {
+    match &trace.resolution {
+        Some(check_debug_trace::Resolution::SubProblems(sub_problems)) => &sub_problems.traces,
+        _ => &[],
+    }
+}
+
+/// Follow granted sub-problems from `trace` down to the relationship that granted it
+fn granted_path<'a>(trace: &'a CheckDebugTrace, path: &mut Vec<&'a CheckDebugTrace>) {
+    path.push(trace);
+    if let Some(next) = sub_traces(trace).iter().find(|t| is_granted(t)) {
+        granted_path(next, path);
+    }
+}
+
+/// Find a deny relation held by the subject, recording the path down to it
+fn deny_path<'a>(trace: &'a CheckDebugTrace, path: &mut Vec<&'a CheckDebugTrace>) -> bool {
+    if is_granted(trace) && is_relation(trace) && is_deny_relation(trace) {
+        granted_path(trace, path);
+        return true;
+    }
+    path.push(trace);
+    for sub in sub_traces(trace) {
+        if deny_path(sub, path) {
+            return true;
+        }
+    }
+    path.pop();
+    false
+}
+
+/// A relation on a Beep object that grants or denies something, as opposed to
+/// the role membership or override targeting that carries it to the user
+fn is_deciding_relation(trace: &CheckDebugTrace) -> bool {
+    is_relation(trace)
+        && matches!(
+            definition_of(trace),
+            Some(Definition::Server | Definition::Channel | Definition::Role)
+        )
+        && !is_role_member(trace)
+}
+
+/// Read the deciding element off a path ending at the granting relationship
+fn deciding_element(path: &[&CheckDebugTrace]) -> DecidingElement {
+    let Some(index) = path.iter().rposition(|t| is_deciding_relation(t)) else {
+        return DecidingElement::Unresolved;
+    };
+    let trace = path[index];
+    let relation = trace.permission.clone();
+    let object_id = object_of(trace).1;
+    let (resource, permission) = match definition_of(trace) {
+        Some(Definition::Server)
+            if server::Relation::from_name(&relation) == Some(server::Relation::Owner) =>
+        {
+            return DecidingElement::ServerOwner {
+                server_id: object_id.to_string(),
+            };
+        }
+        Some(Definition::Server) => (
+            Resource::Server(object_id.to_string()),
+            server_relation_to_permission_display(&relation),
+        ),
+        Some(Definition::Channel) => (
+            Resource::Channel(object_id.to_string()),
+            channel_relation_to_permission_display(&relation),
+        ),
+        _ => (Resource::Role(object_id.to_string()), None),
+    };
+
+    // An override wins over the role it targets: it is what was written on the channel
+    let carriers = &path[index + 1..];
+    let subject = if let Some(t) = carriers
+        .iter()
+        .find(|t| definition_of(t) == Some(Definition::PermissionOverride))
+    {
+        GrantSubject::PermissionOverride {
+            override_id: object_of(t).1.to_string(),
+        }
+    } else if let Some(t) = carriers.iter().find(|t| is_role_member(t)) {
+        GrantSubject::RoleMember {
+            role_id: object_of(t).1.to_string(),
+        }
+    } else {
+        GrantSubject::User
+    };
+
+    let permission = permission.map(str::to_string);
+    if is_deny_relation(trace) {
+        DecidingElement::Deny {
+            resource,
+            relation,
+            permission,
+            subject,
+        }
+    } else {
+        DecidingElement::Grant {
+            resource,
+            relation,
+            permission,
+            subject,
+        }
+    }
+}
+
+impl From<&CheckDebugTrace> for ExplanationStep {
+    fn from(trace: &CheckDebugTrace) -> Self {
+        let (object_type, object_id) = object_of(trace);
+        ExplanationStep {
+            object: format!("{}:{}", object_type, object_id),
+            name: trace.permission.clone(),
+            granted: is_granted(trace),
+        }
+    }
+}
+
+impl TryFrom<CheckPermissionResponse> for PermissionExplanation {
+    type Error = PermissionError;
+
+    fn try_from(response: CheckPermissionResponse) -> Result<Self, Self::Error> {
+        let result =
+            permissionship_to_result(response.permissionship(), response.partial_caveat_info)?;
+        let trace = response
+            .debug_trace
+            .and_then(|debug| debug.check)
+            .ok_or_else(|| PermissionError::ExplainPermissionError {
+                msg: "SpiceDB returned no debug trace".to_string(),
+            })?;
+
+        let mut path = Vec::new();
+        let deciding_element = if is_granted(&trace) {
+            granted_path(&trace, &mut path);
+            deciding_element(&path)
+        } else if deny_path(&trace, &mut path) {
+            deciding_element(&path)
+        } else {
+            path.push(&trace);
+            DecidingElement::NoGrant
+        };
+
+        Ok(PermissionExplanation {
+            result,
+            deciding_element,
+            chain: path.into_iter().map(ExplanationStep::from).collect(),
+        })
+    }
+}
+
+/// Create a RelationshipFilter for the channel->server relationship of a channel
+pub fn channel_server_filter(channel_id: &str) -> RelationshipFilter {
+    Channel::from(channel_id.to_string())
+        .filter()
+        .relation(channel::Relation::Server)
+        .build()
+}
+
+/// One check per capability of the descriptor, paired with its bit.
+/// With a channel, channel capabilities are checked on it (overrides applied)
+/// and the server-only ones on its server.
+pub fn effective_permission_checks(
+    user_id: &str,
+    server_id: &str,
+    channel_id: Option<&str>,
+    descriptor: &CapabilityDescriptor,
+    consistency: &Consistency,
+) -> Vec<(u64, CheckPermissionInput)> {
+    let mut checks = Vec::new();
+
+    for (name, &hex_value) in descriptor.iter() {
+        let target = match channel_id {
+            Some(channel_id) if is_channel_permission(name) => {
+                permission_display_to_channel_permission(name).map(|permission| {
+                    (Resource::Channel(channel_id.to_string()), permission.into())
+                })
+            }
+            _ => permission_display_to_server_permission(name)
+                .map(|permission| (Resource::Server(server_id.to_string()), permission.into())),
+        };
+
+        match target {
+            Some((resource, permission)) => checks.push((
+                hex_value as u64,
+                CheckPermissionInput {
+                    user_id: user_id.to_string(),
+                    resource,
+                    permission,
+                    consistency: consistency.clone(),
+                },
+            )),
+            None => warn!(
+                permission_name = %name,
+                "Unknown permission name, skipping"
+            ),
+        }
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        authzed::api::v1::{CheckBulkPermissionsResponseItem, DebugInformation, ResolvedSubject},
+        domain::permission::entities::{ChannelPermission, ServerPermission},
+        google::rpc::Status,
+    };
+
+    fn response(permissionship: Permissionship) -> CheckPermissionResponse {
+        CheckPermissionResponse {
+            permissionship: permissionship.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resource_to_object_reference() {
+        let server: ObjectReference = Resource::Server("server_1".to_string()).into();
+        assert_eq!(server.object_type, "server");
+        assert_eq!(server.object_id, "server_1");
+
+        let channel: ObjectReference = Resource::Channel("channel_1".to_string()).into();
+        assert_eq!(channel.object_type, "channel");
+        assert_eq!(channel.object_id, "channel_1");
+
+        let role: ObjectReference = Resource::Role("role_1".to_string()).into();
+        assert_eq!(role.object_type, "role");
+        assert_eq!(role.object_id, "role_1");
+    }
+
+    #[test]
+    fn test_resource_type_to_entity_name() {
+        let server: String = Entity::from(ResourceType::Server).into();
+        let channel: String = Entity::from(ResourceType::Channel).into();
+        let role: String = Entity::from(ResourceType::Role).into();
+        assert_eq!(server, "server");
+        assert_eq!(channel, "channel");
+        assert_eq!(role, "role");
+    }
+
+    #[test]
+    fn test_check_response_allowed_and_denied() {
+        assert_eq!(
+            PermissionCheckResult::try_from(response(Permissionship::HasPermission)).unwrap(),
+            PermissionCheckResult::Allowed
+        );
+        assert_eq!(
+            PermissionCheckResult::try_from(response(Permissionship::NoPermission)).unwrap(),
+            PermissionCheckResult::Denied
+        );
+    }
+
+    #[test]
+    fn test_check_response_conditional_keeps_missing_context() {
+        let mut conditional = response(Permissionship::ConditionalPermission);
+        conditional.partial_caveat_info = Some(PartialCaveatInfo {
+            missing_required_context: vec!["current_time".to_string()],
+        });
+
+        assert_eq!(
+            PermissionCheckResult::try_from(conditional).unwrap(),
+            PermissionCheckResult::Conditional {
+                missing_context: vec!["current_time".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_response_unspecified_is_an_error() {
+        assert!(PermissionCheckResult::try_from(response(Permissionship::Unspecified)).is_err());
+    }
+
+    #[test]
+    fn test_check_input_to_bulk_item() {
+        let item: CheckBulkPermissionsRequestItem = CheckPermissionInput {
+            user_id: "user_1".to_string(),
+            resource: Resource::Channel("channel_1".to_string()),
+            permission: ChannelPermission::SendMessage.into(),
+            consistency: Consistency::default(),
+        }
+        .into();
+
+        assert_eq!(item.resource.as_ref().unwrap().object_type, "channel");
+        assert_eq!(item.resource.as_ref().unwrap().object_id, "channel_1");
+        assert_eq!(item.permission, "send_message");
+        let subject = item.subject.unwrap().object.unwrap();
+        assert_eq!(subject.object_type, "user");
+        assert_eq!(subject.object_id, "user_1");
+    }
+
+    #[test]
+    fn test_bulk_pair_item_and_error() {
+        let allowed = CheckBulkPermissionsPair {
+            request: None,
+            response: Some(check_bulk_permissions_pair::Response::Item(
+                CheckBulkPermissionsResponseItem {
+                    permissionship: Permissionship::HasPermission.into(),
+                    ..Default::default()
+                },
+            )),
+        };
+        assert_eq!(
+            PermissionCheckResult::try_from(allowed).unwrap(),
+            PermissionCheckResult::Allowed
+        );
+
+        let failed = CheckBulkPermissionsPair {
+            request: None,
+            response: Some(check_bulk_permissions_pair::Response::Error(Status {
+                code: 3,
+                message: "object definition `chanel` not found".to_string(),
+                details: vec![],
+            })),
+        };
+        match PermissionCheckResult::try_from(failed) {
+            Err(PermissionError::CheckPermissionError { msg }) => {
+                assert!(msg.contains("not found"));
+            }
+            other => panic!("Expected CheckPermissionError, got {:?}", other),
+        }
+    }
+
+    fn subject_response(
+        subject_id: &str,
+        permissionship: LookupPermissionship,
+        excluded: &[&str],
+    ) -> LookupSubjectsResponse {
+        let resolved = |id: &str| ResolvedSubject {
+            subject_object_id: id.to_string(),
+            permissionship: permissionship.into(),
+            partial_caveat_info: None,
+        };
+        LookupSubjectsResponse {
+            subject: Some(resolved(subject_id)),
+            excluded_subjects: excluded.iter().map(|id| resolved(id)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_lookup_subjects_collects_concrete_users() {
+        let lookup = SubjectsLookup::from(vec![
+            subject_response("user_1", LookupPermissionship::HasPermission, &[]),
+            subject_response("user_2", LookupPermissionship::ConditionalPermission, &[]),
+            subject_response("user_3", LookupPermissionship::HasPermission, &[]),
+        ]);
+
+        assert_eq!(lookup.user_ids, vec!["user_1", "user_3"]);
+        assert!(lookup.wildcard.is_none());
+    }
+
+    #[test]
+    fn test_lookup_subjects_wildcard_keeps_exclusions() {
+        let lookup = SubjectsLookup::from(vec![
+            subject_response("*", LookupPermissionship::HasPermission, &["user_banned"]),
+            subject_response("user_1", LookupPermissionship::HasPermission, &[]),
+        ]);
+
+        assert_eq!(lookup.user_ids, vec!["user_1"]);
+        assert_eq!(
+            lookup.wildcard,
+            Some(WildcardGrant {
+                excluded_user_ids: vec!["user_banned".to_string()],
+            })
+        );
+    }
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        let mut descriptor = CapabilityDescriptor::new();
+        descriptor.insert("admin".to_string(), 0x1);
+        descriptor.insert("manage".to_string(), 0x2);
+        descriptor.insert("view_channel".to_string(), 0x40);
+        descriptor.insert("send_message".to_string(), 0x80);
+        descriptor
+    }
+
+    fn find_check(checks: &[(u64, CheckPermissionInput)], bit: u64) -> &CheckPermissionInput {
+        &checks.iter().find(|(b, _)| *b == bit).unwrap().1
+    }
+
+    #[test]
+    fn test_channel_server_filter() {
+        let filter = channel_server_filter("channel_1");
+
+        assert_eq!(filter.resource_type, "channel");
+        assert_eq!(filter.optional_resource_id, "channel_1");
+        assert_eq!(filter.optional_relation, "server");
+        assert!(filter.optional_subject_filter.is_none());
+    }
+
+    #[test]
+    fn test_effective_permission_checks_for_server() {
+        let descriptor = create_test_descriptor();
+
+        let checks = effective_permission_checks(
+            "user_1",
+            "server_1",
+            None,
+            &descriptor,
+            &Consistency::default(),
+        );
+
+        assert_eq!(checks.len(), 4);
+        for (_, check) in &checks {
+            assert_eq!(check.user_id, "user_1");
+            assert_eq!(check.resource, Resource::Server("server_1".to_string()));
+        }
+        assert_eq!(
+            find_check(&checks, 0x40).permission,
+            ServerPermission::ViewChannel.into()
+        );
+    }
+
+    #[test]
+    fn test_effective_permission_checks_for_channel() {
+        let descriptor = create_test_descriptor();
+
+        let checks = effective_permission_checks(
+            "user_1",
+            "server_1",
+            Some("channel_1"),
+            &descriptor,
+            &Consistency::default(),
+        );
+
+        assert_eq!(checks.len(), 4);
+        let admin = find_check(&checks, 0x1);
+        assert_eq!(admin.resource, Resource::Server("server_1".to_string()));
+        assert_eq!(admin.permission, ServerPermission::Admin.into());
+        let view = find_check(&checks, 0x40);
+        assert_eq!(view.resource, Resource::Channel("channel_1".to_string()));
+        assert_eq!(view.permission, ChannelPermission::View.into());
+        let send = find_check(&checks, 0x80);
+        assert_eq!(send.resource, Resource::Channel("channel_1".to_string()));
+        assert_eq!(send.permission, ChannelPermission::SendMessage.into());
+    }
+
+    fn trace(
+        object: &str,
+        name: &str,
+        permission_type: check_debug_trace::PermissionType,
+        granted: bool,
+        sub_problems: Vec<CheckDebugTrace>,
+    ) -> CheckDebugTrace {
+        let (object_type, object_id) = object.split_once(':').unwrap();
+        let result = if granted {
+            check_debug_trace::Permissionship::HasPermission
+        } else {
+            check_debug_trace::Permissionship::NoPermission
+        };
+        CheckDebugTrace {
+            resource: Some(ObjectReference {
+                object_type: object_type.to_string(),
+                object_id: object_id.to_string(),
+            }),
+            permission: name.to_string(),
+            permission_type: permission_type.into(),
+            subject: Some(User::from("bob".to_string()).into()),
+            result: result.into(),
+            resolution: Some(check_debug_trace::Resolution::SubProblems(
+                check_debug_trace::SubProblems {
+                    traces: sub_problems,
+                },
+            )),
+            ..Default::default()
+        }
+    }
+
+    fn permission(
+        object: &str,
+        name: &str,
+        granted: bool,
+        sub: Vec<CheckDebugTrace>,
+    ) -> CheckDebugTrace {
+        trace(
+            object,
+            name,
+            check_debug_trace::PermissionType::Permission,
+            granted,
+            sub,
+        )
+    }
+
+    fn relation(
+        object: &str,
+        name: &str,
+        granted: bool,
+        sub: Vec<CheckDebugTrace>,
+    ) -> CheckDebugTrace {
+        trace(
+            object,
+            name,
+            check_debug_trace::PermissionType::Relation,
+            granted,
+            sub,
+        )
+    }
+
+    fn traced_response(granted: bool, check: CheckDebugTrace) -> CheckPermissionResponse {
+        let permissionship = if granted {
+            Permissionship::HasPermission
+        } else {
+            Permissionship::NoPermission
+        };
+        CheckPermissionResponse {
+            permissionship: permissionship.into(),
+            debug_trace: Some(DebugInformation {
+                check: Some(check),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_explain_server_owner() {
+        let check = permission(
+            "server:s1",
+            "admin",
+            true,
+            vec![
+                relation("server:s1", "owner", true, vec![]),
+                relation("server:s1", "administrator", false, vec![]),
+            ],
+        );
+
+        let explanation = PermissionExplanation::try_from(traced_response(true, check)).unwrap();
+
+        assert_eq!(explanation.result, PermissionCheckResult::Allowed);
+        assert_eq!(
+            explanation.deciding_element,
+            DecidingElement::ServerOwner {
+                server_id: "s1".to_string()
+            }
+        );
+        assert_eq!(explanation.chain.len(), 2);
+    }
+
+    #[test]
+    fn test_explain_role_relation_on_server() {
+        let check = permission(
+            "channel:general",
+            "send_message",
+            true,
+            vec![permission(
+                "server:s1",
+                "send_message",
+                true,
+                vec![
+                    relation("server:s1", "owner", false, vec![]),
+                    relation(
+                        "server:s1",
+                        "message_sender",
+                        true,
+                        vec![relation("role:mods", "member", true, vec![])],
+                    ),
+                ],
+            )],
+        );
+
+        let explanation = PermissionExplanation::try_from(traced_response(true, check)).unwrap();
+
+        assert_eq!(
+            explanation.deciding_element.to_string(),
+            "message_sender@role:mods#member on server:s1 grants send_message"
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "channel:general send_message (granted) -> server:s1 send_message (granted) -> \
+             server:s1 message_sender (granted) -> role:mods member (granted): \
+             decided by message_sender@role:mods#member on server:s1 grants send_message"
+        );
+    }
+
+    #[test]
+    fn test_explain_permission_override_deny() {
+        let check = permission(
+            "channel:general",
+            "send_message",
+            false,
+            vec![
+                relation(
+                    "channel:general",
+                    "send_message_grant",
+                    true,
+                    vec![relation("role:everyone", "member", true, vec![])],
+                ),
+                relation(
+                    "channel:general",
+                    "send_message_deny",
+                    true,
+                    vec![relation(
+                        "permission_override:po1",
+                        "denied_to",
+                        true,
+                        vec![relation("role:muted", "member", true, vec![])],
+                    )],
+                ),
+            ],
+        );
+
+        let explanation = PermissionExplanation::try_from(traced_response(false, check)).unwrap();
+
+        assert_eq!(explanation.result, PermissionCheckResult::Denied);
+        assert_eq!(
+            explanation.deciding_element,
+            DecidingElement::Deny {
+                resource: Resource::Channel("general".to_string()),
+                relation: "send_message_deny".to_string(),
+                permission: Some("send_message".to_string()),
+                subject: GrantSubject::PermissionOverride {
+                    override_id: "po1".to_string()
+                },
+            }
+        );
+        assert_eq!(
+            explanation.deciding_element.to_string(),
+            "send_message_deny@permission_override:po1#denied_to on channel:general denies send_message"
+        );
+    }
+
+    #[test]
+    fn test_explain_direct_channel_grant() {
+        let check = permission(
+            "channel:general",
+            "view",
+            true,
+            vec![relation(
+                "channel:general",
+                "view_channel_grant",
+                true,
+                vec![],
+            )],
+        );
+
+        let explanation = PermissionExplanation::try_from(traced_response(true, check)).unwrap();
+
+        assert_eq!(
+            explanation.deciding_element,
+            DecidingElement::Grant {
+                resource: Resource::Channel("general".to_string()),
+                relation: "view_channel_grant".to_string(),
+                permission: Some("view_channel".to_string()),
+                subject: GrantSubject::User,
+            }
+        );
+    }
+
+    #[test]
+    fn test_explain_no_grant() {
+        let check = permission(
+            "channel:general",
+            "send_message",
+            false,
+            vec![relation(
+                "channel:general",
+                "send_message_grant",
+                false,
+                vec![],
+            )],
+        );
+
+        let explanation = PermissionExplanation::try_from(traced_response(false, check)).unwrap();
+
+        assert_eq!(explanation.deciding_element, DecidingElement::NoGrant);
+        assert_eq!(explanation.chain.len(), 1);
+    }
+
+    #[test]
+    fn test_explain_requires_trace() {
+        let result = PermissionExplanation::try_from(response(Permissionship::HasPermission));
+
+        assert!(matches!(
+            result,
+            Err(PermissionError::ExplainPermissionError { .. })
+        ));
+    }
+}
@@ -0,0 +1,407 @@
+use crate::{
+    authzed::api::v1::{CheckBulkPermissionsRequestItem, ObjectReference},
+    domain::{
+        common::entities::Consistency,
+        permission::{
+            PermissionError,
+            entities::{
+                BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput,
+                LookupResourcesInput, LookupSubjectsInput, PermissionCheckResult,
+                PermissionExplanation, Resource, SubjectsLookup,
+            },
+            port::{PermissionChecker, PermissionRepository},
+        },
+    },
+    infrastructure::{
+        authzed::{AuthZedClient, error::AuthzedError},
+        common::{
+            authzed::entities::{Entity, user::User},
+            permissions::schema_permission,
+        },
+    },
+};
+use permission_translation::models::CapabilityDescriptor;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+pub mod entities;
+
+#[derive(Clone)]
+pub struct AuthzedPermissionRepository {
+    pub authzed_client: AuthZedClient,
+    pub permissions_descriptor: Arc<CapabilityDescriptor>,
+}
+
+impl AuthzedPermissionRepository {
+    pub fn new(
+        authzed_client: AuthZedClient,
+        permissions_descriptor: Arc<CapabilityDescriptor>,
+    ) -> Self {
+        Self {
+            authzed_client,
+            permissions_descriptor,
+        }
+    }
+
+    /// Read the server a channel belongs to
+    async fn channel_server(
+        &self,
+        channel_id: &str,
+        consistency: Consistency,
+    ) -> Result<String, PermissionError> {
+        let relationships = self
+            .authzed_client
+            .read_relationships(entities::channel_server_filter(channel_id), consistency)
+            .await
+            .map_err(|e| {
+                permission_error(e, |msg| PermissionError::EffectivePermissionsError { msg })
+            })?;
+
+        relationships
+            .into_iter()
+            .filter_map(|relationship| relationship.subject)
+            .filter_map(|subject| subject.object)
+            .map(|server| server.object_id)
+            .next()
+            .ok_or_else(|| PermissionError::NotFound {
+                msg: format!("channel {} does not belong to any server", channel_id),
+            })
+    }
+}
+
+/// `Unavailable` when SpiceDB could not answer for now, the error built by `other` otherwise
+fn permission_error(
+    e: AuthzedError,
+    other: impl FnOnce(String) -> PermissionError,
+) -> PermissionError {
+    if e.is_unavailable() {
+        PermissionError::Unavailable { msg: e.to_string() }
+    } else {
+        other(e.to_string())
+    }
+}
+
+impl PermissionChecker for AuthzedPermissionRepository {
+    #[instrument(skip(self), fields(user_id = %input.user_id, resource = ?input.resource, permission = ?input.permission))]
+    async fn check(
+        &self,
+        input: CheckPermissionInput,
+    ) -> Result<PermissionCheckResult, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            resource = ?input.resource,
+            permission = ?input.permission,
+            "Checking permission in AuthZed"
+        );
+
+        let (_, permission) = schema_permission(input.permission);
+        let resource: ObjectReference = input.resource.into();
+        let response = self
+            .authzed_client
+            .check_permission(
+                resource,
+                permission,
+                User::from(input.user_id),
+                input.consistency,
+            )
+            .await
+            .map_err(|e| {
+                permission_error(e, |msg| PermissionError::CheckPermissionError { msg })
+            })?;
+
+        let result = PermissionCheckResult::try_from(response);
+        match &result {
+            Ok(outcome) => info!(outcome = ?outcome, "Permission checked in AuthZed"),
+            Err(e) => info!(error = ?e, "Failed to check permission in AuthZed"),
+        }
+
+        result
+    }
+
+    #[instrument(skip(self, inputs), fields(item_count = inputs.len()))]
+    async fn check_bulk(
+        &self,
+        inputs: Vec<CheckPermissionInput>,
+    ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+        info!(
+            item_count = inputs.len(),
+            "Checking permissions in bulk in AuthZed"
+        );
+
+        // SpiceDB takes one consistency for the whole batch
+        let consistency = inputs.iter().fold(Consistency::default(), |acc, input| {
+            acc.strictest(input.consistency.clone())
+        });
+        let items: Vec<CheckBulkPermissionsRequestItem> =
+            inputs.iter().cloned().map(Into::into).collect();
+        let pairs = self
+            .authzed_client
+            .check_bulk_permissions(items, consistency)
+            .await
+            .map_err(|e| {
+                permission_error(e, |msg| PermissionError::BulkCheckPermissionError { msg })
+            })?;
+
+        // SpiceDB keeps the request order, so pairs line up with inputs
+        if pairs.len() != inputs.len() {
+            return Err(PermissionError::BulkCheckPermissionError {
+                msg: format!(
+                    "expected {} bulk check results, got {}",
+                    inputs.len(),
+                    pairs.len()
+                ),
+            });
+        }
+
+        let results: Vec<BulkCheckItem> = inputs
+            .into_iter()
+            .zip(pairs)
+            .map(|(input, pair)| BulkCheckItem {
+                input,
+                result: PermissionCheckResult::try_from(pair),
+            })
+            .collect();
+
+        let failed_count = results.iter().filter(|item| item.result.is_err()).count();
+        if failed_count > 0 {
+            warn!(
+                failed_count,
+                "Some bulk permission checks failed in AuthZed"
+            );
+        }
+        info!(
+            item_count = results.len(),
+            "Bulk permissions checked in AuthZed"
+        );
+        Ok(results)
+    }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, permission = ?input.permission))]
+    async fn lookup_resources(
+        &self,
+        input: LookupResourcesInput,
+    ) -> Result<Vec<String>, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            permission = ?input.permission,
+            "Looking up resources in AuthZed"
+        );
+
+        let (resource_type, permission) = schema_permission(input.permission);
+        let result = self
+            .authzed_client
+            .lookup_resources(
+                resource_type,
+                permission,
+                User::from(input.user_id),
+                input.consistency,
+            )
+            .await
+            .map_err(|e| permission_error(e, |msg| PermissionError::LookupResourcesError { msg }));
+
+        match &result {
+            Ok(ids) => info!(resource_count = ids.len(), "Resources looked up in AuthZed"),
+            Err(e) => info!(error = ?e, "Failed to look up resources in AuthZed"),
+        }
+
+        result
+    }
+
+    #[instrument(skip(self), fields(resource = ?input.resource, permission = ?input.permission))]
+    async fn lookup_subjects(
+        &self,
+        input: LookupSubjectsInput,
+    ) -> Result<SubjectsLookup, PermissionError> {
+        info!(
+            resource = ?input.resource,
+            permission = ?input.permission,
+            "Looking up subjects in AuthZed"
+        );
+
+        // Asking for `user` subjects makes SpiceDB expand role#member,
+        // permission_override#granted_to and direct grants down to users
+        let (_, permission) = schema_permission(input.permission);
+        let resource: ObjectReference = input.resource.into();
+        let subject_type: String = Entity::User.into();
+        let result = self
+            .authzed_client
+            .lookup_subjects(resource, permission, &subject_type, input.consistency)
+            .await
+            .map(SubjectsLookup::from)
+            .map_err(|e| permission_error(e, |msg| PermissionError::LookupSubjectsError { msg }));
+
+        match &result {
+            Ok(lookup) => info!(
+                user_count = lookup.user_ids.len(),
+                wildcard = lookup.wildcard.is_some(),
+                "Subjects looked up in AuthZed"
+            ),
+            Err(e) => info!(error = ?e, "Failed to look up subjects in AuthZed"),
+        }
+
+        result
+    }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, resource = ?input.resource, permission = ?input.permission))]
+    async fn explain(
+        &self,
+        input: CheckPermissionInput,
+    ) -> Result<PermissionExplanation, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            resource = ?input.resource,
+            permission = ?input.permission,
+            "Explaining permission in AuthZed"
+        );
+
+        let (_, permission) = schema_permission(input.permission);
+        let resource: ObjectReference = input.resource.into();
+        let response = self
+            .authzed_client
+            .check_permission_with_trace(
+                resource,
+                permission,
+                User::from(input.user_id),
+                input.consistency,
+            )
+            .await
+            .map_err(|e| {
+                permission_error(e, |msg| PermissionError::ExplainPermissionError { msg })
+            })?;
+
+        let result = PermissionExplanation::try_from(response);
+        match &result {
+            Ok(explanation) => info!(explanation = %explanation, "Permission explained in AuthZed"),
+            Err(e) => info!(error = ?e, "Failed to explain permission in AuthZed"),
+        }
+
+        result
+    }
+
+    #[instrument(skip(self), fields(user_id = %input.user_id, resource = ?input.resource))]
+    async fn effective_permissions(
+        &self,
+        input: EffectivePermissionsInput,
+    ) -> Result<u64, PermissionError> {
+        info!(
+            user_id = %input.user_id,
+            resource = ?input.resource,
+            "Computing effective permissions in AuthZed"
+        );
+
+        let (server_id, channel_id) = match input.resource {
+            Resource::Server(server_id) => (server_id, None),
+            Resource::Channel(channel_id) => {
+                let server_id = self
+                    .channel_server(&channel_id, input.consistency.clone())
+                    .await?;
+                (server_id, Some(channel_id))
+            }
+            Resource::Role(role_id) => {
+                return Err(PermissionError::InvalidPermission {
+                    msg: format!("role {} has no permission bitmask", role_id),
+                });
+            }
+        };
+
+        let (bits, checks): (Vec<u64>, Vec<CheckPermissionInput>) =
+            entities::effective_permission_checks(
+                &input.user_id,
+                &server_id,
+                channel_id.as_deref(),
+                &self.permissions_descriptor,
+                &input.consistency,
+            )
+            .into_iter()
+            .unzip();
+
+        if checks.is_empty() {
+            return Ok(0);
+        }
+
+        let items = self.check_bulk(checks).await.map_err(|e| match e {
+            PermissionError::Unavailable { .. } => e,
+            e => PermissionError::EffectivePermissionsError { msg: e.to_string() },
+        })?;
+
+        // A single failed check would make the bitmask lie, so fail the whole computation
+        let mut bitmask = 0;
+        for (bit, item) in bits.into_iter().zip(items) {
+            match item.result {
+                Ok(PermissionCheckResult::Allowed) => bitmask |= bit,
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(PermissionError::EffectivePermissionsError { msg: e.to_string() });
+                }
+            }
+        }
+
+        info!(
+            bitmask = format!("{:#x}", bitmask),
+            "Effective permissions computed in AuthZed"
+        );
+        Ok(bitmask)
+    }
+}
+
+impl PermissionRepository for AuthzedPermissionRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::permission::entities::ChannelPermission,
+        infrastructure::{
+            authzed::fake::{FakeSpiceDb, fast_retries},
+            common::permissions_translations::BeepPermissions,
+        },
+    };
+    use tonic::Code;
+
+    async fn repository(spicedb: &Arc<FakeSpiceDb>) -> AuthzedPermissionRepository {
+        AuthzedPermissionRepository::new(
+            spicedb.serve(fast_retries(0)).await,
+            BeepPermissions::new().descriptor(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_effective_permissions_of_unknown_channel_is_not_found() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+
+        // Act
+        let result = repository
+            .effective_permissions(EffectivePermissionsInput {
+                user_id: "user_1".to_string(),
+                resource: Resource::Channel("channel_1".to_string()),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(PermissionError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_transient_spicedb_failure_is_unavailable() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        spicedb.fail_next(1, Code::Unavailable);
+
+        // Act
+        let result = repository
+            .check(CheckPermissionInput {
+                user_id: "user_1".to_string(),
+                resource: Resource::Channel("channel_1".to_string()),
+                permission: ChannelPermission::View.into(),
+                consistency: Consistency::default(),
+            })
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(PermissionError::Unavailable { .. })));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::domain::permission::entities::{Permission, PermissionCheckResult, Resource};
+use clap::Parser;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// In-process check cache configuration
+#[derive(Debug, Clone, Parser)]
+pub struct CheckCacheConfig {
+    /// Cache check results in memory, invalidated through the SpiceDB Watch API
+    #[arg(
+        long = "check-cache",
+        env = "CHECK_CACHE_ENABLED",
+        default_value_t = false
+    )]
+    pub enabled: bool,
+
+    /// Maximum number of cached check results
+    #[arg(
+        long = "check-cache-capacity",
+        env = "CHECK_CACHE_CAPACITY",
+        default_value_t = 10_000
+    )]
+    pub capacity: usize,
+
+    /// Seconds a cached result stays valid, as a bound on missed invalidations
+    #[arg(
+        long = "check-cache-ttl-secs",
+        env = "CHECK_CACHE_TTL_SECS",
+        default_value_t = 30
+    )]
+    pub ttl_secs: u64,
+
+    /// Seconds between two log lines of the cache counters; 0 disables them
+    #[arg(
+        long = "check-cache-stats-interval-secs",
+        env = "CHECK_CACHE_STATS_INTERVAL_SECS",
+        default_value_t = 60
+    )]
+    pub stats_interval_secs: u64,
+}
+
+impl Default for CheckCacheConfig {
+    /// Caching disabled
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: 10_000,
+            ttl_secs: 30,
+            stats_interval_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CheckKey {
+    pub resource: Resource,
+    pub permission: Permission,
+    pub user_id: String,
+}
+
+/// Snapshot of the cache counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+struct Entry {
+    result: PermissionCheckResult,
+    inserted_at: Instant,
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<CheckKey, Entry>,
+    /// Insertion order for eviction; may hold keys already removed or re-inserted
+    order: VecDeque<(CheckKey, Instant)>,
+    /// Bumped by every invalidation, so results computed before one are not stored
+    generation: u64,
+}
+
+/// Bounded, TTL-limited map of check results
+pub struct CheckCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CheckCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_config(config: &CheckCacheConfig) -> Self {
+        Self::new(config.capacity, Duration::from_secs(config.ttl_secs))
+    }
+
+    pub fn get(&self, key: &CheckKey) -> Option<PermissionCheckResult> {
+        let mut entries = self.entries.lock().unwrap();
+        let result = match entries.map.get(key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.ttl => Some(entry.result.clone()),
+            Some(_) => {
+                entries.map.remove(key);
+                None
+            }
+            None => None,
+        };
+
+        let counter = if result.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    /// Current invalidation generation, to pass back to `insert`
+    pub fn generation(&self) -> u64 {
+        self.entries.lock().unwrap().generation
+    }
+
+    /// Store a result computed after `generation` was read; dropped if an
+    /// invalidation happened in between, since the result may predate it
+    pub fn insert(&self, key: CheckKey, result: PermissionCheckResult, generation: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.generation != generation {
+            return;
+        }
+
+        if !entries.map.contains_key(&key) {
+            while entries.map.len() >= self.capacity {
+                let Some((oldest, inserted_at)) = entries.order.pop_front() else {
+                    break;
+                };
+                if entries
+                    .map
+                    .get(&oldest)
+                    .is_some_and(|entry| entry.inserted_at == inserted_at)
+                {
+                    entries.map.remove(&oldest);
+                }
+            }
+        }
+
+        let inserted_at = Instant::now();
+        entries.order.push_back((key.clone(), inserted_at));
+        entries.map.insert(
+            key,
+            Entry {
+                result,
+                inserted_at,
+            },
+        );
+
+        // Re-inserted keys leave stale order entries behind; drop them once they pile up
+        if entries.order.len() > self.capacity * 2 {
+            let Entries { map, order, .. } = &mut *entries;
+            order.retain(|(key, inserted_at)| {
+                map.get(key)
+                    .is_some_and(|entry| entry.inserted_at == *inserted_at)
+            });
+        }
+    }
+
+    /// Forget every result about one user
+    pub fn invalidate_user(&self, user_id: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.generation += 1;
+        entries.map.retain(|key, _| key.user_id != user_id);
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.generation += 1;
+        entries.map.clear();
+        entries.order.clear();
+    }
+
+    pub fn stats(&self) -> CheckCacheStats {
+        CheckCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().map.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::permission::entities::ChannelPermission;
+
+    fn key(user_id: &str, channel_id: &str) -> CheckKey {
+        CheckKey {
+            resource: Resource::Channel(channel_id.to_string()),
+            permission: ChannelPermission::SendMessage.into(),
+            user_id: user_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_hit_and_miss_counters() {
+        let cache = CheckCache::new(10, Duration::from_secs(60));
+        let generation = cache.generation();
+
+        assert_eq!(cache.get(&key("user_1", "channel_1")), None);
+        cache.insert(
+            key("user_1", "channel_1"),
+            PermissionCheckResult::Allowed,
+            generation,
+        );
+        assert_eq!(
+            cache.get(&key("user_1", "channel_1")),
+            Some(PermissionCheckResult::Allowed)
+        );
+
+        assert_eq!(
+            cache.stats(),
+            CheckCacheStats {
+                hits: 1,
+                misses: 1,
+                entries: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_expired_entries_are_misses() {
+        let cache = CheckCache::new(10, Duration::ZERO);
+        cache.insert(
+            key("user_1", "channel_1"),
+            PermissionCheckResult::Allowed,
+            cache.generation(),
+        );
+
+        assert_eq!(cache.get(&key("user_1", "channel_1")), None);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let cache = CheckCache::new(2, Duration::from_secs(60));
+        for channel_id in ["channel_1", "channel_2", "channel_3"] {
+            cache.insert(
+                key("user_1", channel_id),
+                PermissionCheckResult::Denied,
+                cache.generation(),
+            );
+        }
+
+        assert_eq!(cache.stats().entries, 2);
+        assert_eq!(cache.get(&key("user_1", "channel_1")), None);
+        assert!(cache.get(&key("user_1", "channel_3")).is_some());
+    }
+
+    #[test]
+    fn test_invalidate_user_keeps_other_users() {
+        let cache = CheckCache::new(10, Duration::from_secs(60));
+        let generation = cache.generation();
+        cache.insert(
+            key("user_1", "channel_1"),
+            PermissionCheckResult::Allowed,
+            generation,
+        );
+        cache.insert(
+            key("user_2", "channel_1"),
+            PermissionCheckResult::Allowed,
+            generation,
+        );
+
+        cache.invalidate_user("user_1");
+
+        assert_eq!(cache.get(&key("user_1", "channel_1")), None);
+        assert!(cache.get(&key("user_2", "channel_1")).is_some());
+    }
+
+    #[test]
+    fn test_result_computed_before_invalidation_is_dropped() {
+        let cache = CheckCache::new(10, Duration::from_secs(60));
+        let generation = cache.generation();
+
+        cache.clear();
+        cache.insert(
+            key("user_1", "channel_1"),
+            PermissionCheckResult::Allowed,
+            generation,
+        );
+
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -0,0 +1,462 @@
+use crate::{
+    authzed::api::v1::{Relationship, WatchResponse},
+    domain::{
+        common::entities::Consistency,
+        permission::{
+            PermissionError,
+            entities::{
+                BulkCheckItem, CheckPermissionInput, EffectivePermissionsInput,
+                LookupResourcesInput, LookupSubjectsInput, PermissionCheckResult,
+                PermissionExplanation, SubjectsLookup,
+            },
+            port::{PermissionChecker, PermissionRepository},
+        },
+    },
+    infrastructure::{authzed::AuthZedClient, common::authzed::entities::Entity},
+};
+use futures::StreamExt;
+use std::{sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, instrument, warn};
+
+pub mod cache;
+
+pub use cache::{CheckCache, CheckCacheConfig, CheckCacheStats, CheckKey};
+
+/// Delay before resubscribing to the Watch API after the stream broke
+const WATCH_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Permission repository answering checks from an optional in-process cache.
+/// Without a cache, every call goes straight to the wrapped repository.
+#[derive(Clone)]
+pub struct CachedPermissionRepository<A: PermissionRepository> {
+    pub inner: A,
+    pub cache: Option<Arc<CheckCache>>,
+}
+
+impl<A: PermissionRepository> CachedPermissionRepository<A> {
+    pub fn new(inner: A, cache: Option<Arc<CheckCache>>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// The cache to use for this input; freshness requirements bypass it
+    fn cache_for(&self, input: &CheckPermissionInput) -> Option<&CheckCache> {
+        match input.consistency {
+            Consistency::MinimizeLatency => self.cache.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+fn check_key(input: &CheckPermissionInput) -> CheckKey {
+    CheckKey {
+        resource: input.resource.clone(),
+        permission: input.permission,
+        user_id: input.user_id.clone(),
+    }
+}
+
+impl<A: PermissionRepository> PermissionChecker for CachedPermissionRepository<A> {
+    async fn check(
+        &self,
+        input: CheckPermissionInput,
+    ) -> Result<PermissionCheckResult, PermissionError> {
+        let Some(cache) = self.cache_for(&input) else {
+            return self.inner.check(input).await;
+        };
+
+        let key = check_key(&input);
+        if let Some(result) = cache.get(&key) {
+            debug!(resource = ?key.resource, permission = ?key.permission, "Check cache hit");
+            return Ok(result);
+        }
+
+        let generation = cache.generation();
+        let result = self.inner.check(input).await?;
+        cache.insert(key, result.clone(), generation);
+        Ok(result)
+    }
+
+    async fn check_bulk(
+        &self,
+        inputs: Vec<CheckPermissionInput>,
+    ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+        let Some(cache) = self.cache.as_deref() else {
+            return self.inner.check_bulk(inputs).await;
+        };
+
+        // Answer what the cache can, and send the rest as one smaller batch
+        let mut results: Vec<Option<BulkCheckItem>> = Vec::with_capacity(inputs.len());
+        let mut missing = Vec::new();
+        let mut positions = Vec::new();
+        for input in inputs {
+            let cached = match self.cache_for(&input) {
+                Some(_) => cache.get(&check_key(&input)),
+                None => None,
+            };
+            match cached {
+                Some(result) => results.push(Some(BulkCheckItem {
+                    input,
+                    result: Ok(result),
+                })),
+                None => {
+                    positions.push(results.len());
+                    results.push(None);
+                    missing.push(input);
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            let generation = cache.generation();
+            for (position, item) in positions
+                .into_iter()
+                .zip(self.inner.check_bulk(missing).await?)
+            {
+                if let Ok(result) = &item.result {
+                    cache.insert(check_key(&item.input), result.clone(), generation);
+                }
+                results[position] = Some(item);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    async fn lookup_resources(
+        &self,
+        input: LookupResourcesInput,
+    ) -> Result<Vec<String>, PermissionError> {
+        self.inner.lookup_resources(input).await
+    }
+
+    async fn lookup_subjects(
+        &self,
+        input: LookupSubjectsInput,
+    ) -> Result<SubjectsLookup, PermissionError> {
+        self.inner.lookup_subjects(input).await
+    }
+
+    async fn explain(
+        &self,
+        input: CheckPermissionInput,
+    ) -> Result<PermissionExplanation, PermissionError> {
+        self.inner.explain(input).await
+    }
+
+    async fn effective_permissions(
+        &self,
+        input: EffectivePermissionsInput,
+    ) -> Result<u64, PermissionError> {
+        self.inner.effective_permissions(input).await
+    }
+}
+
+impl<A: PermissionRepository> PermissionRepository for CachedPermissionRepository<A> {}
+
+/// Drop the cached results a relationship change can affect.
+/// A relationship ending on a plain user only changes what that user can do;
+/// anything else (role links, subject sets, wildcards) may affect anyone.
+pub fn invalidate(cache: &CheckCache, response: &WatchResponse) {
+    if response.schema_updated {
+        cache.clear();
+        return;
+    }
+
+    let user_type: String = Entity::User.into();
+    for relationship in response
+        .updates
+        .iter()
+        .filter_map(|update| update.relationship.as_ref())
+    {
+        match direct_user(relationship, &user_type) {
+            Some(user_id) => cache.invalidate_user(user_id),
+            None => {
+                cache.clear();
+                return;
+            }
+        }
+    }
+}
+
+fn direct_user<'a>(relationship: &'a Relationship, user_type: &str) -> Option<&'a str> {
+    let subject = relationship.subject.as_ref()?;
+    let object = subject.object.as_ref()?;
+    (object.object_type == user_type
+        && object.object_id != "*"
+        && subject.optional_relation.is_empty())
+    .then_some(object.object_id.as_str())
+}
+
+/// Keep `cache` in sync with SpiceDB for as long as the returned task runs.
+/// Resumes from the last revision seen after a disconnect, and clears the
+/// cache meanwhile since changes may have been missed.
+#[instrument(skip_all)]
+pub fn spawn_invalidation(client: AuthZedClient, cache: Arc<CheckCache>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut cursor = None;
+        loop {
+            match client.watch(Vec::new(), cursor.clone()).await {
+                Ok(mut stream) => {
+                    info!("Check cache subscribed to relationship changes");
+                    while let Some(response) = stream.next().await {
+                        match response {
+                            Ok(response) => {
+                                invalidate(&cache, &response);
+                                if response.changes_through.is_some() {
+                                    cursor = response.changes_through;
+                                }
+                            }
+                            Err(e) => {
+                                warn!(error = %e, "Check cache watch stream broke");
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(error = %e, "Check cache could not watch relationship changes");
+                    // The cursor may be too old to resume from
+                    cursor = None;
+                }
+            }
+
+            cache.clear();
+            tokio::time::sleep(WATCH_RETRY_DELAY).await;
+        }
+    })
+}
+
+/// Log the counters of `cache` every `interval` for as long as the returned task runs
+#[instrument(skip_all)]
+pub fn spawn_stats_logging(cache: Arc<CheckCache>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        // The first tick completes immediately, before anything was cached
+        ticks.tick().await;
+        loop {
+            ticks.tick().await;
+            let stats = cache.stats();
+            info!(
+                hits = stats.hits,
+                misses = stats.misses,
+                entries = stats.entries,
+                "Check cache stats"
+            );
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        authzed::api::v1::{ObjectReference, RelationshipUpdate, SubjectReference},
+        domain::{
+            common::entities::ZedToken,
+            permission::entities::{ChannelPermission, Resource},
+        },
+    };
+    use std::sync::Mutex;
+
+    // Inner repository that allows everything and counts the checks reaching it
+    #[derive(Clone, Default)]
+    struct CountingRepository {
+        checked: Arc<Mutex<Vec<CheckPermissionInput>>>,
+    }
+
+    impl PermissionRepository for CountingRepository {}
+
+    impl PermissionChecker for CountingRepository {
+        async fn check(
+            &self,
+            input: CheckPermissionInput,
+        ) -> Result<PermissionCheckResult, PermissionError> {
+            self.checked.lock().unwrap().push(input);
+            Ok(PermissionCheckResult::Allowed)
+        }
+
+        async fn check_bulk(
+            &self,
+            inputs: Vec<CheckPermissionInput>,
+        ) -> Result<Vec<BulkCheckItem>, PermissionError> {
+            self.checked.lock().unwrap().extend(inputs.iter().cloned());
+            Ok(inputs
+                .into_iter()
+                .map(|input| BulkCheckItem {
+                    input,
+                    result: Ok(PermissionCheckResult::Allowed),
+                })
+                .collect())
+        }
+
+        async fn lookup_resources(
+            &self,
+            _input: LookupResourcesInput,
+        ) -> Result<Vec<String>, PermissionError> {
+            Ok(vec![])
+        }
+
+        async fn lookup_subjects(
+            &self,
+            _input: LookupSubjectsInput,
+        ) -> Result<SubjectsLookup, PermissionError> {
+            Ok(SubjectsLookup::default())
+        }
+
+        async fn explain(
+            &self,
+            _input: CheckPermissionInput,
+        ) -> Result<PermissionExplanation, PermissionError> {
+            Err(PermissionError::ExplainPermissionError {
+                msg: "not supported by this mock".to_string(),
+            })
+        }
+
+        async fn effective_permissions(
+            &self,
+            _input: EffectivePermissionsInput,
+        ) -> Result<u64, PermissionError> {
+            Ok(0)
+        }
+    }
+
+    fn repository() -> (
+        CountingRepository,
+        CachedPermissionRepository<CountingRepository>,
+    ) {
+        let inner = CountingRepository::default();
+        let cache = Arc::new(CheckCache::new(100, Duration::from_secs(60)));
+        (
+            inner.clone(),
+            CachedPermissionRepository::new(inner, Some(cache)),
+        )
+    }
+
+    fn input(user_id: &str, channel_id: &str, consistency: Consistency) -> CheckPermissionInput {
+        CheckPermissionInput {
+            user_id: user_id.to_string(),
+            resource: Resource::Channel(channel_id.to_string()),
+            permission: ChannelPermission::SendMessage.into(),
+            consistency,
+        }
+    }
+
+    fn update(subject_type: &str, subject_id: &str, subject_relation: &str) -> WatchResponse {
+        WatchResponse {
+            updates: vec![RelationshipUpdate {
+                operation: 1,
+                relationship: Some(Relationship {
+                    resource: Some(ObjectReference {
+                        object_type: "role".to_string(),
+                        object_id: "role_1".to_string(),
+                    }),
+                    relation: "member".to_string(),
+                    subject: Some(SubjectReference {
+                        object: Some(ObjectReference {
+                            object_type: subject_type.to_string(),
+                            object_id: subject_id.to_string(),
+                        }),
+                        optional_relation: subject_relation.to_string(),
+                    }),
+                    optional_caveat: None,
+                    optional_expires_at: None,
+                }),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_check_is_served_from_cache() {
+        let (inner, repository) = repository();
+
+        for _ in 0..3 {
+            let result = repository
+                .check(input("user_1", "channel_1", Consistency::default()))
+                .await;
+            assert_eq!(result.unwrap(), PermissionCheckResult::Allowed);
+        }
+
+        assert_eq!(inner.checked.lock().unwrap().len(), 1);
+        let stats = repository.cache.as_ref().unwrap().stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_freshness_bypasses_cache() {
+        let (inner, repository) = repository();
+        repository
+            .check(input("user_1", "channel_1", Consistency::default()))
+            .await
+            .unwrap();
+
+        repository
+            .check(input("user_1", "channel_1", Consistency::FullyConsistent))
+            .await
+            .unwrap();
+        repository
+            .check(input(
+                "user_1",
+                "channel_1",
+                Consistency::AtLeastAsFresh(ZedToken("GhUKEzE3".to_string())),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(inner.checked.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_only_sends_misses() {
+        let (inner, repository) = repository();
+        repository
+            .check(input("user_1", "channel_1", Consistency::default()))
+            .await
+            .unwrap();
+
+        let items = repository
+            .check_bulk(vec![
+                input("user_1", "channel_2", Consistency::default()),
+                input("user_1", "channel_1", Consistency::default()),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].input.resource,
+            Resource::Channel("channel_2".to_string())
+        );
+        assert_eq!(
+            items[1].input.resource,
+            Resource::Channel("channel_1".to_string())
+        );
+        let checked = inner.checked.lock().unwrap();
+        assert_eq!(checked.len(), 2);
+        assert_eq!(
+            checked[1].resource,
+            Resource::Channel("channel_2".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_update_invalidates_affected_users() {
+        let (_, repository) = repository();
+        let cache = repository.cache.clone().unwrap();
+        for user_id in ["user_1", "user_2"] {
+            repository
+                .check(input(user_id, "channel_1", Consistency::default()))
+                .await
+                .unwrap();
+        }
+
+        // A direct user relationship only touches that user
+        invalidate(&cache, &update("user", "user_1", ""));
+        assert_eq!(cache.stats().entries, 1);
+
+        // A subject set may touch anyone
+        invalidate(&cache, &update("role", "role_2", "member"));
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod authzed;
+pub mod cached;
@@ -0,0 +1,99 @@
+use crate::domain::{
+    common::entities::ZedToken,
+    permission_change::{PermissionChangeError, port::CheckpointStore},
+};
+use std::{io::ErrorKind, path::PathBuf};
+use tracing::{debug, instrument};
+
+/// Keeps the last published revision in a file, creating its directory on the
+/// first save. Writes go through a temporary file renamed over the checkpoint,
+/// so a crash never leaves it truncated.
+#[derive(Clone, Debug)]
+pub struct FileCheckpointStore {
+    pub path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    #[instrument(skip(self), fields(path = %self.path.display()))]
+    async fn load(&self) -> Result<Option<ZedToken>, PermissionChangeError> {
+        match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => {
+                let token = content.trim();
+                Ok((!token.is_empty()).then(|| ZedToken(token.to_string())))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                debug!("No checkpoint found, starting from now");
+                Ok(None)
+            }
+            Err(e) => Err(PermissionChangeError::CheckpointError { msg: e.to_string() }),
+        }
+    }
+
+    #[instrument(skip(self), fields(path = %self.path.display()))]
+    async fn save(&self, revision: &ZedToken) -> Result<(), PermissionChangeError> {
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| PermissionChangeError::CheckpointError { msg: e.to_string() })?;
+        }
+
+        tokio::fs::write(&tmp_path, revision.0.as_bytes())
+            .await
+            .map_err(|e| PermissionChangeError::CheckpointError { msg: e.to_string() })?;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .map_err(|e| PermissionChangeError::CheckpointError { msg: e.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("authz_checkpoint_{}_{}", std::process::id(), name))
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_checkpoint() {
+        let store = FileCheckpointStore::new(temp_path("missing"));
+
+        let checkpoint = store.load().await.unwrap();
+
+        assert_eq!(checkpoint, None);
+    }
+
+    #[tokio::test]
+    async fn test_save_then_load_checkpoint() {
+        let path = temp_path("roundtrip");
+        let store = FileCheckpointStore::new(&path);
+
+        store.save(&ZedToken("rev_1".to_string())).await.unwrap();
+        store.save(&ZedToken("rev_2".to_string())).await.unwrap();
+        let checkpoint = store.load().await.unwrap();
+
+        assert_eq!(checkpoint, Some(ZedToken("rev_2".to_string())));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_creates_missing_directory() {
+        let dir = temp_path("nested");
+        let store = FileCheckpointStore::new(dir.join("data").join("watch_checkpoint"));
+
+        store.save(&ZedToken("rev_1".to_string())).await.unwrap();
+        let checkpoint = store.load().await.unwrap();
+
+        assert_eq!(checkpoint, Some(ZedToken("rev_1".to_string())));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
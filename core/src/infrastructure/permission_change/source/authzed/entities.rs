@@ -0,0 +1,435 @@
+use crate::{
+    authzed::api::v1::{
+        ObjectReference, RelationshipUpdate, SubjectReference, WatchResponse,
+        relationship_update::Operation,
+    },
+    domain::{
+        permission::entities::Resource,
+        permission_change::entities::{
+            ChangeOperation, Grantee, PermissionChange, PermissionChangeBatch,
+            PermissionChangeEvent,
+        },
+        permission_override::entities::OverrideTarget,
+    },
+    infrastructure::common::{
+        authzed::beep::{Definition, channel, permission_override, role, server},
+        permissions::{
+            channel_relation_is_allow, channel_relation_to_permission_display,
+            role_relation_is_allow, role_relation_to_permission_display,
+            server_relation_to_permission_display,
+        },
+    },
+};
+use tracing::warn;
+
+/// Translate a Watch response into domain changes; `None` when it carries no
+/// revision to checkpoint
+pub fn to_permission_change_batch(response: &WatchResponse) -> Option<PermissionChangeBatch> {
+    if response.schema_updated {
+        warn!("SpiceDB schema was updated, relationship mappings may be stale");
+    }
+
+    let revision = response.changes_through.clone()?;
+    let changes = response
+        .updates
+        .iter()
+        .filter_map(to_permission_change)
+        .collect();
+
+    Some(PermissionChangeBatch {
+        changes,
+        revision: revision.into(),
+    })
+}
+
+pub fn to_permission_change(update: &RelationshipUpdate) -> Option<PermissionChange> {
+    let operation = match update.operation() {
+        Operation::Create | Operation::Touch => ChangeOperation::Added,
+        Operation::Delete => ChangeOperation::Removed,
+        Operation::Unspecified => return None,
+    };
+    let relationship = update.relationship.as_ref()?;
+    let resource = relationship.resource.as_ref()?;
+    let subject = relationship.subject.as_ref()?;
+    let subject_object = subject.object.as_ref()?;
+
+    let event =
+        to_event(resource, &relationship.relation, subject, subject_object).unwrap_or_else(|| {
+            PermissionChangeEvent::Unmapped {
+                resource: format!("{}:{}", resource.object_type, resource.object_id),
+                relation: relationship.relation.clone(),
+                subject: subject_to_string(subject, subject_object),
+            }
+        });
+
+    Some(PermissionChange { operation, event })
+}
+
+/// The subjects Beep relationships point at
+#[derive(Clone, Copy)]
+enum Subject {
+    User,
+    Server,
+    Channel,
+    RoleMember,
+    Override(permission_override::Relation),
+}
+
+impl Subject {
+    fn parse(subject: &SubjectReference, subject_object: &ObjectReference) -> Option<Self> {
+        let relation = subject.optional_relation.as_str();
+        match Definition::from_name(&subject_object.object_type)? {
+            Definition::User if relation.is_empty() => Some(Subject::User),
+            Definition::Server if relation.is_empty() => Some(Subject::Server),
+            Definition::Channel if relation.is_empty() => Some(Subject::Channel),
+            Definition::Role => match role::Relation::from_name(relation)? {
+                role::Relation::Member => Some(Subject::RoleMember),
+                _ => None,
+            },
+            Definition::PermissionOverride => {
+                permission_override::Relation::from_name(relation).map(Subject::Override)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn to_event(
+    resource: &ObjectReference,
+    relation: &str,
+    subject: &SubjectReference,
+    subject_object: &ObjectReference,
+) -> Option<PermissionChangeEvent> {
+    let resource_id = resource.object_id.clone();
+    let subject_id = subject_object.object_id.clone();
+    let subject = Subject::parse(subject, subject_object)?;
+
+    match Definition::from_name(&resource.object_type)? {
+        Definition::Server => match (server::Relation::from_name(relation)?, subject) {
+            (server::Relation::Owner, Subject::User) => Some(PermissionChangeEvent::ServerOwner {
+                server_id: resource_id,
+                user_id: subject_id,
+            }),
+            (relation, Subject::RoleMember) => Some(PermissionChangeEvent::Permission {
+                resource: Resource::Server(resource_id),
+                permission: server_relation_to_permission_display(relation.as_str())?.to_string(),
+                grantee: Grantee::Role(subject_id),
+                is_allow: true,
+            }),
+            _ => None,
+        },
+        Definition::Role => match (role::Relation::from_name(relation)?, subject) {
+            (role::Relation::Member, Subject::User) => Some(PermissionChangeEvent::RoleMember {
+                role_id: resource_id,
+                user_id: subject_id,
+            }),
+            (role::Relation::Server, Subject::Server) => Some(PermissionChangeEvent::RoleServer {
+                role_id: resource_id,
+                server_id: subject_id,
+            }),
+            (relation, subject) => Some(PermissionChangeEvent::Permission {
+                resource: Resource::Role(resource_id),
+                permission: role_relation_to_permission_display(relation)?.to_string(),
+                grantee: to_grantee(subject, subject_id)?,
+                is_allow: role_relation_is_allow(relation)?,
+            }),
+        },
+        Definition::Channel => match (channel::Relation::from_name(relation)?, subject) {
+            (channel::Relation::Server, Subject::Server) => {
+                Some(PermissionChangeEvent::ChannelServer {
+                    channel_id: resource_id,
+                    server_id: subject_id,
+                })
+            }
+            (relation, subject) => Some(PermissionChangeEvent::Permission {
+                resource: Resource::Channel(resource_id),
+                permission: channel_relation_to_permission_display(relation.as_str())?.to_string(),
+                grantee: to_grantee(subject, subject_id)?,
+                is_allow: channel_relation_is_allow(relation)?,
+            }),
+        },
+        Definition::PermissionOverride => {
+            match (permission_override::Relation::from_name(relation)?, subject) {
+                (permission_override::Relation::Channel, Subject::Channel) => {
+                    Some(PermissionChangeEvent::OverrideChannel {
+                        override_id: resource_id,
+                        channel_id: subject_id,
+                    })
+                }
+                (
+                    relation @ (permission_override::Relation::GrantedTo
+                    | permission_override::Relation::DeniedTo),
+                    subject,
+                ) => {
+                    let target = match to_grantee(subject, subject_id)? {
+                        Grantee::User(user_id) => OverrideTarget::User(user_id),
+                        Grantee::Role(role_id) => OverrideTarget::Role(role_id),
+                        Grantee::Override(_) => return None,
+                    };
+                    Some(PermissionChangeEvent::OverrideTarget {
+                        override_id: resource_id,
+                        target,
+                        is_allow: relation == permission_override::Relation::GrantedTo,
+                    })
+                }
+                _ => None,
+            }
+        }
+        Definition::User => None,
+    }
+}
+
+fn to_grantee(subject: Subject, subject_id: String) -> Option<Grantee> {
+    match subject {
+        Subject::User => Some(Grantee::User(subject_id)),
+        Subject::RoleMember => Some(Grantee::Role(subject_id)),
+        Subject::Override(
+            permission_override::Relation::GrantedTo | permission_override::Relation::DeniedTo,
+        ) => Some(Grantee::Override(subject_id)),
+        _ => None,
+    }
+}
+
+fn subject_to_string(subject: &SubjectReference, object: &ObjectReference) -> String {
+    if subject.optional_relation.is_empty() {
+        format!("{}:{}", object.object_type, object.object_id)
+    } else {
+        format!(
+            "{}:{}#{}",
+            object.object_type, object.object_id, subject.optional_relation
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        authzed::api::v1::{Relationship, ZedToken},
+        domain::common::entities::ZedToken as DomainZedToken,
+    };
+
+    fn update(
+        operation: Operation,
+        resource: (&str, &str),
+        relation: &str,
+        subject: (&str, &str, &str),
+    ) -> RelationshipUpdate {
+        RelationshipUpdate {
+            operation: operation as i32,
+            relationship: Some(Relationship {
+                resource: Some(ObjectReference {
+                    object_type: resource.0.to_string(),
+                    object_id: resource.1.to_string(),
+                }),
+                relation: relation.to_string(),
+                subject: Some(SubjectReference {
+                    object: Some(ObjectReference {
+                        object_type: subject.0.to_string(),
+                        object_id: subject.1.to_string(),
+                    }),
+                    optional_relation: subject.2.to_string(),
+                }),
+                optional_caveat: None,
+                optional_expires_at: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_role_removed_from_server_permission() {
+        let change = to_permission_change(&update(
+            Operation::Delete,
+            ("server", "server_1"),
+            "message_sender",
+            ("role", "role_1", "member"),
+        ))
+        .unwrap();
+
+        assert_eq!(change.operation, ChangeOperation::Removed);
+        assert_eq!(
+            change.event,
+            PermissionChangeEvent::Permission {
+                resource: Resource::Server("server_1".to_string()),
+                permission: "send_message".to_string(),
+                grantee: Grantee::Role("role_1".to_string()),
+                is_allow: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_override_removed_from_channel() {
+        let change = to_permission_change(&update(
+            Operation::Delete,
+            ("permission_override", "override_1"),
+            "channel",
+            ("channel", "channel_1", ""),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            change.event,
+            PermissionChangeEvent::OverrideChannel {
+                override_id: "override_1".to_string(),
+                channel_id: "channel_1".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_channel_deny_through_override() {
+        let change = to_permission_change(&update(
+            Operation::Touch,
+            ("channel", "channel_1"),
+            "view_channel_deny",
+            ("permission_override", "override_1", "denied_to"),
+        ))
+        .unwrap();
+
+        assert_eq!(change.operation, ChangeOperation::Added);
+        assert_eq!(
+            change.event,
+            PermissionChangeEvent::Permission {
+                resource: Resource::Channel("channel_1".to_string()),
+                permission: "view_channel".to_string(),
+                grantee: Grantee::Override("override_1".to_string()),
+                is_allow: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_role_deny_for_user() {
+        let change = to_permission_change(&update(
+            Operation::Touch,
+            ("role", "role_1"),
+            "manage_role_deny",
+            ("user", "user_1", ""),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            change.event,
+            PermissionChangeEvent::Permission {
+                resource: Resource::Role("role_1".to_string()),
+                permission: "manage_role".to_string(),
+                grantee: Grantee::User("user_1".to_string()),
+                is_allow: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_override_target_role() {
+        let change = to_permission_change(&update(
+            Operation::Create,
+            ("permission_override", "override_1"),
+            "granted_to",
+            ("role", "role_1", "member"),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            change.event,
+            PermissionChangeEvent::OverrideTarget {
+                override_id: "override_1".to_string(),
+                target: OverrideTarget::Role("role_1".to_string()),
+                is_allow: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_role_member_and_owner() {
+        let member = to_permission_change(&update(
+            Operation::Create,
+            ("role", "role_1"),
+            "member",
+            ("user", "user_1", ""),
+        ))
+        .unwrap();
+        let owner = to_permission_change(&update(
+            Operation::Create,
+            ("server", "server_1"),
+            "owner",
+            ("user", "user_1", ""),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            member.event,
+            PermissionChangeEvent::RoleMember {
+                role_id: "role_1".to_string(),
+                user_id: "user_1".to_string(),
+            }
+        );
+        assert_eq!(
+            owner.event,
+            PermissionChangeEvent::ServerOwner {
+                server_id: "server_1".to_string(),
+                user_id: "user_1".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_relation_is_unmapped() {
+        let change = to_permission_change(&update(
+            Operation::Create,
+            ("server", "server_1"),
+            "banned",
+            ("user", "user_1", ""),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            change.event,
+            PermissionChangeEvent::Unmapped {
+                resource: "server:server_1".to_string(),
+                relation: "banned".to_string(),
+                subject: "user:user_1".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_unspecified_operation_is_skipped() {
+        let change = to_permission_change(&update(
+            Operation::Unspecified,
+            ("role", "role_1"),
+            "member",
+            ("user", "user_1", ""),
+        ));
+
+        assert!(change.is_none());
+    }
+
+    #[test]
+    fn test_batch_carries_revision() {
+        let response = WatchResponse {
+            updates: vec![update(
+                Operation::Delete,
+                ("role", "role_1"),
+                "member",
+                ("user", "user_1", ""),
+            )],
+            changes_through: Some(ZedToken {
+                token: "rev_1".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let batch = to_permission_change_batch(&response).unwrap();
+
+        assert_eq!(batch.changes.len(), 1);
+        assert_eq!(batch.revision, DomainZedToken("rev_1".to_string()));
+    }
+
+    #[test]
+    fn test_response_without_revision_is_skipped() {
+        let response = WatchResponse::default();
+
+        assert!(to_permission_change_batch(&response).is_none());
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    domain::{
+        common::entities::ZedToken,
+        permission_change::{
+            PermissionChangeError,
+            port::{PermissionChangeSource, PermissionChangeStream},
+        },
+    },
+    infrastructure::authzed::AuthZedClient,
+};
+use futures::{StreamExt, future};
+use tracing::instrument;
+
+pub mod entities;
+
+/// Permission changes read from the SpiceDB Watch API
+#[derive(Clone)]
+pub struct AuthzedPermissionChangeSource {
+    pub authzed_client: AuthZedClient,
+}
+
+impl AuthzedPermissionChangeSource {
+    pub fn new(authzed_client: AuthZedClient) -> Self {
+        Self { authzed_client }
+    }
+}
+
+impl PermissionChangeSource for AuthzedPermissionChangeSource {
+    #[instrument(skip(self))]
+    async fn subscribe(
+        &self,
+        after: Option<ZedToken>,
+    ) -> Result<PermissionChangeStream, PermissionChangeError> {
+        let stream = self
+            .authzed_client
+            .watch(Vec::new(), after.map(Into::into))
+            .await
+            .map_err(|e| PermissionChangeError::WatchError { msg: e.to_string() })?;
+
+        Ok(stream
+            .filter_map(|response| {
+                future::ready(match response {
+                    Ok(response) => entities::to_permission_change_batch(&response).map(Ok),
+                    Err(e) => Some(Err(PermissionChangeError::WatchError {
+                        msg: e.to_string(),
+                    })),
+                })
+            })
+            .boxed())
+    }
+}
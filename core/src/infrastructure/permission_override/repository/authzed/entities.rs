@@ -1,23 +1,64 @@
 use crate::{
-    authzed::api::v1::{ObjectReference, Relationship, RelationshipUpdate, SubjectReference},
-    domain::permission_override::entities::CreatePermissionOverrideInput,
+    authzed::api::v1::{Relationship, RelationshipFilter, SubjectReference},
+    domain::permission_override::entities::{CreatePermissionOverrideInput, OverrideTarget},
     infrastructure::{
-        authzed::entities::Action,
-        common::permissions::{
-            is_channel_permission, parse_permission_bitmask, permission_display_to_channel_relation,
+        authzed::entities::{Action, RelationshipDiff},
+        common::{
+            authzed::{
+                beep::{channel, permission_override},
+                entities::{
+                    Entity, channel::Channel, permission_override::PermissionOverride,
+                    relationship::Object, role::Role, user::User,
+                },
+            },
+            permissions::{
+                is_channel_permission, parse_permission_bitmask,
+                permission_display_to_channel_relation,
+            },
         },
     },
 };
 use permission_translation::models::CapabilityDescriptor;
 use tracing::warn;
 
+/// Relation on permission_override holding the target (granted_to or denied_to)
+fn override_relation(input: &CreatePermissionOverrideInput) -> permission_override::Relation {
+    if input.is_allow {
+        permission_override::Relation::GrantedTo
+    } else {
+        permission_override::Relation::DeniedTo
+    }
+}
+
+/// Create the permission_override->channel relationship
+pub fn create_override_channel_relationship(input: &CreatePermissionOverrideInput) -> Relationship {
+    PermissionOverride::from(input.override_id.clone())
+        .relationship(
+            permission_override::Relation::Channel,
+            Channel::from(input.channel_id.clone()),
+        )
+        .build()
+}
+
+/// Create the relationship storing the target in granted_to or denied_to based on is_allow
+pub fn create_override_target_relationship(input: &CreatePermissionOverrideInput) -> Relationship {
+    let target_subject: SubjectReference = match &input.target {
+        OverrideTarget::User(user_id) => User::from(user_id.clone()).into(),
+        OverrideTarget::Role(role_id) => Role::from(role_id.clone()).members(),
+    };
+
+    PermissionOverride::from(input.override_id.clone())
+        .relationship(override_relation(input), target_subject)
+        .build()
+}
+
 /// Create channel permission relationships pointing to permission_override object
 /// These relationships look like: channel:X#send_message_grant@permission_override:Y#granted_to
 pub fn create_channel_override_relationships(
     input: &CreatePermissionOverrideInput,
     descriptor: &CapabilityDescriptor,
-) -> Vec<RelationshipUpdate> {
-    let mut updates = Vec::new();
+) -> Vec<Relationship> {
+    let mut relationships = Vec::new();
 
     // Parse permission bitmask to get permission names
     let permission_names = parse_permission_bitmask(input.permission_bitmask, descriptor);
@@ -38,47 +79,135 @@ pub fn create_channel_override_relationships(
         })
         .collect();
 
-    // Determine the relation suffix on permission_override (granted_to or denied_to)
-    let override_relation = if input.is_allow {
-        "granted_to"
-    } else {
-        "denied_to"
-    };
-
     // Create relationship for each channel permission
     for permission_name in channel_permissions {
         if let Some(channel_relation) =
             permission_display_to_channel_relation(&permission_name, input.is_allow)
         {
             // Create: channel:X#send_message_grant@permission_override:Y#granted_to
-            let relationship = Relationship {
-                resource: Some(ObjectReference {
-                    object_type: "channel".to_string(),
-                    object_id: input.channel_id.clone(),
-                }),
-                relation: channel_relation,
-                subject: Some(SubjectReference {
-                    object: Some(ObjectReference {
-                        object_type: "permission_override".to_string(),
-                        object_id: input.override_id.clone(),
-                    }),
-                    optional_relation: override_relation.to_string(),
-                }),
-                optional_caveat: None,
-                optional_expires_at: None,
-            };
+            let relationship = Channel::from(input.channel_id.clone())
+                .relationship(
+                    channel_relation,
+                    PermissionOverride::from(input.override_id.clone())
+                        .subject_set(override_relation(input)),
+                )
+                .build();
 
-            updates.push(relationship.create());
+            relationships.push(relationship);
         }
     }
 
-    updates
+    relationships
+}
+
+/// Every relationship of the override: its channel, its target and the channel permissions
+pub fn create_override_relationships(
+    input: &CreatePermissionOverrideInput,
+    descriptor: &CapabilityDescriptor,
+) -> Vec<Relationship> {
+    let mut relationships = vec![
+        create_override_channel_relationship(input),
+        create_override_target_relationship(input),
+    ];
+    relationships.extend(create_channel_override_relationships(input, descriptor));
+    relationships
+}
+
+/// Single write turning the `existing` relationships of the override into the ones of `input`
+pub fn create_override_upsert_diff(
+    input: &CreatePermissionOverrideInput,
+    descriptor: &CapabilityDescriptor,
+    existing: &[Relationship],
+) -> RelationshipDiff {
+    let desired = create_override_relationships(input, descriptor);
+    let mut diff = RelationshipDiff::between(
+        existing,
+        &desired,
+        create_override_vacant_filters(&input.override_id, existing),
+    );
+    if diff.is_empty() {
+        // Nothing changed: still write, so re-sending an upsert returns a fresh token
+        diff.updates
+            .push(create_override_channel_relationship(input).touch());
+    }
+    diff
+}
+
+/// One filter per relation of the override, and per channel relation it can be the subject of,
+/// that is empty in `existing`, so that an upsert also fails when a concurrent one filled it
+/// (e.g. an allow and a deny upsert of the same override racing each other)
+fn create_override_vacant_filters(
+    override_id: &str,
+    existing: &[Relationship],
+) -> Vec<RelationshipFilter> {
+    let permission_override = PermissionOverride::from(override_id.to_string());
+    let is_read = |resource_type: Entity, relation: &str, subject_relation: Option<&str>| {
+        existing.iter().any(|relationship| {
+            relationship
+                .resource
+                .as_ref()
+                .is_some_and(|resource| resource.object_type == resource_type.as_str())
+                && relationship.relation == relation
+                && subject_relation.is_none_or(|subject_relation| {
+                    relationship
+                        .subject
+                        .as_ref()
+                        .is_some_and(|subject| subject.optional_relation == subject_relation)
+                })
+        })
+    };
+
+    let mut filters: Vec<RelationshipFilter> = permission_override::Relation::ALL
+        .iter()
+        .filter(|relation| !is_read(Entity::PermissionOverride, relation.as_str(), None))
+        .map(|&relation| permission_override.filter().relation(relation).build())
+        .collect();
+    for &relation in channel::Relation::ALL {
+        for subject_type in relation.subject_types() {
+            let Some(subject_relation) = subject_type
+                .split_once('#')
+                .filter(|(definition, _)| *definition == Entity::PermissionOverride.as_str())
+                .and_then(|(_, relation)| permission_override::Relation::from_name(relation))
+            else {
+                continue;
+            };
+            if !is_read(
+                Entity::Channel,
+                relation.as_str(),
+                Some(subject_relation.as_str()),
+            ) {
+                filters.push(
+                    Channel::filter_all()
+                        .relation(relation)
+                        .subject(permission_override.subject_set_filter(subject_relation))
+                        .build(),
+                );
+            }
+        }
+    }
+    filters
+}
+
+/// Create a RelationshipFilter matching every relationship where the override is the resource
+/// (permission_override#channel, permission_override#granted_to or permission_override#denied_to)
+pub fn create_override_resource_filter(override_id: &str) -> RelationshipFilter {
+    PermissionOverride::from(override_id.to_string())
+        .filter()
+        .build()
+}
+
+/// Create a RelationshipFilter matching every channel relationship where the override is the subject
+/// (channel:X#*_grant@permission_override:Y#granted_to and channel:X#*_deny@permission_override:Y#denied_to)
+pub fn create_override_subject_filter(override_id: &str) -> RelationshipFilter {
+    Channel::filter_all()
+        .subject(PermissionOverride::from(override_id.to_string()).subject_filter())
+        .build()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::permission_override::entities::OverrideTarget;
+    use crate::authzed::api::v1::precondition;
     use permission_translation::models::CapabilityDescriptor;
 
     fn create_test_descriptor() -> CapabilityDescriptor {
@@ -104,13 +233,11 @@ mod tests {
             target: OverrideTarget::User("user_789".to_string()),
         };
 
-        let updates = create_channel_override_relationships(&input, &descriptor);
+        let relationships = create_channel_override_relationships(&input, &descriptor);
 
-        assert_eq!(updates.len(), 1);
-        // Verify it's a RelationshipUpdate with CREATE operation
-        assert_eq!(updates[0].operation, 1); // CREATE operation
+        assert_eq!(relationships.len(), 1);
 
-        let rel = updates[0].relationship.as_ref().unwrap();
+        let rel = &relationships[0];
         assert_eq!(rel.resource.as_ref().unwrap().object_type, "channel");
         assert_eq!(rel.resource.as_ref().unwrap().object_id, "channel_456");
         assert_eq!(rel.relation, "send_message_grant");
@@ -151,10 +278,10 @@ mod tests {
             target: OverrideTarget::Role("role_999".to_string()),
         };
 
-        let updates = create_channel_override_relationships(&input, &descriptor);
+        let relationships = create_channel_override_relationships(&input, &descriptor);
 
-        assert_eq!(updates.len(), 1);
-        let rel = updates[0].relationship.as_ref().unwrap();
+        assert_eq!(relationships.len(), 1);
+        let rel = &relationships[0];
         assert_eq!(rel.relation, "view_channel_deny");
         assert_eq!(
             rel.subject
@@ -180,10 +307,10 @@ mod tests {
             target: OverrideTarget::User("user_789".to_string()),
         };
 
-        let updates = create_channel_override_relationships(&input, &descriptor);
+        let relationships = create_channel_override_relationships(&input, &descriptor);
 
         // Should create 2 relationships
-        assert_eq!(updates.len(), 2);
+        assert_eq!(relationships.len(), 2);
     }
 
     #[test]
@@ -197,9 +324,44 @@ mod tests {
             target: OverrideTarget::User("user_789".to_string()),
         };
 
-        let updates = create_channel_override_relationships(&input, &descriptor);
+        let relationships = create_channel_override_relationships(&input, &descriptor);
 
         // Should only create 1 relationship for send_message (admin and manage are filtered)
-        assert_eq!(updates.len(), 1);
+        assert_eq!(relationships.len(), 1);
+    }
+
+    #[test]
+    fn test_create_override_upsert_diff_without_changes_touches_channel() {
+        let descriptor = create_test_descriptor();
+        let input = CreatePermissionOverrideInput {
+            override_id: "override_123".to_string(),
+            channel_id: "channel_456".to_string(),
+            permission_bitmask: 0xC0,
+            is_allow: true,
+            target: OverrideTarget::Role("role_999".to_string()),
+        };
+        let existing = create_override_relationships(&input, &descriptor);
+
+        let diff = create_override_upsert_diff(&input, &descriptor, &existing);
+
+        assert_eq!(
+            diff.updates,
+            vec![create_override_channel_relationship(&input).touch()]
+        );
+        // Every relationship read must still be there, and every other relation of the
+        // override, or channel relation pointing at it, must still be empty
+        let must_match = diff
+            .preconditions
+            .iter()
+            .filter(|p| p.operation() == precondition::Operation::MustMatch)
+            .count();
+        assert_eq!(must_match, existing.len());
+        let denied_to_guarded = diff.preconditions.iter().any(|p| {
+            p.operation() == precondition::Operation::MustNotMatch
+                && p.filter.as_ref().unwrap().optional_relation == "denied_to"
+        });
+        assert!(denied_to_guarded);
+        // 3 override relations and 10 channel relations, minus the 4 read
+        assert_eq!(diff.preconditions.len(), existing.len() + 3 + 10 - 4);
     }
 }
@@ -1,18 +1,24 @@
 use crate::{
-    authzed::api::v1::{ObjectReference, Relationship, RelationshipFilter, SubjectReference},
-    domain::permission_override::{
-        PermissionOverrideError,
-        entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput, OverrideTarget},
-        port::PermissionOverrideRepository,
+    domain::{
+        common::entities::{Consistency, ZedToken},
+        permission_override::{
+            PermissionOverrideError,
+            entities::{CreatePermissionOverrideInput, DeletePermissionOverrideInput},
+            port::PermissionOverrideRepository,
+        },
     },
     infrastructure::authzed::AuthZedClient,
 };
 use permission_translation::models::CapabilityDescriptor;
 use std::sync::Arc;
+use tonic::Code;
 use tracing::{info, instrument, warn};
 
 pub mod entities;
 
+/// Upserts racing with others on the same override give up after this many writes
+const MAX_UPSERT_ATTEMPTS: u32 = 5;
+
 #[derive(Clone)]
 pub struct AuthzedPermissionOverrideRepository {
     pub authzed_client: AuthZedClient,
@@ -36,7 +42,7 @@ impl PermissionOverrideRepository for AuthzedPermissionOverrideRepository {
     async fn create(
         &self,
         input: CreatePermissionOverrideInput,
-    ) -> Result<(), PermissionOverrideError> {
+    ) -> Result<ZedToken, PermissionOverrideError> {
         info!(
             override_id = %input.override_id,
             channel_id = %input.channel_id,
@@ -45,98 +51,67 @@ impl PermissionOverrideRepository for AuthzedPermissionOverrideRepository {
             "Creating permission override relationships in AuthZed"
         );
 
-        // Create permission_override object with channel relationship
-        let override_channel_rel = Relationship {
-            resource: Some(ObjectReference {
-                object_type: "permission_override".to_string(),
-                object_id: input.override_id.clone(),
-            }),
-            relation: "channel".to_string(),
-            subject: Some(SubjectReference {
-                object: Some(ObjectReference {
-                    object_type: "channel".to_string(),
-                    object_id: input.channel_id.clone(),
-                }),
-                optional_relation: String::new(),
-            }),
-            optional_caveat: None,
-            optional_expires_at: None,
-        };
-
-        self.authzed_client
-            .create_relationship(override_channel_rel)
-            .await
-            .map_err(|e| PermissionOverrideError::CreateOverrideError { msg: e.to_string() })?;
-
-        // Store target in granted_to or denied_to based on is_allow
-        let target_relation_name = if input.is_allow {
-            "granted_to"
-        } else {
-            "denied_to"
-        };
-
-        let target_subject = match &input.target {
-            OverrideTarget::User(user_id) => SubjectReference {
-                object: Some(ObjectReference {
-                    object_type: "user".to_string(),
-                    object_id: user_id.clone(),
-                }),
-                optional_relation: String::new(),
-            },
-            OverrideTarget::Role(role_id) => SubjectReference {
-                object: Some(ObjectReference {
-                    object_type: "role".to_string(),
-                    object_id: role_id.clone(),
-                }),
-                optional_relation: "member".to_string(),
-            },
-        };
-
-        let target_relation = Relationship {
-            resource: Some(ObjectReference {
-                object_type: "permission_override".to_string(),
-                object_id: input.override_id.clone(),
-            }),
-            relation: target_relation_name.to_string(),
-            subject: Some(target_subject),
-            optional_caveat: None,
-            optional_expires_at: None,
-        };
-
-        self.authzed_client
-            .create_relationship(target_relation)
-            .await
-            .map_err(|e| PermissionOverrideError::CreateOverrideError { msg: e.to_string() })?;
-
-        // Create channel permission relationships pointing to permission_override
-        // channel:X#send_message_grant@permission_override:Y#granted_to
-        let channel_updates =
-            entities::create_channel_override_relationships(&input, &self.permissions_descriptor);
-
-        if channel_updates.is_empty() {
-            warn!(
-                override_id = %input.override_id,
-                channel_id = %input.channel_id,
-                permission_bitmask = %input.permission_bitmask,
-                "No valid channel permissions found in bitmask, no permission relationships created"
+        // Replace every relationship of the override in a single write computed from the
+        // current ones, so flipping allow/deny or shrinking the bitmask leaves nothing behind.
+        // Preconditions make the write fail if another upsert changed them meanwhile,
+        // in which case the diff is computed again from a fresh read.
+        let filters = [
+            entities::create_override_resource_filter(&input.override_id),
+            entities::create_override_subject_filter(&input.override_id),
+        ];
+        let mut attempt = 1;
+        let written_at = loop {
+            let mut existing = Vec::new();
+            for filter in &filters {
+                existing.extend(
+                    self.authzed_client
+                        .read_relationships(filter.clone(), Consistency::FullyConsistent)
+                        .await
+                        .map_err(|e| PermissionOverrideError::CreateOverrideError {
+                            msg: e.to_string(),
+                        })?,
+                );
+            }
+            let diff = entities::create_override_upsert_diff(
+                &input,
+                &self.permissions_descriptor,
+                &existing,
             );
-        } else {
-            // Write all channel permission relationships in bulk
-            self.authzed_client
-                .write_relationships(channel_updates)
+
+            match self
+                .authzed_client
+                .write_relationships_with_preconditions(diff.updates, diff.preconditions)
                 .await
-                .map_err(|e| PermissionOverrideError::CreateOverrideError { msg: e.to_string() })?;
-        }
+            {
+                Ok(written_at) => break written_at,
+                Err(e)
+                    if e.code() == Some(Code::FailedPrecondition)
+                        && attempt < MAX_UPSERT_ATTEMPTS =>
+                {
+                    warn!(
+                        override_id = %input.override_id,
+                        attempt,
+                        "Permission override changed concurrently, retrying upsert"
+                    );
+                    attempt += 1;
+                }
+                Err(e) => {
+                    return Err(PermissionOverrideError::CreateOverrideError {
+                        msg: e.to_string(),
+                    });
+                }
+            }
+        };
 
-        info!("Permission override object and relationships created successfully in AuthZed");
-        Ok(())
+        info!(written_at = %written_at.token, "Permission override object and relationships upserted successfully in AuthZed");
+        Ok(written_at.into())
     }
 
     #[instrument(skip(self), fields(override_id = %input.override_id))]
     async fn delete(
         &self,
         input: DeletePermissionOverrideInput,
-    ) -> Result<(), PermissionOverrideError> {
+    ) -> Result<ZedToken, PermissionOverrideError> {
         info!(
             override_id = %input.override_id,
             "Deleting permission override object and relationships in AuthZed"
@@ -146,15 +121,11 @@ impl PermissionOverrideRepository for AuthzedPermissionOverrideRepository {
         // This deletes:
         // - permission_override#channel
         // - permission_override#granted_to or permission_override#denied_to
-        let override_resource_filter = RelationshipFilter {
-            resource_type: "permission_override".to_string(),
-            optional_resource_id: input.override_id.clone(),
-            optional_relation: String::new(),
-            optional_subject_filter: None,
-            optional_resource_id_prefix: String::new(),
-        };
+        let override_resource_filter =
+            entities::create_override_resource_filter(&input.override_id);
 
-        self.authzed_client
+        let resources_deleted = self
+            .authzed_client
             .filtered_delete(override_resource_filter)
             .await
             .map_err(|e| PermissionOverrideError::DeleteOverrideError { msg: e.to_string() })?;
@@ -162,24 +133,204 @@ impl PermissionOverrideRepository for AuthzedPermissionOverrideRepository {
         // Delete all channel relationships where permission_override is the subject
         // This deletes: channel:X#*_grant@permission_override:Y#granted_to
         //           and: channel:X#*_deny@permission_override:Y#denied_to
-        let override_subject_filter = RelationshipFilter {
-            resource_type: "channel".to_string(),
-            optional_resource_id: String::new(),
-            optional_relation: String::new(),
-            optional_subject_filter: Some(crate::authzed::api::v1::SubjectFilter {
-                subject_type: "permission_override".to_string(),
-                optional_subject_id: input.override_id.clone(),
-                optional_relation: None,
-            }),
-            optional_resource_id_prefix: String::new(),
-        };
+        let override_subject_filter = entities::create_override_subject_filter(&input.override_id);
 
-        self.authzed_client
+        let subjects_deleted = self
+            .authzed_client
             .filtered_delete(override_subject_filter)
             .await
             .map_err(|e| PermissionOverrideError::DeleteOverrideError { msg: e.to_string() })?;
 
-        info!("Permission override object and all relationships deleted successfully in AuthZed");
-        Ok(())
+        info!(
+            deleted_at = %subjects_deleted.deleted_at.token,
+            deleted_count = resources_deleted.deleted_count + subjects_deleted.deleted_count,
+            "Permission override object and all relationships deleted successfully in AuthZed"
+        );
+        Ok(subjects_deleted.deleted_at.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::permission_override::entities::OverrideTarget,
+        infrastructure::authzed::fake::{FakeSpiceDb, fast_retries},
+    };
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        let mut descriptor = CapabilityDescriptor::new();
+        descriptor.insert("view_channel".to_string(), 0x40);
+        descriptor.insert("send_message".to_string(), 0x80);
+        descriptor
+    }
+
+    fn create_override_input(
+        permission_bitmask: u64,
+        is_allow: bool,
+    ) -> CreatePermissionOverrideInput {
+        CreatePermissionOverrideInput {
+            override_id: "override_123".to_string(),
+            channel_id: "channel_456".to_string(),
+            permission_bitmask,
+            is_allow,
+            target: OverrideTarget::Role("role_999".to_string()),
+        }
+    }
+
+    async fn repository(spicedb: &Arc<FakeSpiceDb>) -> AuthzedPermissionOverrideRepository {
+        AuthzedPermissionOverrideRepository::new(
+            spicedb.serve(fast_retries(0)).await,
+            Arc::new(create_test_descriptor()),
+        )
+    }
+
+    /// `relation@subject_relation` of every stored relationship, sorted
+    fn relations(spicedb: &FakeSpiceDb) -> Vec<String> {
+        let mut relations: Vec<_> = spicedb
+            .relationships()
+            .into_iter()
+            .map(|relationship| {
+                format!(
+                    "{}@{}",
+                    relationship.relation,
+                    relationship.subject.unwrap().optional_relation
+                )
+            })
+            .collect();
+        relations.sort();
+        relations
+    }
+
+    #[tokio::test]
+    async fn test_create_flips_allow_to_deny() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        repository
+            .create(create_override_input(0xC0, true))
+            .await
+            .unwrap();
+
+        // Act
+        repository
+            .create(create_override_input(0xC0, false))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            relations(&spicedb),
+            vec![
+                "channel@",
+                "denied_to@member",
+                "send_message_deny@denied_to",
+                "view_channel_deny@denied_to",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_shrinks_bitmask() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        repository
+            .create(create_override_input(0xC0, true))
+            .await
+            .unwrap();
+
+        // Act
+        repository
+            .create(create_override_input(0x80, true))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            relations(&spicedb),
+            vec![
+                "channel@",
+                "granted_to@member",
+                "send_message_grant@granted_to"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_twice_is_idempotent() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        let first = repository
+            .create(create_override_input(0x80, true))
+            .await
+            .unwrap();
+
+        // Act
+        let second = repository
+            .create(create_override_input(0x80, true))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_ne!(first, second);
+        assert_eq!(spicedb.relationships().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_create_retries_after_concurrent_change() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        // Both reads succeed, the first write loses the race against another upsert
+        spicedb.fail_after(2, 1, Code::FailedPrecondition);
+
+        // Act
+        let result = repository.create(create_override_input(0x80, false)).await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(spicedb.relationships().len(), 3);
+        // Two reads and a write per attempt
+        assert_eq!(spicedb.calls(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_allow_and_deny_upserts_do_not_merge() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        // Both upserts read an empty override before either writes
+        spicedb.hold_writes(2);
+
+        // Act
+        let (allow, deny) = tokio::join!(
+            repository.create(create_override_input(0x80, true)),
+            repository.create(create_override_input(0x40, false)),
+        );
+
+        // Assert
+        assert!(allow.is_ok());
+        assert!(deny.is_ok());
+        let relations = relations(&spicedb);
+        assert!(
+            relations
+                == vec![
+                    "channel@",
+                    "granted_to@member",
+                    "send_message_grant@granted_to"
+                ]
+                || relations
+                    == vec![
+                        "channel@",
+                        "denied_to@member",
+                        "view_channel_deny@denied_to"
+                    ],
+            "{:?}",
+            relations
+        );
+        // Two reads and a write each, then again for the upsert that lost
+        assert_eq!(spicedb.calls(), 9);
     }
 }
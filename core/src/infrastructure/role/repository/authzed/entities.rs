@@ -1,13 +1,18 @@
 use crate::{
-    authzed::api::v1::{
-        ObjectReference, Relationship, RelationshipFilter, RelationshipUpdate, SubjectReference,
-    },
+    authzed::api::v1::{Relationship, RelationshipFilter, RelationshipUpdate},
     domain::role::entities::{
-        AssignMemberInput, CreateRoleInput, DeleteRoleInput, RemoveMemberInput,
+        AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListServerRolesInput,
+        RemoveMemberInput,
     },
     infrastructure::{
-        authzed::entities::Action,
-        common::permissions::{parse_permission_bitmask, permission_display_to_server_relation},
+        authzed::entities::{Action, RelationshipDiff},
+        common::{
+            authzed::{
+                beep::{role, server},
+                entities::{relationship::Object, role::Role, server::Server, user::User},
+            },
+            permissions::{parse_permission_bitmask, permission_display_to_server_relation},
+        },
     },
 };
 use permission_translation::models::CapabilityDescriptor;
@@ -15,22 +20,12 @@ use tracing::warn;
 
 /// Create the base role->server relationship
 pub fn create_role_server_relationship(input: &CreateRoleInput) -> Relationship {
-    Relationship {
-        resource: Some(ObjectReference {
-            object_type: "role".to_string(),
-            object_id: input.role_id.clone(),
-        }),
-        relation: "server".to_string(),
-        subject: Some(SubjectReference {
-            object: Some(ObjectReference {
-                object_type: "server".to_string(),
-                object_id: input.server_id.clone(),
-            }),
-            optional_relation: String::new(),
-        }),
-        optional_caveat: None,
-        optional_expires_at: None,
-    }
+    Role::from(input.role_id.clone())
+        .relationship(
+            role::Relation::Server,
+            Server::from(input.server_id.clone()),
+        )
+        .build()
 }
 
 /// Convert CreateRoleInput to a vector of RelationshipUpdates
@@ -39,11 +34,21 @@ pub fn create_role_to_updates(
     input: &CreateRoleInput,
     descriptor: &CapabilityDescriptor,
 ) -> Vec<RelationshipUpdate> {
-    let mut updates = Vec::new();
+    let mut updates = vec![create_role_server_relationship(input).touch()];
+    updates.extend(
+        create_role_permission_relationships(input, descriptor)
+            .iter()
+            .map(Action::touch),
+    );
+    updates
+}
 
-    // Create base role->server relationship
-    let role_server_relationship = create_role_server_relationship(input);
-    updates.push(role_server_relationship.touch());
+/// The server#relation@role#member relationships granting the role its permissions
+pub fn create_role_permission_relationships(
+    input: &CreateRoleInput,
+    descriptor: &CapabilityDescriptor,
+) -> Vec<Relationship> {
+    let mut relationships = Vec::new();
 
     // Parse permission bitmask to get permission names
     let permission_names = parse_permission_bitmask(input.permissions_bitmask, descriptor);
@@ -51,23 +56,11 @@ pub fn create_role_to_updates(
     // For each permission, create server#relation@role#member relationship
     for permission_name in permission_names {
         if let Some(server_relation) = permission_display_to_server_relation(&permission_name) {
-            let relationship = Relationship {
-                resource: Some(ObjectReference {
-                    object_type: "server".to_string(),
-                    object_id: input.server_id.clone(),
-                }),
-                relation: server_relation.to_string(),
-                subject: Some(SubjectReference {
-                    object: Some(ObjectReference {
-                        object_type: "role".to_string(),
-                        object_id: input.role_id.clone(),
-                    }),
-                    optional_relation: "member".to_string(),
-                }),
-                optional_caveat: None,
-                optional_expires_at: None,
-            };
-            updates.push(relationship.touch());
+            relationships.push(
+                Server::from(input.server_id.clone())
+                    .relationship(server_relation, Role::from(input.role_id.clone()).members())
+                    .build(),
+            );
         } else {
             warn!(
                 permission_name = %permission_name,
@@ -76,80 +69,92 @@ pub fn create_role_to_updates(
         }
     }
 
-    updates
+    relationships
+}
+
+/// Single write turning the `existing` server permission relationships of the role into
+/// the ones of `input`, and touching the role->server relationship
+pub fn create_role_upsert_diff(
+    input: &CreateRoleInput,
+    descriptor: &CapabilityDescriptor,
+    existing: &[Relationship],
+) -> RelationshipDiff {
+    let desired = create_role_permission_relationships(input, descriptor);
+    let mut diff = RelationshipDiff::between(
+        existing,
+        &desired,
+        create_role_vacant_permission_filters(input, existing),
+    );
+    diff.updates
+        .insert(0, create_role_server_relationship(input).touch());
+    diff
+}
+
+/// One filter per server relation the role could hold but does not in `existing`,
+/// so that an upsert also fails when another one granted the role a new permission
+fn create_role_vacant_permission_filters(
+    input: &CreateRoleInput,
+    existing: &[Relationship],
+) -> Vec<RelationshipFilter> {
+    let role = Role::from(input.role_id.clone());
+    server::Relation::ALL
+        .iter()
+        .filter(|relation| relation.subject_types().contains(&"role#member"))
+        .filter(|relation| {
+            !existing
+                .iter()
+                .any(|relationship| relationship.relation == relation.as_str())
+        })
+        .map(|&relation| {
+            Server::filter_all()
+                .relation(relation)
+                .subject(role.members_filter())
+                .build()
+        })
+        .collect()
 }
 
 /// Create a RelationshipFilter for deleting all relationships where role is the resource
 pub fn create_role_resource_filter(input: &DeleteRoleInput) -> RelationshipFilter {
-    RelationshipFilter {
-        resource_type: "role".to_string(),
-        optional_resource_id: input.role_id.clone(),
-        optional_relation: String::new(),
-        optional_subject_filter: None,
-        optional_resource_id_prefix: String::new(),
-    }
+    Role::from(input.role_id.clone()).filter().build()
 }
 
 /// Create a RelationshipFilter for deleting all server relationships where role is the subject
 pub fn create_role_subject_filter(input: &DeleteRoleInput) -> RelationshipFilter {
-    RelationshipFilter {
-        resource_type: "server".to_string(),
-        optional_resource_id: String::new(),
-        optional_relation: String::new(),
-        optional_subject_filter: Some(crate::authzed::api::v1::SubjectFilter {
-            subject_type: "role".to_string(),
-            optional_subject_id: input.role_id.clone(),
-            optional_relation: Some(crate::authzed::api::v1::subject_filter::RelationFilter {
-                relation: "member".to_string(),
-            }),
-        }),
-        optional_resource_id_prefix: String::new(),
-    }
+    Server::filter_all()
+        .subject(Role::from(input.role_id.clone()).members_filter())
+        .build()
+}
+
+/// Create a RelationshipFilter matching every role->server relationship of a server
+pub fn create_server_roles_filter(input: &ListServerRolesInput) -> RelationshipFilter {
+    Role::filter_all()
+        .relation(role::Relation::Server)
+        .subject(Server::from(input.server_id.clone()).subject_filter())
+        .build()
 }
 
 /// Convert AssignMemberInput to Relationship
 pub fn assign_member_to_relationship(input: &AssignMemberInput) -> Relationship {
-    Relationship {
-        resource: Some(ObjectReference {
-            object_type: "role".to_string(),
-            object_id: input.role_id.clone(),
-        }),
-        relation: "member".to_string(),
-        subject: Some(SubjectReference {
-            object: Some(ObjectReference {
-                object_type: "user".to_string(),
-                object_id: input.user_id.clone(),
-            }),
-            optional_relation: String::new(),
-        }),
-        optional_caveat: None,
-        optional_expires_at: None,
-    }
+    Role::from(input.role_id.clone())
+        .relationship(role::Relation::Member, User::from(input.user_id.clone()))
+        .build()
 }
 
 /// Convert RemoveMemberInput to Relationship (for deletion)
 pub fn remove_member_to_relationship(input: &RemoveMemberInput) -> Relationship {
-    Relationship {
-        resource: Some(ObjectReference {
-            object_type: "role".to_string(),
-            object_id: input.role_id.clone(),
-        }),
-        relation: "member".to_string(),
-        subject: Some(SubjectReference {
-            object: Some(ObjectReference {
-                object_type: "user".to_string(),
-                object_id: input.user_id.clone(),
-            }),
-            optional_relation: String::new(),
-        }),
-        optional_caveat: None,
-        optional_expires_at: None,
-    }
+    Role::from(input.role_id.clone())
+        .relationship(role::Relation::Member, User::from(input.user_id.clone()))
+        .build()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        authzed::api::v1::{precondition, relationship_update::Operation},
+        domain::common::entities::Consistency,
+    };
     use permission_translation::models::CapabilityDescriptor;
 
     fn create_test_descriptor() -> CapabilityDescriptor {
@@ -231,6 +236,79 @@ mod tests {
         assert_eq!(updates.len(), 3);
     }
 
+    #[test]
+    fn test_create_role_upsert_diff_replaces_permissions() {
+        let descriptor = create_test_descriptor();
+        let existing = create_role_permission_relationships(
+            &CreateRoleInput {
+                role_id: "role_123".to_string(),
+                server_id: "server_456".to_string(),
+                permissions_bitmask: 0x88, // send_message | create_invitation
+            },
+            &descriptor,
+        );
+        let input = CreateRoleInput {
+            role_id: "role_123".to_string(),
+            server_id: "server_456".to_string(),
+            permissions_bitmask: 0x81, // admin | send_message
+        };
+
+        let diff = create_role_upsert_diff(&input, &descriptor, &existing);
+
+        // role->server touch, create_invitation delete, admin touch; send_message is kept
+        let updates: Vec<_> = diff
+            .updates
+            .iter()
+            .map(|u| (u.operation(), u.relationship.clone().unwrap().relation))
+            .collect();
+        assert_eq!(
+            updates,
+            vec![
+                (Operation::Touch, "server".to_string()),
+                (Operation::Delete, "invitation_creator".to_string()),
+                (Operation::Touch, "administrator".to_string()),
+            ]
+        );
+        // Both existing relationships must still be there, and every permission relation
+        // the role does not hold, admin included, must still be missing
+        let must_match = diff
+            .preconditions
+            .iter()
+            .filter(|p| p.operation() == precondition::Operation::MustMatch)
+            .count();
+        assert_eq!(must_match, 2);
+        assert_eq!(diff.preconditions.len(), 2 + 12);
+    }
+
+    #[test]
+    fn test_create_role_upsert_diff_without_changes() {
+        let descriptor = create_test_descriptor();
+        let input = CreateRoleInput {
+            role_id: "role_123".to_string(),
+            server_id: "server_456".to_string(),
+            permissions_bitmask: 0x88,
+        };
+        let existing = create_role_permission_relationships(&input, &descriptor);
+
+        let diff = create_role_upsert_diff(&input, &descriptor, &existing);
+
+        assert_eq!(diff.updates.len(), 1);
+        // Still guarded: the write fails if any permission relation of the role changed
+        let mut guarded: Vec<_> = diff
+            .preconditions
+            .iter()
+            .map(|p| p.filter.clone().unwrap().optional_relation)
+            .collect();
+        guarded.sort();
+        let mut permission_relations: Vec<_> = server::Relation::ALL
+            .iter()
+            .filter(|relation| **relation != server::Relation::Owner)
+            .map(|relation| relation.as_str().to_string())
+            .collect();
+        permission_relations.sort();
+        assert_eq!(guarded, permission_relations);
+    }
+
     #[test]
     fn test_create_role_resource_filter() {
         let input = DeleteRoleInput {
@@ -258,6 +336,24 @@ mod tests {
         assert_eq!(subject_filter.optional_subject_id, "role_123");
     }
 
+    #[test]
+    fn test_create_server_roles_filter() {
+        let input = ListServerRolesInput {
+            server_id: "server_456".to_string(),
+            consistency: Consistency::default(),
+        };
+
+        let filter = create_server_roles_filter(&input);
+
+        assert_eq!(filter.resource_type, "role");
+        assert_eq!(filter.optional_resource_id, "");
+        assert_eq!(filter.optional_relation, "server");
+        let subject_filter = filter.optional_subject_filter.unwrap();
+        assert_eq!(subject_filter.subject_type, "server");
+        assert_eq!(subject_filter.optional_subject_id, "server_456");
+        assert!(subject_filter.optional_relation.is_none());
+    }
+
     #[test]
     fn test_assign_member_to_relationship() {
         let input = AssignMemberInput {
@@ -1,17 +1,27 @@
 use crate::{
-    domain::role::{
-        RoleError,
-        entities::{AssignMemberInput, CreateRoleInput, DeleteRoleInput, RemoveMemberInput},
-        port::RoleRepository,
+    domain::{
+        common::entities::{Consistency, ZedToken},
+        role::{
+            RoleError,
+            entities::{
+                AssignMemberInput, CreateRoleInput, DeleteRoleInput, ListServerRolesInput,
+                RemoveMemberInput,
+            },
+            port::RoleRepository,
+        },
     },
-    infrastructure::authzed::AuthZedClient,
+    infrastructure::authzed::{AuthZedClient, READ_PAGE_SIZE},
 };
 use permission_translation::models::CapabilityDescriptor;
 use std::sync::Arc;
+use tonic::Code;
 use tracing::{info, instrument, warn};
 
 pub mod entities;
 
+/// Upserts racing with others on the same role give up after this many writes
+const MAX_UPSERT_ATTEMPTS: u32 = 5;
+
 #[derive(Clone)]
 pub struct AuthzedRoleRepository {
     pub authzed_client: AuthZedClient,
@@ -32,7 +42,7 @@ impl AuthzedRoleRepository {
 
 impl RoleRepository for AuthzedRoleRepository {
     #[instrument(skip(self), fields(role_id = %input.role_id, server_id = %input.server_id, permissions_bitmask = %input.permissions_bitmask))]
-    async fn create(&self, input: CreateRoleInput) -> Result<(), RoleError> {
+    async fn create(&self, input: CreateRoleInput) -> Result<ZedToken, RoleError> {
         info!(
             role_id = %input.role_id,
             server_id = %input.server_id,
@@ -40,48 +50,49 @@ impl RoleRepository for AuthzedRoleRepository {
             "Creating/updating role relationships in AuthZed"
         );
 
-        // First, delete all existing server permission relations for this role (for update semantics)
-        // This ensures we replace permissions instead of merging them
-        info!(
-            role_id = %input.role_id,
-            "Deleting existing server permission relationships for role"
-        );
+        // Replace the permissions in a single write computed from the current ones.
+        // Preconditions make the write fail if another upsert changed them meanwhile,
+        // in which case the diff is computed again from a fresh read.
         let subject_filter = entities::create_role_subject_filter(&DeleteRoleInput {
             role_id: input.role_id.clone(),
         });
-        self.authzed_client
-            .filtered_delete(subject_filter)
-            .await
-            .map_err(|e| RoleError::CreateRoleError { msg: e.to_string() })?;
-
-        // Convert input to relationship updates
-        let updates = entities::create_role_to_updates(&input, &self.permissions_descriptor);
-
-        if updates.is_empty() {
-            warn!(
-                role_id = %input.role_id,
-                "No valid relationships to create for role"
-            );
-            // Still create the base role->server relationship
-            let base_relationship = entities::create_role_server_relationship(&input);
-            self.authzed_client
-                .touch_relationship(base_relationship)
+        let mut attempt = 1;
+        let written_at = loop {
+            let existing = self
+                .authzed_client
+                .read_relationships(subject_filter.clone(), Consistency::FullyConsistent)
                 .await
                 .map_err(|e| RoleError::CreateRoleError { msg: e.to_string() })?;
-        } else {
-            // Write all relationships in bulk
-            self.authzed_client
-                .write_relationships(updates)
+            let diff =
+                entities::create_role_upsert_diff(&input, &self.permissions_descriptor, &existing);
+
+            match self
+                .authzed_client
+                .write_relationships_with_preconditions(diff.updates, diff.preconditions)
                 .await
-                .map_err(|e| RoleError::CreateRoleError { msg: e.to_string() })?;
-        }
+            {
+                Ok(written_at) => break written_at,
+                Err(e)
+                    if e.code() == Some(Code::FailedPrecondition)
+                        && attempt < MAX_UPSERT_ATTEMPTS =>
+                {
+                    warn!(
+                        role_id = %input.role_id,
+                        attempt,
+                        "Role permissions changed concurrently, retrying upsert"
+                    );
+                    attempt += 1;
+                }
+                Err(e) => return Err(RoleError::CreateRoleError { msg: e.to_string() }),
+            }
+        };
 
-        info!("Role relationships created/updated successfully in AuthZed");
-        Ok(())
+        info!(written_at = %written_at.token, "Role relationships created/updated successfully in AuthZed");
+        Ok(written_at.into())
     }
 
     #[instrument(skip(self), fields(role_id = %input.role_id))]
-    async fn delete(&self, input: DeleteRoleInput) -> Result<(), RoleError> {
+    async fn delete(&self, input: DeleteRoleInput) -> Result<ZedToken, RoleError> {
         info!(
             role_id = %input.role_id,
             "Deleting role relationships in AuthZed"
@@ -89,24 +100,31 @@ impl RoleRepository for AuthzedRoleRepository {
 
         // Delete all relationships where role is the resource
         let resource_filter = entities::create_role_resource_filter(&input);
-        self.authzed_client
+        let resources_deleted = self
+            .authzed_client
             .filtered_delete(resource_filter)
             .await
             .map_err(|e| RoleError::DeleteRoleError { msg: e.to_string() })?;
 
         // Delete all server permission relations where this role is the subject
+        // Deleted last, so its token covers both deletions
         let subject_filter = entities::create_role_subject_filter(&input);
-        self.authzed_client
+        let subjects_deleted = self
+            .authzed_client
             .filtered_delete(subject_filter)
             .await
             .map_err(|e| RoleError::DeleteRoleError { msg: e.to_string() })?;
 
-        info!("Role relationships deleted successfully in AuthZed");
-        Ok(())
+        info!(
+            deleted_at = %subjects_deleted.deleted_at.token,
+            deleted_count = resources_deleted.deleted_count + subjects_deleted.deleted_count,
+            "Role relationships deleted successfully in AuthZed"
+        );
+        Ok(subjects_deleted.deleted_at.into())
     }
 
     #[instrument(skip(self), fields(user_id = %input.user_id, role_id = %input.role_id))]
-    async fn assign_member(&self, input: AssignMemberInput) -> Result<(), RoleError> {
+    async fn assign_member(&self, input: AssignMemberInput) -> Result<ZedToken, RoleError> {
         info!(
             user_id = %input.user_id,
             role_id = %input.role_id,
@@ -114,17 +132,18 @@ impl RoleRepository for AuthzedRoleRepository {
         );
 
         let relationship = entities::assign_member_to_relationship(&input);
-        self.authzed_client
+        let written_at = self
+            .authzed_client
             .create_relationship(relationship)
             .await
             .map_err(|e| RoleError::AssignMemberError { msg: e.to_string() })?;
 
         info!("Member assigned to role successfully in AuthZed");
-        Ok(())
+        Ok(written_at.into())
     }
 
     #[instrument(skip(self), fields(user_id = %input.user_id, role_id = %input.role_id))]
-    async fn remove_member(&self, input: RemoveMemberInput) -> Result<(), RoleError> {
+    async fn remove_member(&self, input: RemoveMemberInput) -> Result<ZedToken, RoleError> {
         info!(
             user_id = %input.user_id,
             role_id = %input.role_id,
@@ -132,12 +151,142 @@ impl RoleRepository for AuthzedRoleRepository {
         );
 
         let relationship = entities::remove_member_to_relationship(&input);
-        self.authzed_client
+        let written_at = self
+            .authzed_client
             .delete_relationship(relationship)
             .await
             .map_err(|e| RoleError::RemoveMemberError { msg: e.to_string() })?;
 
         info!("Member removed from role successfully in AuthZed");
-        Ok(())
+        Ok(written_at.into())
+    }
+
+    #[instrument(skip(self), fields(server_id = %input.server_id))]
+    async fn list_by_server(&self, input: ListServerRolesInput) -> Result<Vec<String>, RoleError> {
+        info!(
+            server_id = %input.server_id,
+            "Listing server roles in AuthZed"
+        );
+
+        let filter = entities::create_server_roles_filter(&input);
+        let mut roles: Vec<String> = Vec::new();
+        self.authzed_client
+            .for_each_relationship_page(filter, READ_PAGE_SIZE, input.consistency, |page| {
+                roles.extend(
+                    page.into_iter()
+                        .filter_map(|relationship| relationship.resource)
+                        .map(|resource| resource.object_id),
+                )
+            })
+            .await
+            .map_err(|e| RoleError::ListRolesError { msg: e.to_string() })?;
+
+        info!(role_count = roles.len(), "Server roles listed in AuthZed");
+        Ok(roles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::authzed::fake::{FakeSpiceDb, fast_retries};
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        let mut descriptor = CapabilityDescriptor::new();
+        descriptor.insert("admin".to_string(), 0x1);
+        descriptor.insert("send_message".to_string(), 0x80);
+        descriptor.insert("create_invitation".to_string(), 0x8);
+        descriptor
+    }
+
+    fn create_role_input(permissions_bitmask: u64) -> CreateRoleInput {
+        CreateRoleInput {
+            role_id: "role_123".to_string(),
+            server_id: "server_456".to_string(),
+            permissions_bitmask,
+        }
+    }
+
+    async fn repository(spicedb: &Arc<FakeSpiceDb>) -> AuthzedRoleRepository {
+        AuthzedRoleRepository::new(
+            spicedb.serve(fast_retries(0)).await,
+            Arc::new(create_test_descriptor()),
+        )
+    }
+
+    fn relations(spicedb: &FakeSpiceDb) -> Vec<String> {
+        let mut relations: Vec<_> = spicedb
+            .relationships()
+            .into_iter()
+            .map(|relationship| relationship.relation)
+            .collect();
+        relations.sort();
+        relations
+    }
+
+    #[tokio::test]
+    async fn test_create_replaces_permissions_in_one_write() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        repository.create(create_role_input(0x88)).await.unwrap();
+        let calls = spicedb.calls();
+
+        // Act
+        repository.create(create_role_input(0x1)).await.unwrap();
+
+        // Assert
+        assert_eq!(relations(&spicedb), vec!["administrator", "server"]);
+        // One read of the current permissions, one write
+        assert_eq!(spicedb.calls() - calls, 2);
+    }
+
+    #[tokio::test]
+    async fn test_create_retries_after_concurrent_change() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        repository.create(create_role_input(0x88)).await.unwrap();
+        let calls = spicedb.calls();
+        // The first write loses the race against another upsert
+        spicedb.fail_after(1, 1, Code::FailedPrecondition);
+
+        // Act
+        let result = repository.create(create_role_input(0x80)).await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(relations(&spicedb), vec!["message_sender", "server"]);
+        assert_eq!(spicedb.calls() - calls, 4);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_upserts_of_different_permissions_do_not_merge() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = repository(&spicedb).await;
+        repository.create(create_role_input(0x80)).await.unwrap();
+        let calls = spicedb.calls();
+        // Both upserts read send_message alone before either writes
+        spicedb.hold_writes(2);
+
+        // Act
+        let (with_admin, with_invitation) = tokio::join!(
+            repository.create(create_role_input(0x81)),
+            repository.create(create_role_input(0x88)),
+        );
+
+        // Assert
+        assert!(with_admin.is_ok());
+        assert!(with_invitation.is_ok());
+        let relations = relations(&spicedb);
+        assert!(
+            relations == vec!["administrator", "message_sender", "server"]
+                || relations == vec!["invitation_creator", "message_sender", "server"],
+            "{:?}",
+            relations
+        );
+        // Two reads and two writes, then one more read and write for the upsert that lost
+        assert_eq!(spicedb.calls() - calls, 6);
     }
 }
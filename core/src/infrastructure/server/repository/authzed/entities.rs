@@ -1,7 +1,10 @@
 use crate::{
     authzed::api::v1::{Relationship, RelationshipFilter},
     domain::server::entities::{CreateServerInput, DeleteServerInput},
-    infrastructure::common::authzed::entities::{Relation, server::Server, user::User},
+    infrastructure::common::authzed::{
+        beep::server,
+        entities::{relationship::Object, server::Server, user::User},
+    },
 };
 
 impl Into<(User, Server)> for CreateServerInput {
@@ -15,23 +18,12 @@ impl Into<(User, Server)> for CreateServerInput {
 impl From<CreateServerInput> for Relationship {
     fn from(input: CreateServerInput) -> Self {
         let (user, server): (User, Server) = input.into();
-        Relationship {
-            resource: Some(server.into()),
-            relation: Relation::Owner.into(),
-            subject: Some(user.into()),
-            ..Default::default()
-        }
+        server.relationship(server::Relation::Owner, user).build()
     }
 }
 
 impl From<DeleteServerInput> for RelationshipFilter {
     fn from(input: DeleteServerInput) -> Self {
-        RelationshipFilter {
-            resource_type: "server".to_string(),
-            optional_resource_id: input.server_id,
-            optional_resource_id_prefix: String::new(),
-            optional_relation: String::new(),
-            optional_subject_filter: None,
-        }
+        Server::from(input.server_id).filter().build()
     }
 }
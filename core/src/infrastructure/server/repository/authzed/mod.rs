@@ -1,12 +1,15 @@
 use crate::{
-    domain::server::{
-        ServerError,
-        entities::{CreateServerInput, DeleteServerInput},
-        port::ServerRepository,
+    domain::{
+        common::entities::{DeleteOutput, ZedToken},
+        server::{
+            ServerError,
+            entities::{CreateServerInput, DeleteServerInput},
+            port::ServerRepository,
+        },
     },
     infrastructure::authzed::AuthZedClient,
 };
-use tracing::{info, instrument};
+use tracing::{error, info, instrument};
 pub mod entities;
 
 #[derive(Clone)]
@@ -22,7 +25,7 @@ impl AuthzedServerRepository {
 
 impl ServerRepository for AuthzedServerRepository {
     #[instrument(skip(self), fields(server_id = %input.server_id, owner_id = %input.owner_id))]
-    async fn create(&self, input: CreateServerInput) -> Result<(), ServerError> {
+    async fn create(&self, input: CreateServerInput) -> Result<ZedToken, ServerError> {
         info!(
             server_id = %input.server_id,
             owner_id = %input.owner_id,
@@ -33,6 +36,7 @@ impl ServerRepository for AuthzedServerRepository {
             .authzed_client
             .create_relationship(input)
             .await
+            .map(ZedToken::from)
             .map_err(|e| ServerError::CreateServerError { msg: e.to_string() });
 
         match &result {
@@ -44,7 +48,7 @@ impl ServerRepository for AuthzedServerRepository {
     }
 
     #[instrument(skip(self), fields(server_id = %input.server_id))]
-    async fn delete(&self, input: DeleteServerInput) -> Result<(), ServerError> {
+    async fn delete(&self, input: DeleteServerInput) -> Result<DeleteOutput, ServerError> {
         info!(
             server_id = %input.server_id,
             "Deleting server relationships in AuthZed"
@@ -57,10 +61,49 @@ impl ServerRepository for AuthzedServerRepository {
             .map_err(|e| ServerError::DeleteServerError { msg: e.to_string() });
 
         match &result {
-            Ok(_) => info!("Server relationships deleted successfully in AuthZed"),
-            Err(e) => info!(error = ?e, "Failed to delete server relationships in AuthZed"),
+            Ok(summary) => info!(
+                deleted_count = summary.deleted_count,
+                batches = summary.batches,
+                "Server relationships deleted successfully in AuthZed"
+            ),
+            Err(e) => error!(error = ?e, "Failed to delete server relationships in AuthZed"),
         }
 
-        result
+        result.map(DeleteOutput::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::authzed::fake::{FakeSpiceDb, fast_retries};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_delete_returns_deleted_count() {
+        // Arrange
+        let spicedb = Arc::new(FakeSpiceDb::default());
+        let repository = AuthzedServerRepository::new(spicedb.serve(fast_retries(0)).await);
+        repository
+            .create(CreateServerInput {
+                owner_id: "user_1".to_string(),
+                server_id: "server_1".to_string(),
+            })
+            .await
+            .unwrap();
+        let created = spicedb.relationships().len() as u64;
+
+        // Act
+        let output = repository
+            .delete(DeleteServerInput {
+                server_id: "server_1".to_string(),
+            })
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(created > 0);
+        assert_eq!(output.deleted_count, created);
+        assert!(spicedb.relationships().is_empty());
     }
 }
@@ -50,6 +50,14 @@ app.kubernetes.io/name: {{ include "authz-listeners.name" . }}
 app.kubernetes.io/instance: {{ .Release.Name }}
 {{- end }}
 
+{{/*
+Selector labels of the permission change watcher, kept apart from the consumers' selector
+*/}}
+{{- define "authz-listeners.watcherSelectorLabels" -}}
+app.kubernetes.io/name: {{ include "authz-listeners.name" . }}-watcher
+app.kubernetes.io/instance: {{ .Release.Name }}
+{{- end }}
+
 {{/*
 Create the name of the service account to use
 */}}
@@ -71,3 +79,64 @@ Create the name of the secret to use
 {{- include "authz-listeners.fullname" . }}
 {{- end }}
 {{- end }}
+
+{{/*
+Environment shared by the consumers and the permission change watcher
+*/}}
+{{- define "authz-listeners.env" -}}
+- name: AUTHZED_ENDPOINT
+  valueFrom:
+    configMapKeyRef:
+      name: {{ include "authz-listeners.fullname" . }}
+      key: AUTHZED_ENDPOINT
+- name: AUTHZED_INSECURE
+  valueFrom:
+    configMapKeyRef:
+      name: {{ include "authz-listeners.fullname" . }}
+      key: AUTHZED_INSECURE
+- name: AUTHZED_TLS_MODE
+  valueFrom:
+    configMapKeyRef:
+      name: {{ include "authz-listeners.fullname" . }}
+      key: AUTHZED_TLS_MODE
+- name: RABBIT_CONSUMER_TAG_SUFFIX
+  valueFrom:
+    configMapKeyRef:
+      name: {{ include "authz-listeners.fullname" . }}
+      key: RABBIT_CONSUMER_TAG_SUFFIX
+- name: RUST_LOG
+  valueFrom:
+    configMapKeyRef:
+      name: {{ include "authz-listeners.fullname" . }}
+      key: RUST_LOG
+- name: SCHEMA_MODE
+  valueFrom:
+    configMapKeyRef:
+      name: {{ include "authz-listeners.fullname" . }}
+      key: SCHEMA_MODE
+- name: QUEUE_CONFIG_PATH
+  value: "/app/config/queues.json"
+- name: AUTHZED_TOKEN
+  valueFrom:
+    secretKeyRef:
+      name: {{ include "authz-listeners.secretName" . }}
+      key: AUTHZED_TOKEN
+- name: RABBIT_URI
+  valueFrom:
+    secretKeyRef:
+      name: {{ include "authz-listeners.secretName" . }}
+      key: RABBIT_URI
+{{- end }}
+
+{{/*
+Create the name of the checkpoint PersistentVolumeClaim to use
+*/}}
+{{- define "authz-listeners.checkpointClaimName" -}}
+{{- if .Values.checkpoint.existingClaim }}
+{{- .Values.checkpoint.existingClaim }}
+{{- else if .Values.checkpoint.persistence.enabled }}
+{{- include "authz-listeners.fullname" . }}-checkpoint
+{{- else }}
+{{- fail "config.permissionChanges.exchange requires checkpoint.existingClaim or checkpoint.persistence.enabled, otherwise a rescheduled watcher loses its checkpoint" }}
+{{- end }}
+{{- end }}
@@ -15,9 +15,14 @@
 
 use authz_core::{
     authzed::api::v1::{RelationshipFilter, SubjectFilter},
-    infrastructure::authzed::{AuthZedClient, AuthZedConfig},
+    domain::common::entities::Consistency,
+    infrastructure::authzed::{
+        AuthZedClient, AuthZedConfig, DEFAULT_BULK_CHECK_CHUNK_SIZE,
+        connection::AuthZedConnectionConfig, retry::RetryConfig, tls::AuthZedTlsConfig,
+    },
 };
 use clap::Parser;
+use std::num::NonZeroUsize;
 
 #[derive(Parser, Debug)]
 #[command(name = "query_servers")]
@@ -42,6 +47,9 @@ struct Args {
     /// SpiceDB authentication token
     #[arg(long, env = "AUTHZED_TOKEN", default_value = "foobar")]
     authzed_token: String,
+
+    #[command(flatten)]
+    tls: AuthZedTlsConfig,
 }
 
 #[tokio::main]
@@ -55,6 +63,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = AuthZedConfig {
         endpoint: args.authzed_endpoint.clone(),
         token: Some(args.authzed_token.clone()),
+        tls: args.tls.clone(),
+        channel_pool_size: NonZeroUsize::MIN,
+        bulk_check_chunk_size: DEFAULT_BULK_CHECK_CHUNK_SIZE,
+        retry: RetryConfig::default(),
+        connection: AuthZedConnectionConfig::default(),
     };
     let client = AuthZedClient::new(config).await?;
 
@@ -66,7 +79,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Query relationships
-    let relationships = client.read_relationships(filter).await?;
+    let relationships = client
+        .read_relationships(filter, Consistency::FullyConsistent)
+        .await?;
 
     if relationships.is_empty() {
         println!("❌ No relationships found matching the filter.");
@@ -1,5 +1,14 @@
-use authz_core::{application::create_repositories, domain::common::CoreError};
-use tracing::{debug, info, instrument};
+use authz_core::{
+    application::create_repositories,
+    domain::{common::CoreError, permission_change::service::PermissionChangeWatcher},
+    infrastructure::{
+        permission::repository::cached::CheckCacheConfig,
+        permission_change::{
+            checkpoint::file::FileCheckpointStore, source::authzed::AuthzedPermissionChangeSource,
+        },
+    },
+};
+use tracing::{debug, error, info, instrument};
 
 use crate::{
     config::Config,
@@ -11,15 +20,24 @@ use crate::{
             AppState,
             pool::{ConsumerPool, Consumers},
         },
+        permission_change::publisher::RabbitPermissionChangePublisher,
         permission_override::consumers::permission_override_consumers,
         role::consumers::role_consumers,
         server::consumers::server_consumers,
     },
 };
 
+type Watcher = PermissionChangeWatcher<
+    AuthzedPermissionChangeSource,
+    RabbitPermissionChangePublisher,
+    FileCheckpointStore,
+>;
+
 pub struct App {
     app_state: AppState,
     consumer_pool: ConsumerPool<AppState>,
+    permission_change_watcher: Option<Watcher>,
+    watcher_only: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -29,6 +47,10 @@ pub enum AppError {
     // Other error variants can be added here
     #[error("Repositories creation error: {0}")]
     RepositoriesCreationError(CoreError),
+    #[error("Schema error: {0}")]
+    SchemaError(CoreError),
+    #[error("Configuration error: {0}")]
+    ConfigError(String),
 }
 
 impl App {
@@ -40,6 +62,15 @@ impl App {
         let queue_config = config.queue_config().clone();
         let rabbit_config = config.rabbit_config;
         let authzed_config = config.authzed_config;
+        let schema_config = config.schema_config;
+        let permission_changes_exchange = config.permission_changes_exchange;
+        let watch_checkpoint_path = config.watch_checkpoint_path;
+        let watcher_only = config.watcher_only;
+        if watcher_only && permission_changes_exchange.is_none() {
+            return Err(AppError::ConfigError(
+                "WATCHER_ONLY requires PERMISSION_CHANGES_EXCHANGE".to_string(),
+            ));
+        }
 
         debug!("Connecting to RabbitMQ");
         let rabbit_client = RabbitClient::new(rabbit_config)
@@ -52,11 +83,43 @@ impl App {
         info!("Permissions descriptor created successfully");
 
         debug!("Creating authorization repositories");
-        let authz_repositories = create_repositories(authzed_config, permissions_descriptor)
-            .await
-            .map_err(|e| AppError::RepositoriesCreationError(e))?;
+        // Listeners only write relationships, so no check cache
+        let authz_repositories = create_repositories(
+            authzed_config,
+            &CheckCacheConfig::default(),
+            permissions_descriptor,
+        )
+        .await
+        .map_err(|e| AppError::RepositoriesCreationError(e))?;
         info!("Authorization repositories created successfully");
 
+        debug!(mode = ?schema_config.mode, "Checking SpiceDB schema");
+        authz_repositories
+            .ensure_schema(&schema_config)
+            .await
+            .map_err(|e| AppError::SchemaError(e))?;
+
+        let permission_change_watcher = match permission_changes_exchange {
+            Some(exchange) => {
+                debug!(exchange = %exchange, "Creating permission change watcher");
+                let channel = rabbit_client
+                    .create_publisher_channel(&exchange)
+                    .await
+                    .map_err(|e| AppError::RabbitError(e))?;
+                let watcher = PermissionChangeWatcher::new(
+                    AuthzedPermissionChangeSource::new(authz_repositories.authzed_client.clone()),
+                    RabbitPermissionChangePublisher::new(channel, exchange),
+                    FileCheckpointStore::new(watch_checkpoint_path),
+                );
+                info!("Permission change watcher created successfully");
+                Some(watcher)
+            }
+            None => {
+                info!("No permission changes exchange configured, watcher disabled");
+                None
+            }
+        };
+
         let app_state = AppState::from(authz_repositories);
 
         debug!("Registering consumers");
@@ -80,11 +143,27 @@ impl App {
         Ok(Self {
             app_state,
             consumer_pool,
+            permission_change_watcher,
+            watcher_only,
         })
     }
 
     #[instrument(skip_all)]
     pub async fn start(self) {
+        if let Some(watcher) = self.permission_change_watcher {
+            let run = async move {
+                if let Err(e) = watcher.run().await {
+                    error!(error = %e, "Permission change watcher stopped");
+                }
+            };
+            if self.watcher_only {
+                info!("Starting permission change watcher only");
+                return run.await;
+            }
+            info!("Starting permission change watcher");
+            tokio::spawn(run);
+        }
+
         info!("Starting consumer pool");
         self.consumer_pool.start().await;
     }
@@ -0,0 +1,70 @@
+//! Import an existing community into SpiceDB from a JSONL file, without going
+//! through RabbitMQ events one by one
+//!
+//! Run with:
+//! ```
+//! cargo run -p listeners --bin import_relationships -- community.jsonl
+//! cargo run -p listeners --bin import_relationships -- community.jsonl --dry-run
+//! ```
+
+use std::path::PathBuf;
+
+use authz_core::{
+    application::import::relationships_to_import,
+    infrastructure::authzed::{AuthZedClient, AuthZedConfig},
+};
+use clap::Parser;
+use listeners::{import::read_records, permissions_translations::BeepPermissions};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[derive(Parser, Debug)]
+#[command(name = "import_relationships")]
+#[command(about = "Bulk import servers, channels, roles, members and overrides into SpiceDB", long_about = None)]
+struct Args {
+    /// JSONL file with one server, channel, role, member or permission_override per line
+    file: PathBuf,
+
+    /// Parse the file and count the relationships without writing them
+    #[arg(long, default_value = "false")]
+    dry_run: bool,
+
+    #[command(flatten)]
+    authzed_config: AuthZedConfig,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let args = Args::parse();
+
+    let records = read_records(&args.file)?;
+    let record_count = records.len();
+    let relationships = relationships_to_import(records, &BeepPermissions::new().descriptor());
+    tracing::info!(
+        records = record_count,
+        relationships = relationships.len(),
+        "Import file converted"
+    );
+
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let client = AuthZedClient::new(args.authzed_config).await?;
+    let summary = client.import_relationships(relationships).await?;
+    tracing::info!(
+        loaded = summary.loaded,
+        path = ?summary.path,
+        "Relationships imported"
+    );
+
+    Ok(())
+}
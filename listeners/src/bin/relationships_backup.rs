@@ -0,0 +1,77 @@
+//! Back up the relationships of the Beep definitions to a file and restore them
+//!
+//! Run with:
+//! ```
+//! cargo run -p listeners --bin relationships_backup -- export backup.zed
+//! cargo run -p listeners --bin relationships_backup -- restore backup.zed
+//! ```
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+
+use authz_core::infrastructure::authzed::{
+    AuthZedClient, AuthZedConfig,
+    backup::{export_backup, restore_backup},
+};
+use clap::{Parser, Subcommand};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[derive(Parser, Debug)]
+#[command(name = "relationships_backup")]
+#[command(about = "Export or restore the server, channel, role and permission_override relationships", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    authzed_config: AuthZedConfig,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Stream every relationship to a new backup file
+    Export { file: PathBuf },
+    /// Load a backup file with the bulk import path
+    Restore { file: PathBuf },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let args = Args::parse();
+    let client = AuthZedClient::new(args.authzed_config).await?;
+
+    match args.command {
+        Command::Export { file } => {
+            let summary = export_backup(&client, BufWriter::new(File::create(&file)?)).await?;
+            tracing::info!(
+                file = %file.display(),
+                exported = summary.exported,
+                skipped = summary.skipped,
+                "Backup written"
+            );
+        }
+        Command::Restore { file } => {
+            let summary = restore_backup(&client, BufReader::new(File::open(&file)?)).await?;
+            tracing::info!(
+                file = %file.display(),
+                loaded = summary.loaded,
+                path = ?summary.path,
+                "Backup restored"
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use authz_core::infrastructure::authzed::AuthZedConfig;
+use authz_core::infrastructure::authzed::{AuthZedConfig, schema::SchemaConfig};
 use clap::{Parser, command};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -16,10 +16,30 @@ pub struct Config {
     #[command(flatten)]
     pub authzed_config: AuthZedConfig,
 
+    #[command(flatten)]
+    pub schema_config: SchemaConfig,
+
     /// Path to the queue configuration JSON file
     #[arg(long, env = "QUEUE_CONFIG_PATH", default_value = "config/queues.json")]
     pub queue_config_path: PathBuf,
 
+    /// Exchange receiving permission change events; the watcher is disabled when unset
+    #[arg(long, env = "PERMISSION_CHANGES_EXCHANGE")]
+    pub permission_changes_exchange: Option<String>,
+
+    /// File keeping the revision of the last published permission changes
+    #[arg(
+        long,
+        env = "WATCH_CHECKPOINT_PATH",
+        default_value = "data/watch_checkpoint"
+    )]
+    pub watch_checkpoint_path: PathBuf,
+
+    /// Only run the permission change watcher, without consuming any queue, so
+    /// a single replica can watch while the others consume
+    #[arg(long, env = "WATCHER_ONLY", default_value_t = false)]
+    pub watcher_only: bool,
+
     #[clap(skip)]
     pub queue_config: Option<QueueConfig>,
 }
@@ -98,7 +118,12 @@ pub struct PermissionOverrideQueues {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use authz_core::infrastructure::authzed::{
+        DEFAULT_BULK_CHECK_CHUNK_SIZE, connection::AuthZedConnectionConfig, retry::RetryConfig,
+        tls::AuthZedTlsConfig,
+    };
     use std::io::Write;
+    use std::num::NonZeroUsize;
     use tempfile::NamedTempFile;
 
     #[test]
@@ -137,8 +162,17 @@ mod tests {
             authzed_config: AuthZedConfig {
                 endpoint: "localhost:50051".to_string(),
                 token: Some("test_token".to_string()),
+                tls: AuthZedTlsConfig::default(),
+                channel_pool_size: NonZeroUsize::MIN,
+                bulk_check_chunk_size: DEFAULT_BULK_CHECK_CHUNK_SIZE,
+                retry: RetryConfig::default(),
+                connection: AuthZedConnectionConfig::default(),
             },
+            schema_config: SchemaConfig::default(),
             queue_config_path: temp_file.path().to_path_buf(),
+            permission_changes_exchange: None,
+            watch_checkpoint_path: PathBuf::from("data/watch_checkpoint"),
+            watcher_only: false,
             queue_config: None,
         };
 
@@ -174,8 +208,17 @@ mod tests {
             authzed_config: AuthZedConfig {
                 endpoint: "localhost:50051".to_string(),
                 token: Some("test_token".to_string()),
+                tls: AuthZedTlsConfig::default(),
+                channel_pool_size: NonZeroUsize::MIN,
+                bulk_check_chunk_size: DEFAULT_BULK_CHECK_CHUNK_SIZE,
+                retry: RetryConfig::default(),
+                connection: AuthZedConnectionConfig::default(),
             },
+            schema_config: SchemaConfig::default(),
             queue_config_path: PathBuf::from("nonexistent_file.json"),
+            permission_changes_exchange: None,
+            watch_checkpoint_path: PathBuf::from("data/watch_checkpoint"),
+            watcher_only: false,
             queue_config: None,
         };
 
@@ -198,8 +241,17 @@ mod tests {
             authzed_config: AuthZedConfig {
                 endpoint: "localhost:50051".to_string(),
                 token: Some("test_token".to_string()),
+                tls: AuthZedTlsConfig::default(),
+                channel_pool_size: NonZeroUsize::MIN,
+                bulk_check_chunk_size: DEFAULT_BULK_CHECK_CHUNK_SIZE,
+                retry: RetryConfig::default(),
+                connection: AuthZedConnectionConfig::default(),
             },
+            schema_config: SchemaConfig::default(),
             queue_config_path: temp_file.path().to_path_buf(),
+            permission_changes_exchange: None,
+            watch_checkpoint_path: PathBuf::from("data/watch_checkpoint"),
+            watcher_only: false,
             queue_config: None,
         };
 
@@ -0,0 +1,181 @@
+//! JSONL format of the `import_relationships` CLI: one object of the community per line,
+//! tagged by `type`, e.g. `{"type": "member", "user_id": "user_1", "role_id": "role_1"}`
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use authz_core::{
+    application::import::ImportRecord,
+    domain::{
+        channel::entities::CreateChannelInput,
+        permission_override::entities::{CreatePermissionOverrideInput, OverrideTarget},
+        role::entities::{AssignMemberInput, CreateRoleInput},
+        server::entities::CreateServerInput,
+    },
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ImportFileError {
+    #[error("Could not read import file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid record on line {line}: {msg}")]
+    InvalidRecord { line: usize, msg: String },
+}
+
+/// One line of the import file
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImportLine {
+    Server {
+        server_id: String,
+        owner_id: String,
+    },
+    Channel {
+        channel_id: String,
+        server_id: String,
+    },
+    Role {
+        role_id: String,
+        server_id: String,
+        permissions_bitmask: u64,
+    },
+    Member {
+        user_id: String,
+        role_id: String,
+    },
+    PermissionOverride {
+        override_id: String,
+        channel_id: String,
+        permission_bitmask: u64,
+        is_allow: bool,
+        target: ImportTarget,
+    },
+}
+
+/// Target of a permission override: `{"user": "user_1"}` or `{"role": "role_1"}`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportTarget {
+    User(String),
+    Role(String),
+}
+
+impl From<ImportLine> for ImportRecord {
+    fn from(line: ImportLine) -> Self {
+        match line {
+            ImportLine::Server {
+                server_id,
+                owner_id,
+            } => ImportRecord::Server(CreateServerInput {
+                owner_id,
+                server_id,
+            }),
+            ImportLine::Channel {
+                channel_id,
+                server_id,
+            } => ImportRecord::Channel(CreateChannelInput {
+                channel_id,
+                server_id,
+            }),
+            ImportLine::Role {
+                role_id,
+                server_id,
+                permissions_bitmask,
+            } => ImportRecord::Role(CreateRoleInput {
+                role_id,
+                server_id,
+                permissions_bitmask,
+            }),
+            ImportLine::Member { user_id, role_id } => {
+                ImportRecord::Member(AssignMemberInput { user_id, role_id })
+            }
+            ImportLine::PermissionOverride {
+                override_id,
+                channel_id,
+                permission_bitmask,
+                is_allow,
+                target,
+            } => ImportRecord::PermissionOverride(CreatePermissionOverrideInput {
+                override_id,
+                channel_id,
+                permission_bitmask,
+                is_allow,
+                target: match target {
+                    ImportTarget::User(user_id) => OverrideTarget::User(user_id),
+                    ImportTarget::Role(role_id) => OverrideTarget::Role(role_id),
+                },
+            }),
+        }
+    }
+}
+
+/// Parse every record of a JSONL import, skipping blank lines
+pub fn parse_records(reader: impl BufRead) -> Result<Vec<ImportRecord>, ImportFileError> {
+    let mut records = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: ImportLine =
+            serde_json::from_str(&line).map_err(|e| ImportFileError::InvalidRecord {
+                line: index + 1,
+                msg: e.to_string(),
+            })?;
+        records.push(record.into());
+    }
+    Ok(records)
+}
+
+/// Read the records of a JSONL import file
+pub fn read_records(path: &Path) -> Result<Vec<ImportRecord>, ImportFileError> {
+    parse_records(BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_records_of_every_type() {
+        let file = r#"{"type": "server", "server_id": "server_1", "owner_id": "user_1"}
+{"type": "channel", "channel_id": "channel_1", "server_id": "server_1"}
+
+{"type": "role", "role_id": "role_1", "server_id": "server_1", "permissions_bitmask": 129}
+{"type": "member", "user_id": "user_2", "role_id": "role_1"}
+{"type": "permission_override", "override_id": "override_1", "channel_id": "channel_1", "permission_bitmask": 64, "is_allow": false, "target": {"role": "role_1"}}
+"#;
+
+        let records = parse_records(file.as_bytes()).unwrap();
+
+        assert_eq!(records.len(), 5);
+        assert!(matches!(
+            &records[4],
+            ImportRecord::PermissionOverride(CreatePermissionOverrideInput {
+                target: OverrideTarget::Role(role_id),
+                is_allow: false,
+                ..
+            }) if role_id == "role_1"
+        ));
+    }
+
+    #[test]
+    fn test_parse_records_reports_the_invalid_line() {
+        let file = r#"{"type": "member", "user_id": "user_2", "role_id": "role_1"}
+{"type": "emoji", "emoji_id": "emoji_1"}
+"#;
+
+        let error = parse_records(file.as_bytes()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ImportFileError::InvalidRecord { line: 2, .. }
+        ));
+    }
+}
@@ -2,7 +2,11 @@ use std::convert::Infallible;
 use std::future::Future;
 
 use clap::Parser;
-use lapin::{Channel, Connection, Consumer, options::BasicConsumeOptions, types::FieldTable};
+use lapin::{
+    Channel, Connection, Consumer, ExchangeKind,
+    options::{BasicConsumeOptions, ConfirmSelectOptions, ExchangeDeclareOptions},
+    types::FieldTable,
+};
 use prost::Message;
 use thiserror::Error;
 use tokio_stream::StreamExt;
@@ -98,6 +102,47 @@ impl RabbitClient {
         Ok(())
     }
 
+    /// Open a channel in confirm mode publishing to `exchange`, declared as a
+    /// durable topic exchange if it does not exist yet
+    #[instrument(skip(self))]
+    pub async fn create_publisher_channel(
+        &self,
+        exchange: &str,
+    ) -> Result<Channel, RabbitClientError> {
+        debug!("Creating RabbitMQ publisher channel");
+        let channel = self.connection.create_channel().await.map_err(|e| {
+            error!("Failed to create RabbitMQ publisher channel: {}", e);
+            RabbitClientError::StartupError { msg: e.to_string() }
+        })?;
+
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await
+            .map_err(|e| {
+                error!("Failed to enable publisher confirms: {}", e);
+                RabbitClientError::StartupError { msg: e.to_string() }
+            })?;
+
+        channel
+            .exchange_declare(
+                exchange,
+                ExchangeKind::Topic,
+                ExchangeDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await
+            .map_err(|e| {
+                error!("Failed to declare exchange {}: {}", exchange, e);
+                RabbitClientError::StartupError { msg: e.to_string() }
+            })?;
+
+        info!(exchange = %exchange, "RabbitMQ publisher channel created successfully");
+        Ok(channel)
+    }
+
     #[instrument(skip(self), fields(queue_name = %queue_name))]
     async fn create_consumer(&self, queue_name: String) -> Result<Consumer, RabbitClientError> {
         let consumer_tag = format!("{}-{}", queue_name, self.consumer_tag_suffix);
@@ -1,7 +1,8 @@
 pub mod app;
 pub mod config;
+pub mod import;
 pub mod lapin;
 pub mod rabbit;
-pub mod permissions_translations;
+pub use authz_core::infrastructure::common::permissions_translations;
 // Re-export commonly used items for tests
 pub use config::Config;
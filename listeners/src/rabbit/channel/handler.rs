@@ -26,8 +26,9 @@ pub async fn create_channel(state: Arc<AppState>, input: ChannelCreated) -> Resu
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 channel_id = %input.channel_id,
                 server_id = %input.server_id,
                 "Successfully created channel"
@@ -60,8 +61,10 @@ pub async fn delete_channel(state: Arc<AppState>, input: ChannelDeleted) -> Resu
         })
         .await
     {
-        Ok(_) => {
+        Ok(output) => {
             info!(
+                zed_token = %output.deleted_at,
+                deleted_count = output.deleted_count,
                 channel_id = %input.channel_id,
                 "Successfully deleted channel"
             );
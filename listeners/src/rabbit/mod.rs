@@ -1,5 +1,6 @@
 pub mod channel;
 pub mod consumers;
+pub mod permission_change;
 pub mod permission_override;
 pub mod role;
 pub mod server;
@@ -0,0 +1,239 @@
+use authz_core::domain::{
+    common::entities::ZedToken,
+    permission::entities::Resource,
+    permission_change::entities::{
+        ChangeOperation, Grantee, PermissionChange, PermissionChangeEvent,
+    },
+    permission_override::entities::OverrideTarget,
+};
+use serde::Serialize;
+
+/// JSON body of a message published on the permission changes exchange
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionChangeMessage {
+    /// SpiceDB revision of the change, identical for changes written together.
+    /// A batch may be redelivered after a restart, so consumers should dedupe on it.
+    pub revision: String,
+    pub operation: &'static str,
+    #[serde(flatten)]
+    pub event: PermissionChangeEventMessage,
+    /// Human readable summary, e.g. "role R lost send_message on server S"
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PermissionChangeEventMessage {
+    ServerOwner {
+        server_id: String,
+        user_id: String,
+    },
+    RoleMember {
+        role_id: String,
+        user_id: String,
+    },
+    RoleServer {
+        role_id: String,
+        server_id: String,
+    },
+    ChannelServer {
+        channel_id: String,
+        server_id: String,
+    },
+    OverrideChannel {
+        override_id: String,
+        channel_id: String,
+    },
+    OverrideTarget {
+        override_id: String,
+        target_type: &'static str,
+        target_id: String,
+        effect: &'static str,
+    },
+    Permission {
+        resource_type: &'static str,
+        resource_id: String,
+        permission: String,
+        grantee_type: &'static str,
+        grantee_id: String,
+        effect: &'static str,
+    },
+    Unmapped {
+        resource: String,
+        relation: String,
+        subject: String,
+    },
+}
+
+impl PermissionChangeMessage {
+    pub fn new(change: &PermissionChange, revision: &ZedToken) -> Self {
+        let operation = match change.operation {
+            ChangeOperation::Added => "added",
+            ChangeOperation::Removed => "removed",
+        };
+
+        Self {
+            revision: revision.to_string(),
+            operation,
+            event: change.event.clone().into(),
+            description: change.to_string(),
+        }
+    }
+
+    /// Routing key on the topic exchange, e.g. `permission.role_member.removed`
+    pub fn routing_key(&self) -> String {
+        format!("permission.{}.{}", self.event.event_type(), self.operation)
+    }
+}
+
+impl PermissionChangeEventMessage {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            PermissionChangeEventMessage::ServerOwner { .. } => "server_owner",
+            PermissionChangeEventMessage::RoleMember { .. } => "role_member",
+            PermissionChangeEventMessage::RoleServer { .. } => "role_server",
+            PermissionChangeEventMessage::ChannelServer { .. } => "channel_server",
+            PermissionChangeEventMessage::OverrideChannel { .. } => "override_channel",
+            PermissionChangeEventMessage::OverrideTarget { .. } => "override_target",
+            PermissionChangeEventMessage::Permission { .. } => "permission",
+            PermissionChangeEventMessage::Unmapped { .. } => "unmapped",
+        }
+    }
+}
+
+fn effect(is_allow: bool) -> &'static str {
+    if is_allow { "allow" } else { "deny" }
+}
+
+impl From<PermissionChangeEvent> for PermissionChangeEventMessage {
+    fn from(event: PermissionChangeEvent) -> Self {
+        match event {
+            PermissionChangeEvent::ServerOwner { server_id, user_id } => {
+                PermissionChangeEventMessage::ServerOwner { server_id, user_id }
+            }
+            PermissionChangeEvent::RoleMember { role_id, user_id } => {
+                PermissionChangeEventMessage::RoleMember { role_id, user_id }
+            }
+            PermissionChangeEvent::RoleServer { role_id, server_id } => {
+                PermissionChangeEventMessage::RoleServer { role_id, server_id }
+            }
+            PermissionChangeEvent::ChannelServer {
+                channel_id,
+                server_id,
+            } => PermissionChangeEventMessage::ChannelServer {
+                channel_id,
+                server_id,
+            },
+            PermissionChangeEvent::OverrideChannel {
+                override_id,
+                channel_id,
+            } => PermissionChangeEventMessage::OverrideChannel {
+                override_id,
+                channel_id,
+            },
+            PermissionChangeEvent::OverrideTarget {
+                override_id,
+                target,
+                is_allow,
+            } => {
+                let (target_type, target_id) = match target {
+                    OverrideTarget::User(id) => ("user", id),
+                    OverrideTarget::Role(id) => ("role", id),
+                };
+                PermissionChangeEventMessage::OverrideTarget {
+                    override_id,
+                    target_type,
+                    target_id,
+                    effect: effect(is_allow),
+                }
+            }
+            PermissionChangeEvent::Permission {
+                resource,
+                permission,
+                grantee,
+                is_allow,
+            } => {
+                let (resource_type, resource_id) = match resource {
+                    Resource::Server(id) => ("server", id),
+                    Resource::Channel(id) => ("channel", id),
+                    Resource::Role(id) => ("role", id),
+                };
+                let (grantee_type, grantee_id) = match grantee {
+                    Grantee::User(id) => ("user", id),
+                    Grantee::Role(id) => ("role", id),
+                    Grantee::Override(id) => ("permission_override", id),
+                };
+                PermissionChangeEventMessage::Permission {
+                    resource_type,
+                    resource_id,
+                    permission,
+                    grantee_type,
+                    grantee_id,
+                    effect: effect(is_allow),
+                }
+            }
+            PermissionChangeEvent::Unmapped {
+                resource,
+                relation,
+                subject,
+            } => PermissionChangeEventMessage::Unmapped {
+                resource,
+                relation,
+                subject,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_message_json_and_routing_key() {
+        let change = PermissionChange {
+            operation: ChangeOperation::Removed,
+            event: PermissionChangeEvent::Permission {
+                resource: Resource::Server("server_1".to_string()),
+                permission: "send_message".to_string(),
+                grantee: Grantee::Role("role_1".to_string()),
+                is_allow: true,
+            },
+        };
+
+        let message = PermissionChangeMessage::new(&change, &ZedToken("rev_1".to_string()));
+        let json = serde_json::to_value(&message).unwrap();
+
+        assert_eq!(message.routing_key(), "permission.permission.removed");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "revision": "rev_1",
+                "operation": "removed",
+                "type": "permission",
+                "resource_type": "server",
+                "resource_id": "server_1",
+                "permission": "send_message",
+                "grantee_type": "role",
+                "grantee_id": "role_1",
+                "effect": "allow",
+                "description": "role role_1 lost send_message on server server_1",
+            })
+        );
+    }
+
+    #[test]
+    fn test_override_channel_routing_key() {
+        let change = PermissionChange {
+            operation: ChangeOperation::Removed,
+            event: PermissionChangeEvent::OverrideChannel {
+                override_id: "override_1".to_string(),
+                channel_id: "channel_1".to_string(),
+            },
+        };
+
+        let message = PermissionChangeMessage::new(&change, &ZedToken("rev_1".to_string()));
+
+        assert_eq!(message.routing_key(), "permission.override_channel.removed");
+    }
+}
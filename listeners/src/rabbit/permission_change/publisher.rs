@@ -0,0 +1,62 @@
+use authz_core::domain::permission_change::{
+    PermissionChangeError, entities::PermissionChangeBatch, port::PermissionChangePublisher,
+};
+use lapin::{BasicProperties, Channel, options::BasicPublishOptions};
+use tracing::{debug, instrument};
+
+use crate::rabbit::permission_change::entities::PermissionChangeMessage;
+
+/// Persistent delivery mode, so messages survive a broker restart
+const PERSISTENT: u8 = 2;
+
+/// Publishes each permission change as a JSON message on a topic exchange.
+/// The channel must be in confirm mode: a batch only counts as published once
+/// the broker acknowledged every message of it.
+#[derive(Clone)]
+pub struct RabbitPermissionChangePublisher {
+    channel: Channel,
+    exchange: String,
+}
+
+impl RabbitPermissionChangePublisher {
+    pub fn new(channel: Channel, exchange: String) -> Self {
+        Self { channel, exchange }
+    }
+}
+
+impl PermissionChangePublisher for RabbitPermissionChangePublisher {
+    #[instrument(skip_all, fields(exchange = %self.exchange, revision = %batch.revision))]
+    async fn publish(&self, batch: &PermissionChangeBatch) -> Result<(), PermissionChangeError> {
+        for change in &batch.changes {
+            let message = PermissionChangeMessage::new(change, &batch.revision);
+            let routing_key = message.routing_key();
+            let payload = serde_json::to_vec(&message)
+                .map_err(|e| PermissionChangeError::PublishError { msg: e.to_string() })?;
+
+            let confirmation = self
+                .channel
+                .basic_publish(
+                    self.exchange.as_str(),
+                    routing_key.as_str(),
+                    BasicPublishOptions::default(),
+                    &payload,
+                    BasicProperties::default()
+                        .with_content_type("application/json".into())
+                        .with_delivery_mode(PERSISTENT),
+                )
+                .await
+                .map_err(|e| PermissionChangeError::PublishError { msg: e.to_string() })?
+                .await
+                .map_err(|e| PermissionChangeError::PublishError { msg: e.to_string() })?;
+
+            if confirmation.is_nack() {
+                return Err(PermissionChangeError::PublishError {
+                    msg: format!("Broker rejected message {}", routing_key),
+                });
+            }
+            debug!(routing_key = %routing_key, description = %message.description, "Permission change published");
+        }
+
+        Ok(())
+    }
+}
@@ -72,8 +72,9 @@ pub async fn upsert_permission_override(
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 override_id = %input.override_id,
                 channel_id = %input.channel_id,
                 "Successfully created/updated permission override"
@@ -118,8 +119,9 @@ pub async fn delete_permission_override(
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 override_id = %input.override_id,
                 "Successfully deleted permission override"
             );
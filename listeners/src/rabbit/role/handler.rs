@@ -36,8 +36,9 @@ pub async fn upsert_role(state: Arc<AppState>, input: UpsertRole) -> Result<(),
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 role_id = %input.role_id,
                 server_id = %input.server_id,
                 "Successfully created/updated role"
@@ -70,8 +71,9 @@ pub async fn delete_role(state: Arc<AppState>, input: DeleteRole) -> Result<(),
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 role_id = %input.role_id,
                 "Successfully deleted role"
             );
@@ -107,8 +109,9 @@ pub async fn assign_member_to_role(
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 user_id = %input.user_id,
                 role_id = %input.role_id,
                 "Successfully assigned member to role"
@@ -146,8 +149,9 @@ pub async fn remove_member_from_role(
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 user_id = %input.user_id,
                 role_id = %input.role_id,
                 "Successfully removed member from role"
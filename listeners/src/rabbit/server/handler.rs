@@ -26,8 +26,9 @@ pub async fn create_server(state: Arc<AppState>, input: CreateServer) -> Result<
         })
         .await
     {
-        Ok(_) => {
+        Ok(zed_token) => {
             info!(
+                zed_token = %zed_token,
                 server_id = %input.server_id,
                 owner_id = %input.owner_id,
                 "Successfully created server"
@@ -60,8 +61,10 @@ pub async fn delete_server(state: Arc<AppState>, input: DeleteServer) -> Result<
         })
         .await
     {
-        Ok(_) => {
+        Ok(output) => {
             info!(
+                zed_token = %output.deleted_at,
+                deleted_count = output.deleted_count,
                 server_id = %input.server_id,
                 "Successfully deleted server"
             );